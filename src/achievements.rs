@@ -0,0 +1,306 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    consts::TICK_RATE,
+    difficulty::DifficultyPreset,
+    errors::DodgerError,
+    objects::GoodObjectValue,
+    stats::{LevelTally, Statistics},
+    storage::{load_json, save_json},
+};
+
+/// What has to be done to unlock an achievement.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Condition {
+    /// Complete a number of levels over all games.
+    LevelsCompleted(u32),
+    /// Complete a level (by index) without losing a life.
+    FlawlessLevel(usize),
+    /// Catch a number of objects of a value over all games.
+    CaughtOfValue(GoodObjectValue, u32),
+    /// Catch a number of "good" objects of any value over all games.
+    CaughtTotal(u32),
+    /// Catch a number of "good" objects in a row within a level.
+    Combo(u32),
+    /// Play for a number of seconds over all games.
+    TimePlayed(u64),
+    /// Complete the last level.
+    Victory,
+    /// Complete the last level on a difficulty preset.
+    VictoryOn(DifficultyPreset),
+}
+
+/// **An achievement that can be unlocked by a profile.**
+///
+/// ## Fields
+/// * `id`: identifier stored in the profile's files; it must not change once released.
+/// * `name`: the name shown in the notification and the gallery.
+/// * `description`: what has to be done, shown in the gallery.
+/// * `condition`: the condition that unlocks the achievement.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Achievement {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub condition: Condition,
+}
+
+/// The achievements of the game, in the order they are shown in the gallery.
+pub const ACHIEVEMENTS: [Achievement; 12] = [
+    Achievement {
+        id: "first_level",
+        name: "First Steps",
+        description: "Complete a level",
+        condition: Condition::LevelsCompleted(1),
+    },
+    Achievement {
+        id: "flawless_level_2",
+        name: "Untouchable",
+        description: "Finish Level 2 without losing a life",
+        condition: Condition::FlawlessLevel(1),
+    },
+    Achievement {
+        id: "flawless_level_4",
+        name: "Flawless Finale",
+        description: "Finish Level 4 without losing a life",
+        condition: Condition::FlawlessLevel(3),
+    },
+    Achievement {
+        id: "combo_10",
+        name: "On a Roll",
+        description: "Catch 10 objects in a row",
+        condition: Condition::Combo(10),
+    },
+    Achievement {
+        id: "combo_30",
+        name: "Unstoppable",
+        description: "Catch 30 objects in a row",
+        condition: Condition::Combo(30),
+    },
+    Achievement {
+        id: "high_value_100",
+        name: "Treasure Hunter",
+        description: "Catch 100 high-value objects",
+        condition: Condition::CaughtOfValue(GoodObjectValue::High, 100),
+    },
+    Achievement {
+        id: "caught_1000",
+        name: "Collector",
+        description: "Catch 1000 good objects",
+        condition: Condition::CaughtTotal(1000),
+    },
+    Achievement {
+        id: "coop_25",
+        name: "Team Player",
+        description: "Catch 25 co-op objects",
+        condition: Condition::CaughtOfValue(GoodObjectValue::Coop, 25),
+    },
+    // The game has no endless mode and a run lasts less than 5 minutes, so this counts the time played over all games.
+    Achievement {
+        id: "played_5_minutes",
+        name: "Warming Up",
+        description: "Play for 5 minutes in total",
+        condition: Condition::TimePlayed(5 * 60),
+    },
+    Achievement {
+        id: "played_1_hour",
+        name: "Marathon",
+        description: "Play for an hour in total",
+        condition: Condition::TimePlayed(60 * 60),
+    },
+    Achievement {
+        id: "victory",
+        name: "Champion",
+        description: "Complete every level",
+        condition: Condition::Victory,
+    },
+    Achievement {
+        id: "victory_hardcore",
+        name: "Hardcore Hero",
+        description: "Complete every level on Hardcore",
+        condition: Condition::VictoryOn(DifficultyPreset::Hardcore),
+    },
+];
+
+/// **How a level ended, for the achievements checked when it ends.**
+///
+/// ## Fields
+/// * `level`: index of the level.
+/// * `completed`: whether the level was completed.
+/// * `lives_lost`: whether any life was lost during the level.
+/// * `victory`: whether the last level was completed.
+/// * `preset`: the difficulty preset of the run.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct LevelOutcome {
+    pub level: usize,
+    pub completed: bool,
+    pub lives_lost: bool,
+    pub victory: bool,
+    pub preset: DifficultyPreset,
+}
+
+/// **What a profile has done, achievements are checked against.**
+///
+/// ## Fields
+/// * `statistics`: the lifetime statistics, without the level being played.
+/// * `tally`: what happened during the level being played, if it was not added to the statistics yet.
+/// * `outcome`: how the level ended, once it has ended.
+#[derive(Clone, Copy, Debug)]
+pub struct Accomplishments<'a> {
+    pub statistics: &'a Statistics,
+    pub tally: &'a LevelTally,
+    pub outcome: Option<LevelOutcome>,
+}
+
+impl Condition {
+    /// **Checks whether the condition is met by what a profile has done.**
+    pub fn is_met(&self, done: &Accomplishments) -> bool {
+        let stats = done.statistics;
+        let tally = done.tally;
+        match *self {
+            Condition::LevelsCompleted(count) => stats.levels_completed >= count,
+            Condition::FlawlessLevel(level) => done
+                .outcome
+                .is_some_and(|o| o.completed && o.level == level && !o.lives_lost),
+            Condition::CaughtOfValue(value, count) => {
+                stats.caught.get(value) + tally.caught.get(value) >= count
+            }
+            Condition::CaughtTotal(count) => stats.caught.total() + tally.caught.total() >= count,
+            Condition::Combo(count) => stats.best_combo.max(tally.best_combo) >= count,
+            Condition::TimePlayed(secs) => stats.ticks_played + tally.ticks >= secs * TICK_RATE,
+            Condition::Victory => done.outcome.is_some_and(|o| o.victory),
+            Condition::VictoryOn(preset) => done
+                .outcome
+                .is_some_and(|o| o.victory && o.preset == preset),
+        }
+    }
+}
+
+/// **The achievements unlocked by a profile, persisted between game sessions.**
+///
+/// ## Fields
+/// `unlocked`: identifiers of the unlocked achievements, in the order they were unlocked.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UnlockedAchievements {
+    pub unlocked: Vec<String>,
+}
+
+impl UnlockedAchievements {
+    /// **Returns the path of the achievements file in a directory.**
+    pub fn path(dir: &Path) -> PathBuf {
+        dir.join("achievements.json")
+    }
+
+    /// **Loads the achievements stored in a directory.**
+    ///
+    /// ## Parameters
+    /// `dir`: directory containing the achievements file.
+    ///
+    /// ## Returns
+    /// A result containing the stored achievements (or none if nothing is stored yet), or a `DodgerError` if the file cannot be read or parsed.
+    pub fn load(dir: &Path) -> Result<Self, DodgerError> {
+        Ok(load_json(&Self::path(dir))?.unwrap_or_default())
+    }
+
+    /// **Stores the achievements in a directory.**
+    ///
+    /// ## Parameters
+    /// `dir`: directory to store the achievements file in.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the achievements are stored successfully, or a `DodgerError` if the file cannot be written.
+    pub fn save(&self, dir: &Path) -> Result<(), DodgerError> {
+        save_json(&Self::path(dir), self)
+    }
+
+    /// **Checks whether an achievement is unlocked.**
+    pub fn is_unlocked(&self, achievement: &Achievement) -> bool {
+        self.unlocked.iter().any(|id| id == achievement.id)
+    }
+
+    /// **Unlocks the achievements whose conditions are met.**
+    ///
+    /// ## Parameters
+    /// `done`: what the profile has done.
+    ///
+    /// ## Returns
+    /// The newly unlocked achievements, in gallery order.
+    pub fn unlock(&mut self, done: &Accomplishments) -> Vec<&'static Achievement> {
+        let newly_unlocked: Vec<&'static Achievement> = ACHIEVEMENTS
+            .iter()
+            .filter(|achievement| {
+                !self.is_unlocked(achievement) && achievement.condition.is_met(done)
+            })
+            .collect();
+        self.unlocked.extend(
+            newly_unlocked
+                .iter()
+                .map(|achievement| achievement.id.to_string()),
+        );
+        newly_unlocked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{levels::get_levels, simulation::WorldEvent};
+
+    #[test]
+    fn test_achievement_ids_are_unique_and_levels_exist() {
+        let level_count = get_levels().len();
+        for (i, achievement) in ACHIEVEMENTS.iter().enumerate() {
+            assert!(ACHIEVEMENTS[..i].iter().all(|a| a.id != achievement.id));
+            if let Condition::FlawlessLevel(level) = achievement.condition {
+                assert!(
+                    level < level_count,
+                    "{} needs a missing level",
+                    achievement.id
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_achievements_unlock_once() {
+        let mut statistics = Statistics::default();
+        statistics.caught.high = 99;
+        let mut tally = LevelTally::default();
+        tally.record_tick(&[WorldEvent::Caught {
+            player: 0,
+            value: Some(GoodObjectValue::High),
+        }]);
+        let mut unlocked = UnlockedAchievements::default();
+
+        let during_level = Accomplishments {
+            statistics: &statistics,
+            tally: &tally,
+            outcome: None,
+        };
+        let names: Vec<_> = unlocked
+            .unlock(&during_level)
+            .iter()
+            .map(|a| a.name)
+            .collect();
+        assert_eq!(names, ["Treasure Hunter"]);
+        assert!(unlocked.unlock(&during_level).is_empty());
+
+        statistics.record_level(1, &tally, true);
+        let level_end = Accomplishments {
+            statistics: &statistics,
+            tally: &LevelTally::default(),
+            outcome: Some(LevelOutcome {
+                level: 1,
+                completed: true,
+                lives_lost: false,
+                victory: false,
+                preset: DifficultyPreset::Normal,
+            }),
+        };
+        let names: Vec<_> = unlocked.unlock(&level_end).iter().map(|a| a.name).collect();
+        assert_eq!(names, ["First Steps", "Untouchable"]);
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::VecDeque;
+
+use crate::consts::TICK_RATE;
+
+/// Length of the sliding window the player's performance is measured over.
+pub const ADAPTIVE_WINDOW_SECS: u64 = 15;
+/// Lowest difficulty factor: objects fall at 80% speed and spawn 20% less often.
+pub const ADAPTIVE_MIN_FACTOR: f32 = 0.8;
+/// Highest difficulty factor: objects fall at 120% speed and spawn 20% more often.
+pub const ADAPTIVE_MAX_FACTOR: f32 = 1.2;
+/// Largest change of the difficulty factor per second, so adjustments stay gradual.
+pub const ADAPTIVE_RATE_PER_SEC: f32 = 0.02;
+/// Catch rate at which the difficulty is left unchanged.
+pub const ADAPTIVE_TARGET_CATCH_RATE: f32 = 0.6;
+/// How much each life lost in the window lowers the target factor.
+pub const ADAPTIVE_LIFE_WEIGHT: f32 = 0.1;
+/// How much each catch of the current combo raises the target factor.
+pub const ADAPTIVE_COMBO_WEIGHT: f32 = 0.01;
+/// Longest combo taken into account.
+pub const ADAPTIVE_MAX_COMBO: u32 = 10;
+
+/// Something the player did that tells how well they are doing.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Performance {
+    Caught,
+    Missed,
+    LifeLost,
+}
+
+/// **What happened during the sliding window.**
+///
+/// ## Fields
+/// * `caught`: "good" objects caught.
+/// * `missed`: "good" objects that fell out of the window.
+/// * `lives_lost`: lives lost to "bad" objects.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct WindowStats {
+    pub caught: u32,
+    pub missed: u32,
+    pub lives_lost: u32,
+}
+
+impl WindowStats {
+    /// **Returns the share of "good" objects caught, or `None` if no object was caught or missed yet.**
+    pub fn catch_rate(&self) -> Option<f32> {
+        let attempts = self.caught + self.missed;
+        (attempts > 0).then(|| self.caught as f32 / attempts as f32)
+    }
+}
+
+/// **Adjusts the difficulty of a level to how well the player is doing.**
+///
+/// ## Fields
+/// * `events`: the catches, misses and lives lost of the sliding window, with the tick they happened on.
+/// * `combo`: number of "good" objects caught in a row, without missing one or losing a life.
+/// * `factor`: the current difficulty factor; the fall speed is multiplied and the spawn interval divided by it.
+///
+/// ## Behavior
+/// Every tick, the factor moves by at most `ADAPTIVE_RATE_PER_SEC` per second towards a target computed from the
+/// catch rate, lives lost and combo of the last `ADAPTIVE_WINDOW_SECS` seconds, and stays between
+/// `ADAPTIVE_MIN_FACTOR` and `ADAPTIVE_MAX_FACTOR`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct AdaptiveDifficulty {
+    events: VecDeque<(u64, Performance)>,
+    pub combo: u32,
+    factor: f32,
+}
+
+impl AdaptiveDifficulty {
+    /// **Creates an adjuster starting at a difficulty factor.**
+    ///
+    /// ## Parameters
+    /// `factor`: the starting factor, e.g. the factor reached at the end of the previous level; clamped to the bounds.
+    pub fn new(factor: f32) -> Self {
+        Self {
+            events: VecDeque::new(),
+            combo: 0,
+            factor: factor.clamp(ADAPTIVE_MIN_FACTOR, ADAPTIVE_MAX_FACTOR),
+        }
+    }
+
+    /// **Returns the current difficulty factor.**
+    pub fn factor(&self) -> f32 {
+        self.factor
+    }
+
+    /// **Records a "good" object caught on a tick.**
+    pub fn record_catch(&mut self, tick: u64) {
+        self.combo += 1;
+        self.events.push_back((tick, Performance::Caught));
+    }
+
+    /// **Records a "good" object that fell out of the window on a tick.**
+    pub fn record_miss(&mut self, tick: u64) {
+        self.combo = 0;
+        self.events.push_back((tick, Performance::Missed));
+    }
+
+    /// **Records a life lost on a tick.**
+    pub fn record_life_lost(&mut self, tick: u64) {
+        self.combo = 0;
+        self.events.push_back((tick, Performance::LifeLost));
+    }
+
+    /// **Returns what happened during the sliding window.**
+    pub fn window_stats(&self) -> WindowStats {
+        let mut stats = WindowStats::default();
+        for (_, performance) in &self.events {
+            match performance {
+                Performance::Caught => stats.caught += 1,
+                Performance::Missed => stats.missed += 1,
+                Performance::LifeLost => stats.lives_lost += 1,
+            }
+        }
+        stats
+    }
+
+    /// **Returns the factor the adjuster is moving towards.**
+    ///
+    /// ## Behavior
+    /// The target rises with the catch rate above `ADAPTIVE_TARGET_CATCH_RATE` and with the combo, and drops with
+    /// each life lost; without any catches or misses in the window, the catch rate counts as on target.
+    pub fn target_factor(&self) -> f32 {
+        let stats = self.window_stats();
+        let catch_rate = stats.catch_rate().unwrap_or(ADAPTIVE_TARGET_CATCH_RATE);
+        let skill = catch_rate
+            - ADAPTIVE_TARGET_CATCH_RATE
+            - stats.lives_lost as f32 * ADAPTIVE_LIFE_WEIGHT
+            + self.combo.min(ADAPTIVE_MAX_COMBO) as f32 * ADAPTIVE_COMBO_WEIGHT;
+        (1.0 + skill).clamp(ADAPTIVE_MIN_FACTOR, ADAPTIVE_MAX_FACTOR)
+    }
+
+    /// **Advances the adjuster to a tick.**
+    ///
+    /// ## Parameters
+    /// `tick`: the current simulation tick.
+    ///
+    /// ## Behavior
+    /// Forgets events older than the sliding window and moves the factor one tick's worth towards the target.
+    pub fn update(&mut self, tick: u64) {
+        let window_ticks = ADAPTIVE_WINDOW_SECS * TICK_RATE;
+        while self
+            .events
+            .front()
+            .is_some_and(|(event_tick, _)| tick.saturating_sub(*event_tick) >= window_ticks)
+        {
+            self.events.pop_front();
+        }
+
+        let max_change = ADAPTIVE_RATE_PER_SEC / TICK_RATE as f32;
+        let change = (self.target_factor() - self.factor).clamp(-max_change, max_change);
+        self.factor = (self.factor + change).clamp(ADAPTIVE_MIN_FACTOR, ADAPTIVE_MAX_FACTOR);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn play(
+        adaptive: &mut AdaptiveDifficulty,
+        secs: u64,
+        mut on_second: impl FnMut(&mut AdaptiveDifficulty, u64),
+    ) {
+        for tick in 1..=secs * TICK_RATE {
+            if tick % TICK_RATE == 0 {
+                on_second(adaptive, tick);
+            }
+            adaptive.update(tick);
+        }
+    }
+
+    #[test]
+    fn test_factor_follows_performance_within_bounds() {
+        let mut good = AdaptiveDifficulty::new(1.0);
+        play(&mut good, 60, |adaptive, tick| adaptive.record_catch(tick));
+        assert!(good.factor() > 1.0);
+        assert!(good.factor() <= ADAPTIVE_MAX_FACTOR);
+
+        let mut struggling = AdaptiveDifficulty::new(1.0);
+        play(&mut struggling, 60, |adaptive, tick| {
+            adaptive.record_miss(tick);
+            if tick % (5 * TICK_RATE) == 0 {
+                adaptive.record_life_lost(tick);
+            }
+        });
+        assert_eq!(struggling.factor(), ADAPTIVE_MIN_FACTOR);
+        assert_eq!(struggling.combo, 0);
+    }
+
+    #[test]
+    fn test_window_forgets_old_events() {
+        let mut adaptive = AdaptiveDifficulty::new(2.0);
+        assert_eq!(adaptive.factor(), ADAPTIVE_MAX_FACTOR);
+        adaptive.record_life_lost(1);
+        adaptive.record_catch(2);
+        assert_eq!(adaptive.window_stats().lives_lost, 1);
+        assert_eq!(adaptive.window_stats().catch_rate(), Some(1.0));
+
+        adaptive.update(ADAPTIVE_WINDOW_SECS * TICK_RATE + 2);
+        assert_eq!(adaptive.window_stats(), WindowStats::default());
+    }
+}
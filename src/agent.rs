@@ -0,0 +1,342 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    consts::{TICK_RATE, WINDOW_WIDTH},
+    difficulty::DifficultyPreset,
+    errors::DodgerError,
+    levels::Level,
+    modes::PlayMode,
+    simulation::{PlayerInput, SpriteSizes, World},
+};
+
+/// Number of positions along the window the heuristic bot considers moving to.
+const BOT_CANDIDATES: usize = 40;
+/// Score penalty of standing under a "bad" object.
+const BOT_BAD_PENALTY: f32 = 500.0;
+/// Score penalty for each pixel the bot has to travel, so it does not wander without a reason.
+const BOT_DISTANCE_WEIGHT: f32 = 0.01;
+/// A "bad" object landing on the bot within this many ticks makes it dash away.
+const BOT_PANIC_TICKS: f32 = 12.0;
+/// Number of ticks the random bot keeps the same input.
+const RANDOM_BOT_HOLD_TICKS: u64 = 30;
+
+/// **Something that plays Dodger by choosing the input of a player on each tick.**
+pub trait Agent {
+    /// **Chooses the input of a player for the next tick.**
+    ///
+    /// ## Parameters
+    /// * `world`: the current state of the level: the players, the falling objects with their kinds, and the fall speed.
+    /// * `player_index`: index of the player controlled by the agent.
+    ///
+    /// ## Returns
+    /// The input to apply to the player during the next tick.
+    fn act(&mut self, world: &World, player_index: usize) -> PlayerInput;
+}
+
+/// **A built-in bot that tries to catch "good" objects and dodge "bad" ones.**
+///
+/// ## Behavior
+/// * On every tick, rates a set of positions along the bottom of the window and moves toward the best one.
+/// * A position gains the value of each "good" object the bot can reach there before it lands, weighted by how soon it lands.
+/// * A position loses points for each "bad" object that would hit the bot there or on its way there.
+/// * Dashes when a "bad" object is about to land on it.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HeuristicBot;
+
+impl HeuristicBot {
+    /// **Rates a position the bot could move to.**
+    ///
+    /// ## Parameters
+    /// * `world`: the current state of the level.
+    /// * `player_index`: index of the player controlled by the bot.
+    /// * `target_x`: the x-coordinate of the position.
+    ///
+    /// ## Returns
+    /// The rating of the position; higher is better.
+    fn rate_position(world: &World, player_index: usize, target_x: f32) -> f32 {
+        let player = &world.players[player_index].player;
+        let speed = player.speed / TICK_RATE as f32;
+        let player = player.rect();
+        let fall_speed = world.fall_speed.max(f32::EPSILON);
+
+        let reach_ticks = (target_x - player.x).abs() / speed;
+        let path_start = target_x.min(player.x);
+        let path_end = target_x.max(player.x) + player.w;
+        let mut rating = -(target_x - player.x).abs() * BOT_DISTANCE_WEIGHT;
+
+        for obj in &world.objects {
+            if obj.hit_tick.is_some() {
+                continue;
+            }
+            let rect = obj.rect();
+            if rect.y > player.y + player.h {
+                continue;
+            }
+
+            // Ticks until the object reaches the top of the player, and until it has passed the player.
+            let arrival = ((player.y - (rect.y + rect.h)) / fall_speed).max(0.0);
+            let departure = (player.y + player.h - rect.y) / fall_speed;
+            let urgency = 1.0 + arrival / TICK_RATE as f32;
+            let over_target = rect.x < target_x + player.w && rect.x + rect.w > target_x;
+
+            if obj.is_good {
+                if over_target && reach_ticks <= arrival {
+                    let score = obj.good_object_value.map_or(10, |value| value.score());
+                    rating += score as f32 / urgency;
+                }
+            } else {
+                let over_path = rect.x < path_end && rect.x + rect.w > path_start;
+                let hits_at_target = over_target && departure >= reach_ticks;
+                let hits_on_the_way = over_path && arrival <= reach_ticks;
+                if hits_at_target || hits_on_the_way {
+                    rating -= BOT_BAD_PENALTY / urgency;
+                }
+            }
+        }
+        rating
+    }
+
+    /// **Checks whether a "bad" object is about to land on the player.**
+    fn is_in_danger(world: &World, player_index: usize) -> bool {
+        let player = world.players[player_index].player.rect();
+        let fall_speed = world.fall_speed.max(f32::EPSILON);
+        world.objects.iter().any(|obj| {
+            let rect = obj.rect();
+            !obj.is_good
+                && obj.hit_tick.is_none()
+                && rect.x < player.x + player.w
+                && rect.x + rect.w > player.x
+                && rect.y <= player.y + player.h
+                && (player.y - (rect.y + rect.h)) / fall_speed < BOT_PANIC_TICKS
+        })
+    }
+}
+
+impl Agent for HeuristicBot {
+    fn act(&mut self, world: &World, player_index: usize) -> PlayerInput {
+        let Some(state) = world.players.get(player_index) else {
+            return PlayerInput::default();
+        };
+        let player = state.player.rect();
+        let max_x = WINDOW_WIDTH - player.w;
+
+        let mut best = (Self::rate_position(world, player_index, player.x), player.x);
+        for i in 0..=BOT_CANDIDATES {
+            let x = max_x * i as f32 / BOT_CANDIDATES as f32;
+            let rating = Self::rate_position(world, player_index, x);
+            if rating > best.0 {
+                best = (rating, x);
+            }
+        }
+
+        let speed = state.player.speed / TICK_RATE as f32;
+        let axis = ((best.1 - player.x) / speed).clamp(-1.0, 1.0);
+        PlayerInput {
+            axis,
+            dash: axis != 0.0 && Self::is_in_danger(world, player_index),
+        }
+    }
+}
+
+/// **A bot that does not move, showing how a level plays out for an absent player.**
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IdleBot;
+
+impl Agent for IdleBot {
+    fn act(&mut self, _world: &World, _player_index: usize) -> PlayerInput {
+        PlayerInput::default()
+    }
+}
+
+/// **A bot that moves randomly, changing its direction every `RANDOM_BOT_HOLD_TICKS` ticks.**
+///
+/// ## Fields
+/// * `rng`: the seeded random number generator choosing the moves.
+/// * `input`: the input currently held.
+#[derive(Clone, Debug)]
+pub struct RandomBot {
+    rng: StdRng,
+    input: PlayerInput,
+}
+
+impl RandomBot {
+    /// **Creates a random bot.**
+    ///
+    /// ## Parameters
+    /// `seed`: seed of the bot's random number generator.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+            input: PlayerInput::default(),
+        }
+    }
+}
+
+impl Agent for RandomBot {
+    fn act(&mut self, world: &World, _player_index: usize) -> PlayerInput {
+        if world.tick.is_multiple_of(RANDOM_BOT_HOLD_TICKS) {
+            self.input = PlayerInput {
+                axis: self.rng.gen_range(-1.0..=1.0),
+                dash: self.rng.gen_bool(0.1),
+            };
+        }
+        self.input
+    }
+}
+
+/// **The outcome of a level played by an agent.**
+///
+/// ## Fields
+/// * `survived`: whether the agent still had lives when the time was up.
+/// * `score`: the score of the level.
+/// * `lives_lost`: number of lives lost during the level.
+/// * `ticks`: number of simulation ticks played.
+/// * `good_spawned`: number of "good" objects spawned.
+/// * `good_catchable`: number of "good" objects the player could reach from where they were when the object spawned.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct EpisodeResult {
+    pub survived: bool,
+    pub score: i32,
+    pub lives_lost: u8,
+    pub ticks: u64,
+    pub good_spawned: usize,
+    pub good_catchable: usize,
+}
+
+/// **Plays a single-player level headlessly with an agent.**
+///
+/// ## Parameters
+/// * `level`: the level to play.
+/// * `preset`: the difficulty preset.
+/// * `agent`: the agent controlling the player.
+/// * `seed`: seed of the level's random number generator.
+///
+/// ## Returns
+/// A result containing the outcome of the level, or a `DodgerError` if the world cannot be created.
+pub fn run_episode(
+    level: &Level,
+    preset: DifficultyPreset,
+    agent: &mut dyn Agent,
+    seed: u64,
+) -> Result<EpisodeResult, DodgerError> {
+    let mut world = World::new(
+        level,
+        PlayMode::Single,
+        preset,
+        SpriteSizes::default(),
+        seed,
+    )?;
+    let starting_lives = world.players[0].lives;
+    let (mut good_spawned, mut good_catchable) = (0, 0);
+
+    while !world.is_lost() && !world.is_time_up() {
+        let input = agent.act(&world, 0);
+        let spawned = world.spawned();
+        world.step(&[input]);
+
+        // Spawned objects are appended, so a spawn during the tick is the last object.
+        if world.spawned() > spawned {
+            if let Some(obj) = world.objects.last().filter(|obj| obj.is_good) {
+                good_spawned += 1;
+                if world.is_catchable(obj, 0) {
+                    good_catchable += 1;
+                }
+            }
+        }
+    }
+
+    Ok(EpisodeResult {
+        survived: !world.is_lost(),
+        score: world.level_score(),
+        lives_lost: starting_lives - world.players[0].lives,
+        ticks: world.tick,
+        good_spawned,
+        good_catchable,
+    })
+}
+
+/// **Measures the difficulty of a level as the share of runs an agent survives.**
+///
+/// ## Parameters
+/// * `level`: the level to play.
+/// * `preset`: the difficulty preset.
+/// * `agent`: the agent controlling the player.
+/// * `runs`: number of runs, played with the seeds `seed`, `seed + 1`, ...
+/// * `seed`: seed of the first run.
+///
+/// ## Returns
+/// A result containing the survival rate from 0.0 to 1.0, or a `DodgerError` if a run cannot be played.
+pub fn survival_rate(
+    level: &Level,
+    preset: DifficultyPreset,
+    agent: &mut dyn Agent,
+    runs: u64,
+    seed: u64,
+) -> Result<f32, DodgerError> {
+    if runs == 0 {
+        return Ok(0.0);
+    }
+    let mut survived = 0;
+    for run in 0..runs {
+        if run_episode(level, preset, agent, seed.wrapping_add(run))?.survived {
+            survived += 1;
+        }
+    }
+    Ok(survived as f32 / runs as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        levels::get_levels,
+        objects::{FallingObject, GoodObjectValue},
+    };
+    use ggez::mint::Point2;
+
+    fn world_with(obj_x: f32, is_good: bool) -> World {
+        let mut world = World::new(
+            &get_levels()[0],
+            PlayMode::Single,
+            DifficultyPreset::Normal,
+            SpriteSizes::default(),
+            0,
+        )
+        .unwrap();
+        let value = is_good.then_some(GoodObjectValue::High);
+        let size = world.sizes.object(value);
+        let obj =
+            FallingObject::new(Point2::from_slice(&[obj_x, 300.0]), size, is_good, value).unwrap();
+        world.objects.push(obj);
+        world
+    }
+
+    #[test]
+    fn test_bot_moves_toward_good_object() {
+        let world = world_with(100.0, true);
+        let input = HeuristicBot.act(&world, 0);
+        assert!(world.players[0].player.coords.x > 100.0);
+        assert!(input.axis < 0.0);
+    }
+
+    #[test]
+    fn test_bot_dodges_bad_object_above() {
+        let mut world = world_with(0.0, false);
+        world.objects[0].coords.x = world.players[0].player.coords.x + 40.0;
+        let input = HeuristicBot.act(&world, 0);
+        assert_ne!(input.axis, 0.0);
+    }
+
+    #[test]
+    fn test_bot_survives_first_level() {
+        let rate = survival_rate(
+            &get_levels()[0],
+            DifficultyPreset::Normal,
+            &mut HeuristicBot,
+            5,
+            0,
+        )
+        .unwrap();
+        assert!(rate >= 0.8, "survival rate {}", rate);
+    }
+}
@@ -0,0 +1,164 @@
+use serde::Serialize;
+
+use crate::{
+    agent::{run_episode, Agent, HeuristicBot, IdleBot, RandomBot},
+    consts::TICK_RATE,
+    difficulty::DifficultyPreset,
+    errors::DodgerError,
+    levels::Level,
+};
+
+/// The bots available for difficulty analysis.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BotKind {
+    Heuristic,
+    Random,
+    Idle,
+}
+
+impl BotKind {
+    /// **Parses the name of a bot (`heuristic`, `random` or `idle`).**
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "heuristic" => Some(BotKind::Heuristic),
+            "random" => Some(BotKind::Random),
+            "idle" => Some(BotKind::Idle),
+            _ => None,
+        }
+    }
+
+    /// **Creates a bot of this kind.**
+    ///
+    /// ## Parameters
+    /// `seed`: seed of the bot's own randomness, if it has any.
+    pub fn create(self, seed: u64) -> Box<dyn Agent> {
+        match self {
+            BotKind::Heuristic => Box::new(HeuristicBot),
+            BotKind::Random => Box::new(RandomBot::new(seed)),
+            BotKind::Idle => Box::new(IdleBot),
+        }
+    }
+}
+
+/// **Statistics of a level played many times by a bot.**
+///
+/// ## Fields
+/// * `level`: number of the level (1-based).
+/// * `fall_speed`: fall speed at the start of the level.
+/// * `runs`: number of runs played.
+/// * `survival_rate`: share of runs the bot survived, from 0.0 to 1.0.
+/// * `average_score`: average score of a run.
+/// * `lives_lost_per_minute`: lives lost per minute of play.
+/// * `catchable_percent`: percentage of "good" objects the bot could reach from where it was when they spawned.
+#[derive(Clone, Copy, PartialEq, Debug, Serialize)]
+pub struct LevelStats {
+    pub level: usize,
+    pub fall_speed: f32,
+    pub runs: u64,
+    pub survival_rate: f32,
+    pub average_score: f32,
+    pub lives_lost_per_minute: f32,
+    pub catchable_percent: f32,
+}
+
+/// **Plays a level headlessly many times with a bot and sums up the results.**
+///
+/// ## Parameters
+/// * `level_index`: index of the level (0-based).
+/// * `level`: the level to play.
+/// * `preset`: the difficulty preset.
+/// * `bot`: the kind of bot playing.
+/// * `runs`: number of runs, played with the seeds `seed`, `seed + 1`, ...
+/// * `seed`: seed of the first run.
+///
+/// ## Returns
+/// A result containing the statistics of the level, or a `DodgerError` if a run cannot be played.
+pub fn analyze_level(
+    level_index: usize,
+    level: &Level,
+    preset: DifficultyPreset,
+    bot: BotKind,
+    runs: u64,
+    seed: u64,
+) -> Result<LevelStats, DodgerError> {
+    let (mut survived, mut score, mut lives_lost, mut ticks) = (0u64, 0i64, 0u64, 0u64);
+    let (mut good_spawned, mut good_catchable) = (0usize, 0usize);
+
+    for run in 0..runs {
+        let run_seed = seed.wrapping_add(run);
+        let result = run_episode(level, preset, bot.create(run_seed).as_mut(), run_seed)?;
+        survived += u64::from(result.survived);
+        score += i64::from(result.score);
+        lives_lost += u64::from(result.lives_lost);
+        ticks += result.ticks;
+        good_spawned += result.good_spawned;
+        good_catchable += result.good_catchable;
+    }
+
+    let ratio = |part: f64, whole: f64| if whole > 0.0 { part / whole } else { 0.0 };
+    let minutes = ticks as f64 / TICK_RATE as f64 / 60.0;
+    Ok(LevelStats {
+        level: level_index + 1,
+        fall_speed: level.fall_speed,
+        runs,
+        survival_rate: ratio(survived as f64, runs as f64) as f32,
+        average_score: ratio(score as f64, runs as f64) as f32,
+        lives_lost_per_minute: ratio(lives_lost as f64, minutes) as f32,
+        catchable_percent: (ratio(good_catchable as f64, good_spawned as f64) * 100.0) as f32,
+    })
+}
+
+/// **Formats level statistics as CSV with a header row.**
+pub fn stats_to_csv(stats: &[LevelStats]) -> String {
+    let mut csv = String::from(
+        "level,fall_speed,runs,survival_rate,average_score,lives_lost_per_minute,catchable_percent\n",
+    );
+    for row in stats {
+        csv.push_str(&format!(
+            "{},{},{},{:.3},{:.1},{:.3},{:.1}\n",
+            row.level,
+            row.fall_speed,
+            row.runs,
+            row.survival_rate,
+            row.average_score,
+            row.lives_lost_per_minute,
+            row.catchable_percent
+        ));
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::levels::get_levels;
+
+    #[test]
+    fn test_idle_bot_is_worse_than_heuristic_bot() {
+        let level = &get_levels()[3];
+        let idle = analyze_level(3, level, DifficultyPreset::Normal, BotKind::Idle, 3, 0).unwrap();
+        let heuristic =
+            analyze_level(3, level, DifficultyPreset::Normal, BotKind::Heuristic, 3, 0).unwrap();
+
+        assert_eq!(heuristic.level, 4);
+        assert!(heuristic.average_score > idle.average_score);
+        assert!(heuristic.lives_lost_per_minute <= idle.lives_lost_per_minute);
+        assert!((0.0..=100.0).contains(&idle.catchable_percent));
+    }
+
+    #[test]
+    fn test_csv_has_a_row_per_level() {
+        let stats = analyze_level(
+            0,
+            &get_levels()[0],
+            DifficultyPreset::Normal,
+            BotKind::Random,
+            2,
+            5,
+        )
+        .unwrap();
+        let csv = stats_to_csv(&[stats, stats]);
+        assert_eq!(csv.lines().count(), 3);
+        assert!(csv.lines().nth(1).unwrap().starts_with("1,2.5,2,"));
+    }
+}
@@ -0,0 +1,11 @@
+use std::io::{stdin, stdout};
+
+use dodger::env::serve;
+
+/// Runs the headless training environment, reading JSON requests from stdin and writing the responses to stdout.
+fn main() {
+    if let Err(err) = serve(stdin().lock(), stdout().lock()) {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
+}
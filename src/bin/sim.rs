@@ -0,0 +1,122 @@
+use std::process::exit;
+
+use dodger::{
+    analysis::{analyze_level, stats_to_csv, BotKind},
+    difficulty::DifficultyPreset,
+    levels::get_levels,
+};
+
+const USAGE: &str = "Usage: dodger-sim [--runs N] [--seed S] [--bot heuristic|random|idle]
+                  [--difficulty easy|normal|hard|hardcore] [--level L] [--format csv|json]
+
+Plays every level (or only level L, 1-based) N times without a window and prints
+survival rate, average score, lives lost per minute and the percentage of catchable objects.";
+
+/// Options of the simulator, read from the command line.
+struct Options {
+    runs: u64,
+    seed: u64,
+    bot: BotKind,
+    preset: DifficultyPreset,
+    level: Option<usize>,
+    json: bool,
+}
+
+/// **Parses the command line arguments.**
+///
+/// ## Returns
+/// The options, or a message describing the invalid argument.
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        runs: 100,
+        seed: 0,
+        bot: BotKind::Heuristic,
+        preset: DifficultyPreset::Normal,
+        level: None,
+        json: false,
+    };
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--runs" => options.runs = value()?.parse().map_err(|_| "invalid --runs")?,
+            "--seed" => options.seed = value()?.parse().map_err(|_| "invalid --seed")?,
+            "--bot" => {
+                options.bot = BotKind::parse(&value()?).ok_or("unknown --bot")?;
+            }
+            "--difficulty" => {
+                let name = value()?;
+                options.preset = DifficultyPreset::ALL
+                    .into_iter()
+                    .find(|preset| preset.name().eq_ignore_ascii_case(&name))
+                    .ok_or("unknown --difficulty")?;
+            }
+            "--level" => {
+                let level: usize = value()?.parse().map_err(|_| "invalid --level")?;
+                options.level = Some(level.checked_sub(1).ok_or("levels start at 1")?);
+            }
+            "--format" => {
+                options.json = match value()?.as_str() {
+                    "csv" => false,
+                    "json" => true,
+                    _ => return Err("unknown --format".to_string()),
+                }
+            }
+            "--help" | "-h" => return Err(String::new()),
+            _ => return Err(format!("unknown argument {}", arg)),
+        }
+    }
+    Ok(options)
+}
+
+fn main() {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            if !message.is_empty() {
+                eprintln!("{}\n", message);
+            }
+            eprintln!("{}", USAGE);
+            exit(2);
+        }
+    };
+
+    let levels = get_levels();
+    if options.level.is_some_and(|level| level >= levels.len()) {
+        eprintln!("There are only {} levels", levels.len());
+        exit(2);
+    }
+
+    let mut stats = Vec::new();
+    for (i, level) in levels.iter().enumerate() {
+        if options.level.is_some_and(|only| only != i) {
+            continue;
+        }
+        match analyze_level(
+            i,
+            level,
+            options.preset,
+            options.bot,
+            options.runs,
+            options.seed,
+        ) {
+            Ok(level_stats) => stats.push(level_stats),
+            Err(err) => {
+                eprintln!("{}", err);
+                exit(1);
+            }
+        }
+    }
+
+    if options.json {
+        match serde_json::to_string_pretty(&stats) {
+            Ok(json) => println!("{}", json),
+            Err(err) => {
+                eprintln!("{}", err);
+                exit(1);
+            }
+        }
+    } else {
+        print!("{}", stats_to_csv(&stats));
+    }
+}
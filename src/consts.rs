@@ -1,10 +1,27 @@
 use ggez::graphics::Color;
 
 pub const LIVES: u8 = 5;
+pub const MAX_CONTINUES: u8 = 2;
+pub const CONTINUE_SCORE_PENALTY: i32 = 100;
 pub const LEVEL_DURATION_SECS: u64 = 40;
 pub const FALLING_OBJECT_UPDATE_MILLIS: u64 = 800;
+pub const DASH_COOLDOWN_MILLIS: u64 = 1000;
+pub const BLINK_MILLIS: u64 = 1000;
+pub const TOAST_SECS: f32 = 3.0;
+pub const COUNTDOWN_SECS: f32 = 3.0;
+pub const GO_SECS: f32 = 0.5;
 pub const YELLOW: Color = Color::new(153.0, 153.0, 0.0, 1.0);
 
+// Simulation
+pub const TICK_RATE: u64 = 60;
+pub const MAX_CATCH_UP_TICKS: u32 = 10;
+
+// Network
+pub const DEFAULT_PORT: u16 = 7878;
+pub const INPUT_DELAY_TICKS: usize = 3;
+pub const CONNECT_TIMEOUT_MILLIS: u64 = 3000;
+pub const MAX_ADDRESS_LENGTH: usize = 64;
+
 // Dimensions
 pub const WINDOW_WIDTH: f32 = 1000.0;
 pub const WINDOW_HEIGHT: f32 = 800.0;
@@ -14,8 +31,13 @@ pub const TEXT_BUTTON_HEIGHT: f32 = 50.0;
 
 pub const BUTTON_TEXT_SIZE: f32 = 26.0;
 pub const TEXT_SIZE: f32 = 34.0;
+pub const DEBUG_TEXT_SIZE: f32 = 20.0;
 
 pub const BUTTON_SPACING: f32 = 10.0;
 
+pub const PLAYER_SPEED: f32 = 600.0;
+pub const STICK_DEADZONE: f32 = 0.2;
+pub const DASH_DISTANCE: f32 = 120.0;
+
 pub const PLAYER_SCALING: f32 = 0.4;
 pub const OBJECT_SCALING: f32 = 0.08;
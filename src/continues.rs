@@ -0,0 +1,118 @@
+use crate::{
+    consts::{CONTINUE_SCORE_PENALTY, MAX_CONTINUES},
+    highscores::{HighScore, HighScores},
+};
+
+/// **The continues spent during a run to keep playing after losing every life.**
+///
+/// ## Fields
+/// `used`: the number of continues spent, at most `MAX_CONTINUES`.
+///
+/// ## Behavior
+/// Continuing the lost level and retrying it both spend a continue; a run that spent any is not ranked in the high scores.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Continues {
+    pub used: u8,
+}
+
+impl Continues {
+    /// **Returns the number of continues left in the run.**
+    pub fn left(&self) -> u8 {
+        MAX_CONTINUES.saturating_sub(self.used)
+    }
+
+    /// **Spends a continue.**
+    ///
+    /// ## Parameters
+    /// `run_score`: the score of the run when it was lost.
+    ///
+    /// ## Returns
+    /// The points the continue costs, `CONTINUE_SCORE_PENALTY` or the whole score if it is lower,
+    /// or `None` if no continue is left.
+    pub fn spend(&mut self, run_score: i32) -> Option<i32> {
+        if self.left() == 0 {
+            return None;
+        }
+        self.used += 1;
+        Some(run_score.clamp(0, CONTINUE_SCORE_PENALTY))
+    }
+
+    /// **Checks whether a run counts for the high scores.**
+    ///
+    /// ## Parameters
+    /// `adaptive`: whether the run used adaptive difficulty.
+    ///
+    /// ## Behavior
+    /// Runs with adaptive difficulty or continues are not ranked.
+    pub fn is_ranked(&self, adaptive: bool) -> bool {
+        !adaptive && self.used == 0
+    }
+
+    /// **Adds a finished run to the high scores, if it is ranked.**
+    ///
+    /// ## Parameters
+    /// * `adaptive`: whether the run used adaptive difficulty.
+    /// * `entry`: the finished run.
+    /// * `high_scores`: the high score table.
+    ///
+    /// ## Returns
+    /// The rank of the run in the table (0-based), or `None` if it is not ranked or not good enough to be kept.
+    pub fn add_to_high_scores(
+        &self,
+        adaptive: bool,
+        entry: HighScore,
+        high_scores: &mut HighScores,
+    ) -> Option<usize> {
+        if !self.is_ranked(adaptive) {
+            return None;
+        }
+        high_scores.add(entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::DifficultyPreset;
+
+    #[test]
+    fn test_continues_are_limited_and_cost_points() {
+        let mut continues = Continues::default();
+        assert_eq!(continues.spend(500), Some(CONTINUE_SCORE_PENALTY));
+        assert_eq!(continues.left(), MAX_CONTINUES - 1);
+        assert_eq!(Continues::default().spend(40), Some(40));
+        assert_eq!(Continues::default().spend(-10), Some(0));
+
+        while continues.spend(500).is_some() {}
+        assert_eq!(continues.used, MAX_CONTINUES);
+        assert_eq!(continues.spend(500), None);
+    }
+
+    #[test]
+    fn test_retried_lost_run_is_not_added_to_high_scores() {
+        let entry = HighScore {
+            score: 300,
+            level: 2,
+            difficulty: DifficultyPreset::Normal,
+        };
+        let mut high_scores = HighScores::default();
+        let mut continues = Continues::default();
+        assert_eq!(
+            continues.add_to_high_scores(true, entry, &mut high_scores),
+            None
+        );
+
+        // Retrying the level the run was lost on spends a continue.
+        continues.spend(entry.score);
+        assert_eq!(
+            continues.add_to_high_scores(false, entry, &mut high_scores),
+            None
+        );
+        assert!(high_scores.entries.is_empty());
+
+        assert_eq!(
+            Continues::default().add_to_high_scores(false, entry, &mut high_scores),
+            Some(0)
+        );
+    }
+}
@@ -0,0 +1,219 @@
+use serde::{Deserialize, Serialize};
+
+use crate::consts::{LEVEL_DURATION_SECS, LIVES};
+
+/// **A value that changes over the time elapsed in a level.**
+#[derive(Clone, PartialEq, Debug)]
+pub enum Curve {
+    /// The same value for the whole level.
+    Constant(f32),
+    /// Changes evenly from `start` when the level begins to `end` when its time is up.
+    Linear { start: f32, end: f32 },
+    /// Jumps between values: each `(seconds, value)` step applies from its time until the next step.
+    /// The first step's value also applies before its time.
+    Stepped(Vec<(f32, f32)>),
+    /// Changes evenly between `(seconds, value)` keyframes and keeps the first and last values outside of them.
+    Keyframes(Vec<(f32, f32)>),
+}
+
+impl Curve {
+    /// **Returns the value of the curve at a point of the level.**
+    ///
+    /// ## Parameters
+    /// `elapsed_secs`: seconds elapsed since the level started.
+    ///
+    /// ## Behavior
+    /// `Stepped` and `Keyframes` curves expect their points sorted by time; an empty curve gives 1.0.
+    pub fn value_at(&self, elapsed_secs: f32) -> f32 {
+        match self {
+            Curve::Constant(value) => *value,
+            Curve::Linear { start, end } => {
+                let progress = (elapsed_secs / LEVEL_DURATION_SECS as f32).clamp(0.0, 1.0);
+                start + (end - start) * progress
+            }
+            Curve::Stepped(steps) => steps
+                .iter()
+                .take_while(|(secs, _)| *secs <= elapsed_secs)
+                .last()
+                .or(steps.first())
+                .map_or(1.0, |(_, value)| *value),
+            Curve::Keyframes(keyframes) => {
+                let Some(&(first_secs, first_value)) = keyframes.first() else {
+                    return 1.0;
+                };
+                if elapsed_secs <= first_secs {
+                    return first_value;
+                }
+                for pair in keyframes.windows(2) {
+                    let ((from_secs, from_value), (to_secs, to_value)) = (pair[0], pair[1]);
+                    if elapsed_secs <= to_secs {
+                        let span = (to_secs - from_secs).max(f32::EPSILON);
+                        return from_value
+                            + (to_value - from_value) * (elapsed_secs - from_secs) / span;
+                    }
+                }
+                keyframes.last().map_or(1.0, |(_, value)| *value)
+            }
+        }
+    }
+}
+
+/// **How the difficulty of a level changes while it is played.**
+///
+/// ## Fields
+/// * `spawn_interval`: multiplier of the time between two spawns (`FALLING_OBJECT_UPDATE_MILLIS`); lower values spawn objects more often.
+/// * `fall_speed`: multiplier of the level's `fall_speed`.
+/// * `bad_ratio`: share of spawned objects that are "bad", from 0.0 to 1.0.
+#[derive(Clone, PartialEq, Debug)]
+pub struct DifficultyCurve {
+    pub spawn_interval: Curve,
+    pub fall_speed: Curve,
+    pub bad_ratio: Curve,
+}
+
+impl Default for DifficultyCurve {
+    /// A constant difficulty: the base spawn interval and fall speed, and one "bad" object in five.
+    fn default() -> Self {
+        Self {
+            spawn_interval: Curve::Constant(1.0),
+            fall_speed: Curve::Constant(1.0),
+            bad_ratio: Curve::Constant(0.2),
+        }
+    }
+}
+
+/// **A difficulty chosen before a run starts.**
+///
+/// ## Behavior
+/// Each preset sets the starting lives and scales the fall speed, the spawn interval, the share of "bad" objects and the score.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum DifficultyPreset {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    /// A single life for the whole level.
+    Hardcore,
+}
+
+impl DifficultyPreset {
+    /// All presets, from the easiest to the hardest.
+    pub const ALL: [DifficultyPreset; 4] = [
+        DifficultyPreset::Easy,
+        DifficultyPreset::Normal,
+        DifficultyPreset::Hard,
+        DifficultyPreset::Hardcore,
+    ];
+
+    /// **Returns the name of the preset shown to the player.**
+    pub fn name(&self) -> &'static str {
+        match self {
+            DifficultyPreset::Easy => "Easy",
+            DifficultyPreset::Normal => "Normal",
+            DifficultyPreset::Hard => "Hard",
+            DifficultyPreset::Hardcore => "Hardcore",
+        }
+    }
+
+    /// **Returns the next harder preset, wrapping around to `Easy` after `Hardcore`.**
+    pub fn next(&self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|preset| preset == self)
+            .unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// **Returns the number of lives a player starts each level with.**
+    pub fn lives(&self) -> u8 {
+        match self {
+            DifficultyPreset::Easy => 7,
+            DifficultyPreset::Normal => LIVES,
+            DifficultyPreset::Hard => 3,
+            DifficultyPreset::Hardcore => 1,
+        }
+    }
+
+    /// **Returns the multiplier of the objects' fall speed.**
+    pub fn fall_speed_multiplier(&self) -> f32 {
+        match self {
+            DifficultyPreset::Easy => 0.8,
+            DifficultyPreset::Normal => 1.0,
+            DifficultyPreset::Hard => 1.2,
+            DifficultyPreset::Hardcore => 1.3,
+        }
+    }
+
+    /// **Returns the multiplier of the time between two spawns.**
+    pub fn spawn_interval_multiplier(&self) -> f32 {
+        match self {
+            DifficultyPreset::Easy => 1.25,
+            DifficultyPreset::Normal => 1.0,
+            DifficultyPreset::Hard => 0.85,
+            DifficultyPreset::Hardcore => 0.8,
+        }
+    }
+
+    /// **Returns the multiplier of the share of "bad" objects.**
+    pub fn bad_ratio_multiplier(&self) -> f32 {
+        match self {
+            DifficultyPreset::Easy => 0.75,
+            DifficultyPreset::Normal => 1.0,
+            DifficultyPreset::Hard => 1.25,
+            DifficultyPreset::Hardcore => 1.4,
+        }
+    }
+
+    /// **Returns the multiplier of the points earned for "good" objects.**
+    pub fn score_multiplier(&self) -> f32 {
+        match self {
+            DifficultyPreset::Easy => 0.5,
+            DifficultyPreset::Normal => 1.0,
+            DifficultyPreset::Hard => 1.5,
+            DifficultyPreset::Hardcore => 2.0,
+        }
+    }
+
+    /// **Scales a score target defined for `Normal` to this preset's score multiplier.**
+    pub fn scaled_score(&self, points: i32) -> i32 {
+        (points as f32 * self.score_multiplier()).round() as i32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linear_curve_spans_the_level() {
+        let curve = Curve::Linear {
+            start: 1.0,
+            end: 2.0,
+        };
+        assert_eq!(curve.value_at(0.0), 1.0);
+        assert_eq!(curve.value_at(LEVEL_DURATION_SECS as f32 / 2.0), 1.5);
+        assert_eq!(curve.value_at(LEVEL_DURATION_SECS as f32 * 2.0), 2.0);
+    }
+
+    #[test]
+    fn test_stepped_and_keyframe_curves() {
+        let stepped = Curve::Stepped(vec![(5.0, 1.0), (20.0, 1.5)]);
+        assert_eq!(stepped.value_at(0.0), 1.0);
+        assert_eq!(stepped.value_at(19.9), 1.0);
+        assert_eq!(stepped.value_at(20.0), 1.5);
+
+        let keyframes = Curve::Keyframes(vec![(10.0, 1.0), (20.0, 2.0), (30.0, 0.0)]);
+        assert_eq!(keyframes.value_at(0.0), 1.0);
+        assert_eq!(keyframes.value_at(15.0), 1.5);
+        assert_eq!(keyframes.value_at(25.0), 1.0);
+        assert_eq!(keyframes.value_at(35.0), 0.0);
+        assert_eq!(Curve::Keyframes(Vec::new()).value_at(3.0), 1.0);
+    }
+
+    #[test]
+    fn test_presets_cycle_from_easy_to_hardcore() {
+        assert_eq!(DifficultyPreset::default().next(), DifficultyPreset::Hard);
+        assert_eq!(DifficultyPreset::Hardcore.next(), DifficultyPreset::Easy);
+        assert_eq!(DifficultyPreset::Hardcore.lives(), 1);
+    }
+}
@@ -0,0 +1,328 @@
+use std::io::{BufRead, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    consts::{
+        DASH_COOLDOWN_MILLIS, LEVEL_DURATION_SECS, LIVES, TICK_RATE, WINDOW_HEIGHT, WINDOW_WIDTH,
+    },
+    difficulty::DifficultyPreset,
+    errors::DodgerError,
+    levels::{get_levels, Level},
+    modes::PlayMode,
+    simulation::{PlayerInput, SpriteSizes, World},
+    utils::millis_to_ticks,
+};
+
+/// Number of falling objects described in an observation, the closest to landing first.
+pub const OBSERVED_OBJECTS: usize = 5;
+/// Number of values describing the player and the level in an observation.
+const PLAYER_FEATURES: usize = 6;
+/// Number of values describing each falling object in an observation.
+const OBJECT_FEATURES: usize = 4;
+/// Length of an observation vector.
+pub const OBSERVATION_SIZE: usize = PLAYER_FEATURES + OBSERVED_OBJECTS * OBJECT_FEATURES;
+/// Reward for each life lost.
+pub const LIFE_LOST_REWARD: f32 = -50.0;
+
+/// **A headless single-player Dodger environment for training agents.**
+///
+/// ## Fields
+/// * `level`: the level being played.
+/// * `world`: the simulation of the level.
+///
+/// ## Behavior
+/// * Each step advances the simulation by one tick (1/`TICK_RATE` of a second); no window is needed.
+/// * The reward of a step is the score gained during it plus `LIFE_LOST_REWARD` for each life lost.
+/// * The episode is done when the player has no lives left or the level's time is up.
+pub struct Env {
+    level: Level,
+    world: World,
+}
+
+impl Env {
+    /// **Creates an environment playing a level.**
+    ///
+    /// ## Parameters
+    /// * `level`: the level to play.
+    /// * `seed`: seed of the first episode.
+    ///
+    /// ## Returns
+    /// A result containing the environment, or a `DodgerError` if the level's simulation cannot be created.
+    pub fn new(level: Level, seed: u64) -> Result<Self, DodgerError> {
+        let world = World::new(
+            &level,
+            PlayMode::Single,
+            DifficultyPreset::Normal,
+            SpriteSizes::default(),
+            seed,
+        )?;
+        Ok(Self { level, world })
+    }
+
+    /// **Starts a new episode.**
+    ///
+    /// ## Parameters
+    /// `seed`: seed of the episode; the same seed and actions always give the same episode.
+    ///
+    /// ## Returns
+    /// A result containing the first observation, or a `DodgerError` if the level's simulation cannot be created.
+    pub fn reset(&mut self, seed: u64) -> Result<Vec<f32>, DodgerError> {
+        self.world = World::new(
+            &self.level,
+            PlayMode::Single,
+            DifficultyPreset::Normal,
+            SpriteSizes::default(),
+            seed,
+        )?;
+        Ok(self.observation())
+    }
+
+    /// **Advances the episode by one tick.**
+    ///
+    /// ## Parameters
+    /// `action`: the input of the player during the tick.
+    ///
+    /// ## Returns
+    /// The observation after the tick, the reward of the tick, and whether the episode is done.
+    /// Once the episode is done, further steps change nothing and give no reward.
+    pub fn step(&mut self, action: PlayerInput) -> (Vec<f32>, f32, bool) {
+        if self.is_done() {
+            return (self.observation(), 0.0, true);
+        }
+
+        let state = &self.world.players[0];
+        let (score, lives) = (state.level_score, state.lives);
+        self.world.step(&[action]);
+
+        let state = &self.world.players[0];
+        let reward =
+            (state.level_score - score) as f32 + f32::from(lives - state.lives) * LIFE_LOST_REWARD;
+        (self.observation(), reward, self.is_done())
+    }
+
+    /// **Checks whether the episode is over.**
+    pub fn is_done(&self) -> bool {
+        self.world.is_lost() || self.world.is_time_up()
+    }
+
+    /// **Returns the simulation of the level.**
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    /// **Describes the current state as a vector of `OBSERVATION_SIZE` numbers.**
+    ///
+    /// ## Behavior
+    /// * Player and level: the player's center x divided by the window width, its direction (-1.0 or 1.0),
+    ///   1.0 if it can dash (otherwise 0.0), its lives divided by `LIVES`, the remaining share of the level's time,
+    ///   and the distance objects fall in a second divided by the window height.
+    /// * Then, for the `OBSERVED_OBJECTS` objects closest to landing on the player: 1.0 if the slot is used (otherwise
+    ///   all four values are 0.0), the horizontal distance from the player's center to the object's center divided by
+    ///   the window width, the object's bottom divided by the window height, and its value: the score of a "good"
+    ///   object divided by 30 or -1.0 for a "bad" one.
+    pub fn observation(&self) -> Vec<f32> {
+        let tick = self.world.tick;
+        let player = &self.world.players[0].player;
+        let player_rect = player.rect();
+        let player_center = player_rect.x + player_rect.w / 2.0;
+        let can_dash = player
+            .last_dash_tick
+            .is_none_or(|dash| tick.saturating_sub(dash) >= millis_to_ticks(DASH_COOLDOWN_MILLIS));
+        let level_ticks = (LEVEL_DURATION_SECS * TICK_RATE) as f32;
+
+        let mut observation = Vec::with_capacity(OBSERVATION_SIZE);
+        observation.extend([
+            player_center / WINDOW_WIDTH,
+            player.direction,
+            if can_dash { 1.0 } else { 0.0 },
+            f32::from(self.world.players[0].lives) / f32::from(LIVES),
+            (level_ticks - tick as f32).max(0.0) / level_ticks,
+            self.world.fall_speed * TICK_RATE as f32 / WINDOW_HEIGHT,
+        ]);
+
+        let mut objects: Vec<_> = self
+            .world
+            .objects
+            .iter()
+            .filter(|obj| obj.hit_tick.is_none() && obj.coords.y <= player_rect.y + player_rect.h)
+            .collect();
+        objects.sort_by(|a, b| (b.coords.y + b.size.h).total_cmp(&(a.coords.y + a.size.h)));
+
+        for slot in 0..OBSERVED_OBJECTS {
+            match objects.get(slot) {
+                Some(obj) => {
+                    let rect = obj.rect();
+                    let value = match obj.good_object_value {
+                        Some(value) if obj.is_good => value.score() as f32 / 30.0,
+                        _ => -1.0,
+                    };
+                    observation.extend([
+                        1.0,
+                        (rect.x + rect.w / 2.0 - player_center) / WINDOW_WIDTH,
+                        (rect.y + rect.h) / WINDOW_HEIGHT,
+                        value,
+                    ]);
+                }
+                None => observation.extend([0.0; OBJECT_FEATURES]),
+            }
+        }
+        observation
+    }
+}
+
+/// A command sent by an external trainer, one JSON object per line.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+pub enum EnvRequest {
+    /// Starts a new episode on a level (0-based, the first level by default).
+    Reset {
+        seed: u64,
+        #[serde(default)]
+        level: usize,
+    },
+    /// Advances the episode by one tick.
+    Step { action: PlayerInput },
+    /// Ends the session.
+    Close,
+}
+
+/// A reply sent to an external trainer, one JSON object per line.
+#[derive(Clone, Debug, Serialize)]
+#[serde(untagged)]
+pub enum EnvResponse {
+    Reset {
+        observation: Vec<f32>,
+    },
+    Step {
+        observation: Vec<f32>,
+        reward: f32,
+        done: bool,
+    },
+    Error {
+        error: String,
+    },
+}
+
+/// **Lets an external trainer drive environments through a line-based JSON protocol.**
+///
+/// ## Parameters
+/// * `input`: where the requests are read from, usually stdin.
+/// * `output`: where the responses are written to, usually stdout.
+///
+/// ## Returns
+/// `Ok(())` when the input ends or a `close` request is received, or a `DodgerError` if reading or writing fails.
+///
+/// ## Behavior
+/// * `{"cmd":"reset","seed":1,"level":0}` starts an episode and answers `{"observation":[...]}`.
+/// * `{"cmd":"step","action":{"axis":-1.0,"dash":false}}` answers `{"observation":[...],"reward":5.0,"done":false}`.
+/// * Invalid requests, unknown levels and steps before the first reset are answered with `{"error":"..."}`.
+pub fn serve(input: impl BufRead, mut output: impl Write) -> Result<(), DodgerError> {
+    let levels = get_levels();
+    let mut env: Option<Env> = None;
+
+    for line in input.lines() {
+        let line = line.map_err(|err| DodgerError::EnvProtocol(err.to_string()))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<EnvRequest>(&line) {
+            Ok(EnvRequest::Reset { seed, level }) => match levels.get(level) {
+                Some(level) => {
+                    let new_env = Env::new(level.clone(), seed)?;
+                    let observation = new_env.observation();
+                    env = Some(new_env);
+                    EnvResponse::Reset { observation }
+                }
+                None => EnvResponse::Error {
+                    error: format!("unknown level {}, there are {}", level, levels.len()),
+                },
+            },
+            Ok(EnvRequest::Step { action }) => match &mut env {
+                Some(env) => {
+                    let (observation, reward, done) = env.step(action);
+                    EnvResponse::Step {
+                        observation,
+                        reward,
+                        done,
+                    }
+                }
+                None => EnvResponse::Error {
+                    error: "send a reset request before the first step".to_string(),
+                },
+            },
+            Ok(EnvRequest::Close) => break,
+            Err(err) => EnvResponse::Error {
+                error: err.to_string(),
+            },
+        };
+
+        let json = serde_json::to_string(&response)
+            .map_err(|err| DodgerError::EnvProtocol(err.to_string()))?;
+        writeln!(output, "{}", json)
+            .and_then(|_| output.flush())
+            .map_err(|err| DodgerError::EnvProtocol(err.to_string()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_episode_runs_until_done() {
+        let mut env = Env::new(get_levels()[0].clone(), 3).unwrap();
+        assert_eq!(env.reset(3).unwrap().len(), OBSERVATION_SIZE);
+
+        let mut steps = 0;
+        let mut total_reward = 0.0;
+        loop {
+            let (observation, reward, done) = env.step(PlayerInput {
+                axis: 1.0,
+                dash: false,
+            });
+            assert_eq!(observation.len(), OBSERVATION_SIZE);
+            total_reward += reward;
+            steps += 1;
+            if done {
+                break;
+            }
+        }
+        assert!(steps <= LEVEL_DURATION_SECS * TICK_RATE);
+        assert_eq!(total_reward, {
+            let state = &env.world().players[0];
+            state.level_score as f32 + f32::from(LIVES - state.lives) * LIFE_LOST_REWARD
+        });
+        assert_eq!(env.step(PlayerInput::default()).1, 0.0);
+    }
+
+    #[test]
+    fn test_serve_answers_each_request() {
+        let input = concat!(
+            "{\"cmd\":\"step\",\"action\":{\"axis\":1.0}}\n",
+            "{\"cmd\":\"reset\",\"seed\":1}\n",
+            "{\"cmd\":\"step\",\"action\":{\"axis\":-1.0,\"dash\":true}}\n",
+            "not json\n",
+            "{\"cmd\":\"close\"}\n",
+            "{\"cmd\":\"reset\",\"seed\":2}\n",
+        );
+        let mut output = Vec::new();
+        serve(input.as_bytes(), &mut output).unwrap();
+
+        let lines: Vec<serde_json::Value> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].get("error").is_some());
+        assert_eq!(
+            lines[1]["observation"].as_array().unwrap().len(),
+            OBSERVATION_SIZE
+        );
+        assert_eq!(lines[2]["done"], false);
+        assert!(lines[3].get("error").is_some());
+    }
+}
@@ -51,12 +51,42 @@ pub enum DodgerError {
     #[error("Failed to build an ellipse, error: {0}")]
     BuildEllipse(String),
 
+    #[error("Failed to build a polygon, error: {0}")]
+    BuildPolygon(String),
+
     #[error("Failed to draw a rectangle, error: {0}")]
     DrawRect(String),
 
     #[error("Failed to draw an ellipse, error: {0}")]
     DrawEllipse(String),
 
+    #[error("Failed to read file {0}, error: {1}")]
+    ReadFile(String, String),
+
+    #[error("Failed to write file {0}, error: {1}")]
+    WriteFile(String, String),
+
+    #[error("Failed to parse file {0}, error: {1}")]
+    ParseFile(String, String),
+
+    #[error("Network error: {0}")]
+    Network(String),
+
+    #[error("The connection to the other player was lost")]
+    Disconnected,
+
+    #[error("The saved game cannot be continued: {0}")]
+    IncompatibleSave(String),
+
+    #[error("Invalid profile name: {0}")]
+    InvalidProfileName(String),
+
+    #[error("Cannot buy the upgrade: {0}")]
+    PurchaseFailed(String),
+
+    #[error("Training environment protocol error: {0}")]
+    EnvProtocol(String),
+
     #[error("An unexpected error occurred")]
     Unexpected,
 }
@@ -1,33 +1,54 @@
-use std::time::{Duration, Instant};
+use std::path::PathBuf;
 
 use ggez::{
-    event::EventHandler,
+    event::{Axis, Button, EventHandler, GamepadId},
     graphics::{Canvas, Color},
     input::keyboard::{KeyCode, KeyInput},
     mint::{Point2, Vector2},
     Context, GameError, GameResult,
 };
 
-use rand::Rng;
-
 use crate::{
+    achievements::{Accomplishments, LevelOutcome, UnlockedAchievements, ACHIEVEMENTS},
+    adaptive::AdaptiveDifficulty,
+    agent::{Agent, HeuristicBot},
     buttons::{DrawText, IconButton, TextButton},
     consts::{
-        BUTTON_TEXT_SIZE, FALLING_OBJECT_UPDATE_MILLIS, LEVEL_DURATION_SECS, LIVES, TEXT_SIZE,
-        WINDOW_HEIGHT, WINDOW_WIDTH,
+        BUTTON_SPACING, BUTTON_TEXT_SIZE, CONTINUE_SCORE_PENALTY, COUNTDOWN_SECS, DEBUG_TEXT_SIZE,
+        DEFAULT_PORT, GO_SECS, MAX_ADDRESS_LENGTH, MAX_CATCH_UP_TICKS, TEXT_BUTTON_WIDTH,
+        TEXT_SIZE, TICK_RATE, WINDOW_HEIGHT, WINDOW_WIDTH, YELLOW,
     },
+    continues::Continues,
+    difficulty::DifficultyPreset,
     errors::DodgerError,
+    highscores::{HighScore, HighScores},
+    input::{
+        mouse_follow_dx, Action, Binding, ControlScheme, InputBindings, InputSource, MovementInput,
+    },
     levels::{get_levels, Level},
-    modes::GameMode,
+    modes::{versus_winner, GameMode, PlayMode},
+    navigation::{MenuCommand, MenuFocus},
+    net::{Connecting, Connection, Host, Lobby, NetGame, NetMessage, PROTOCOL_VERSION},
+    objectives::ObjectiveStatus,
     objects::{FallingObject, GoodObjectValue},
-    player::Player,
+    profiles::{ProfileEditor, Profiles, MAX_PROFILES, MAX_PROFILE_NAME_LENGTH},
+    progress::Progress,
     resources::{add_fonts, Resources},
+    savegame::{SaveGame, SAVE_VERSION},
+    settings::Settings,
+    shop::{Purchases, UPGRADES},
+    simulation::{PlayerInput, SpriteSizes, World, WorldEvent},
     sound::AudioManager,
-    ui::{draw_background, draw_button_with_text, draw_icon, draw_score, draw_text, draw_timer},
+    stats::{LevelTally, Statistics},
+    summary::{level_bonuses, GameOverReport, LevelSummary},
+    ui::{
+        draw_background, draw_button_with_text, draw_focus_frame, draw_icon, draw_lock, draw_score,
+        draw_star, draw_text, draw_timer, draw_toast, Toast,
+    },
     utils::{
-        get_level_button, half_scaling, icon_button_rect, is_button_clicked, object_scaling,
-        player_scaling, start_point_of_button_in_set, start_point_of_centered_button,
-        text_button_rect, text_button_rectsize, RectSize,
+        countdown_label, get_level_button, get_rebind_button, half_scaling, icon_button_rect,
+        is_button_clicked, player_scaling, start_point_of_button_in_set,
+        start_point_of_centered_button, text_button_rect, text_button_rectsize, RectSize,
     },
 };
 
@@ -35,48 +56,121 @@ use crate::{
 ///
 /// ## Fields
 /// * `total_score`: the player's total score across all levels.
-/// * `level_score`: the player's score for the current level.
 /// * `current_level`: the index of the current level being played.
 /// * `levels`: a list of all available levels.
 /// * `resources`: the game resources, including images, fonts, and sounds.
-/// * `player`: the player object.
-/// * `falling_objects`: a list of objects currently falling in the game.
-/// * `last_update`: the timestamp of the last game update.
-/// * `level_start_time`: the timestamp when the current level started.
-/// * `paused_time`: the timestamp when the game was paused, if applicable.
+/// * `world`: the simulation of the current level: players, falling objects, lives and scores.
+/// * `play_mode`: whether the game is played by one player, or by two players competing or cooperating with each other.
+/// * `run_difficulty`: the difficulty preset of the current run, chosen when it started.
+/// * `run_adaptive`: whether the current run uses adaptive difficulty; such runs are not added to the high scores.
+/// * `high_scores`: the best runs, persisted between sessions.
+/// * `progress`: the unlocked levels and the best score and stars of each level, persisted between sessions.
+/// * `statistics`: the lifetime statistics of the active profile, persisted between sessions.
+/// * `level_tally`: what happened during the level being played, added to the statistics when the level ends.
+/// * `achievements`: the achievements unlocked by the active profile, persisted between sessions.
+/// * `toasts`: the notifications waiting to be shown, the one shown first.
+/// * `countdown_secs`: the seconds left on the level intro card before the level starts.
+/// * `objective_met_secs`: the seconds left in the level when its objective was met, if it was.
+/// * `level_summary`: the results of the completed level, shown on the summary screen.
+/// * `game_over_report`: how the last run ended, shown on the "Game Over" screen.
+/// * `continues`: the continues spent in the current run; runs with continues are not ranked in the high scores.
+/// * `run_end_pending`: whether the run lost on the "Game Over" screen has not ended yet; it can still be continued or its level retried.
+/// * `purchases`: the upgrades bought in the shop between the levels of the current run.
+/// * `shop_status`: the reason the last purchase in the shop failed, if any.
+/// * `saved_game`: the run saved in an earlier session that can be continued from the main menu, if any.
+/// * `run_saved`: whether the current run was saved since the game was last paused.
+/// * `movements`: the horizontal movement currently requested by the inputs of each local player.
+/// * `dash_requests`: whether each local player asked to dash since the last simulation tick.
+/// * `tick_accumulator`: time elapsed since the last simulation tick, in seconds.
+/// * `net`: the networked versus game being played, if any.
+/// * `lobby`: the state of the host and join screens.
+/// * `demo`: the level played by a bot behind the main menu before a game is started.
+/// * `demo_bot`: the bot playing the demo.
+/// * `demo_accumulator`: time elapsed since the last tick of the demo, in seconds.
 /// * `audio`: the audio manager for playing sounds.
 /// * `audio_button`: the button to toggle audio on/off.
 /// * `start_button`: the button to start the game.
+/// * `continue_button`: the button to continue the saved run, next to the start button.
 /// * `exit_button`: the button to exit the game.
 /// * `resume_button`: the button to resume the game from pause.
 /// * `menu_button`: the button to enter the main menu.
 /// * `back_to_menu_button`: the button to return to the main menu from the "How to Play" screen.
 /// * `pause_button`: the button to pause the game.
-/// * `next_level_button`: the button to proceed to the next level.
-/// * `restart_button`: the button to restart the current level or the game.
+/// * `next_level_button`: the button to leave the shop and proceed to the next level.
+/// * `restart_button`: the button to restart the game from the victory screen.
+/// * `play_level_button`: the button to start playing from the level intro card without waiting for the countdown.
+/// * `retry_button`: the button to replay a level whose objective was failed.
 /// * `select_level_button`: the button to open the level selection screen.
 /// * `howtoplay_button`: the button to open the "How to Play" screen.
-/// * `lives`: the number of lives the player has remaining.
+/// * `controls_button`: the button to open the controls screen.
+/// * `stats_button`: the button to open the statistics screen, next to the level selection button.
+/// * `achievements_button`: the button to open the achievements gallery, next to the "How to Play" button.
+/// * `reset_controls_button`: the button to restore the default input bindings.
+/// * `multiplayer_button`: the button to open the multiplayer screen.
+/// * `versus_button`: the button to start a local versus game.
+/// * `coop_button`: the button to start a local co-op game.
+/// * `host_button`: the button to host a networked versus game.
+/// * `join_button`: the button to join a networked versus game.
+/// * `connect_button`: the button to connect to the typed address on the join screen.
+/// * `rematch_button`: the button to replay the level after a versus game.
+/// * `main_menu_button`: the button to return to the main menu after a versus game.
+/// * `new_profile_button`: the button to create a profile on the profiles screen.
+/// * `rename_profile_button`: the button to rename the active profile on the profiles screen.
+/// * `confirm_name_button`: the button to confirm the typed name on the profile name screen.
 /// * `game_mode`: the current mode of the game (e.g., Menu, Playing, GameOver).
 /// * `level_complete_sound_played`: whether the level complete sound has been played.
 /// * `victory_sound_played`: whether the victory sound has been played.
 /// * `game_over_sound_played`: whether the game over sound has been played.
 /// * `game_started`: whether the game has started.
 /// * `is_paused`: whether the game is currently paused.
+/// * `menu_focus`: the focused button on menu screens for keyboard and gamepad navigation.
+/// * `settings`: the persisted player settings, including input bindings.
+/// * `settings_dir`: the directory of the active profile, where its settings, high scores, progress and saved game are stored.
+/// * `config_dir`: the directory the profile list and the directories of the profiles are stored in.
+/// * `profiles`: the player profiles; the settings, high scores, progress and saved game belong to the active one.
+/// * `profile_editor`: the name typed on the profile name screen.
+/// * `confirm_profile_delete`: whether the delete button on the profiles screen waits for a second press.
+/// * `rebinding`: the player and the action waiting for a new key or button on the controls screen, if any.
+/// * `controls_player`: index of the player whose bindings are shown on the controls screen.
+/// * `gamepads`: the gamepads connected during the last update, used to detect hot-plugging.
+/// * `show_debug_overlay`: whether the simulation's difficulty values are drawn over the game (toggled with F3).
 pub struct GameState {
     total_score: i32,
-    level_score: i32,
     current_level: usize,
     levels: Vec<Level>,
     resources: Resources,
-    player: Player,
-    falling_objects: Vec<FallingObject>,
-    last_update: Instant,
-    level_start_time: Instant,
-    paused_time: Option<Instant>,
+    world: World,
+    play_mode: PlayMode,
+    run_difficulty: DifficultyPreset,
+    run_adaptive: bool,
+    high_scores: HighScores,
+    progress: Progress,
+    statistics: Statistics,
+    level_tally: LevelTally,
+    achievements: UnlockedAchievements,
+    toasts: Vec<Toast>,
+    countdown_secs: f32,
+    objective_met_secs: Option<u64>,
+    level_summary: Option<LevelSummary>,
+    game_over_report: Option<GameOverReport>,
+    continues: Continues,
+    run_end_pending: bool,
+    purchases: Purchases,
+    shop_status: Option<String>,
+    saved_game: Option<SaveGame>,
+    run_saved: bool,
+    movements: Vec<MovementInput>,
+    dash_requests: Vec<bool>,
+    tick_accumulator: f32,
+    net: Option<NetGame>,
+    lobby: Lobby,
+    demo: World,
+    demo_bot: HeuristicBot,
+    demo_accumulator: f32,
     audio: AudioManager,
     audio_button: IconButton,
     start_button: TextButton,
+    continue_button: TextButton,
     exit_button: TextButton,
     resume_button: TextButton,
     menu_button: TextButton,
@@ -84,15 +178,42 @@ pub struct GameState {
     pause_button: IconButton,
     next_level_button: TextButton,
     restart_button: TextButton,
+    play_level_button: TextButton,
+    retry_button: TextButton,
     select_level_button: TextButton,
     howtoplay_button: TextButton,
-    lives: u8,
+    controls_button: TextButton,
+    stats_button: TextButton,
+    achievements_button: TextButton,
+    reset_controls_button: TextButton,
+    multiplayer_button: TextButton,
+    versus_button: TextButton,
+    coop_button: TextButton,
+    host_button: TextButton,
+    join_button: TextButton,
+    connect_button: TextButton,
+    rematch_button: TextButton,
+    main_menu_button: TextButton,
+    new_profile_button: TextButton,
+    rename_profile_button: TextButton,
+    confirm_name_button: TextButton,
     game_mode: GameMode,
     level_complete_sound_played: bool,
     victory_sound_played: bool,
     game_over_sound_played: bool,
     game_started: bool,
     is_paused: bool,
+    menu_focus: MenuFocus,
+    settings: Settings,
+    settings_dir: PathBuf,
+    config_dir: PathBuf,
+    profiles: Profiles,
+    profile_editor: ProfileEditor,
+    confirm_profile_delete: bool,
+    rebinding: Option<(usize, Action)>,
+    controls_player: usize,
+    gamepads: Vec<GamepadId>,
+    show_debug_overlay: bool,
 }
 
 impl GameState {
@@ -118,11 +239,13 @@ impl GameState {
         audio_manager: AudioManager,
     ) -> Result<Self, DodgerError> {
         add_fonts(ctx)?;
-        let player = Player::new(
-            ctx,
-            Point2::from_slice(&[WINDOW_WIDTH / 2.0, WINDOW_HEIGHT - 175.0]),
-            player_scaling(),
-            &resources.player_image,
+        let levels = get_levels();
+        let world = World::new(
+            &levels[current_level],
+            PlayMode::Single,
+            DifficultyPreset::Normal,
+            SpriteSizes::from_resources(&resources),
+            rand::random(),
         )?;
         let default_text_button_size = text_button_rectsize();
         let restart_button = TextButton::new(
@@ -148,7 +271,7 @@ impl GameState {
         )?;
 
         let next_level_button = TextButton::new(
-            start_point_of_centered_button(),
+            start_point_of_button_in_set(0, 600.0),
             Color::WHITE,
             default_text_button_size,
             "Next Level".to_string(),
@@ -167,6 +290,20 @@ impl GameState {
             "button_font".to_string(),
         )?;
 
+        let start_point = start_point_of_button_in_set(0, 300.0);
+        let continue_button = TextButton::new(
+            Point2::from_slice(&[
+                start_point.x + TEXT_BUTTON_WIDTH + BUTTON_SPACING,
+                start_point.y,
+            ]),
+            Color::WHITE,
+            default_text_button_size,
+            "Continue".to_string(),
+            Color::BLACK,
+            BUTTON_TEXT_SIZE,
+            "button_font".to_string(),
+        )?;
+
         let resume_button = TextButton::new(
             start_point_of_button_in_set(0, 300.0),
             Color::WHITE,
@@ -177,10 +314,20 @@ impl GameState {
             "button_font".to_string(),
         )?;
 
-        let select_level_button = TextButton::new(
+        let multiplayer_button = TextButton::new(
             start_point_of_button_in_set(1, 300.0),
             Color::WHITE,
             default_text_button_size,
+            "Multiplayer".to_string(),
+            Color::BLACK,
+            BUTTON_TEXT_SIZE,
+            "button_font".to_string(),
+        )?;
+
+        let select_level_button = TextButton::new(
+            start_point_of_button_in_set(2, 300.0),
+            Color::WHITE,
+            default_text_button_size,
             "Select Level".to_string(),
             Color::BLACK,
             BUTTON_TEXT_SIZE,
@@ -188,7 +335,7 @@ impl GameState {
         )?;
 
         let howtoplay_button = TextButton::new(
-            start_point_of_button_in_set(2, 300.0),
+            start_point_of_button_in_set(3, 300.0),
             Color::WHITE,
             default_text_button_size,
             "How to Play".to_string(),
@@ -197,8 +344,46 @@ impl GameState {
             "button_font".to_string(),
         )?;
 
+        let controls_button = TextButton::new(
+            start_point_of_button_in_set(4, 300.0),
+            Color::WHITE,
+            default_text_button_size,
+            "Controls".to_string(),
+            Color::BLACK,
+            BUTTON_TEXT_SIZE,
+            "button_font".to_string(),
+        )?;
+
+        let select_level_point = start_point_of_button_in_set(2, 300.0);
+        let stats_button = TextButton::new(
+            Point2::from_slice(&[
+                select_level_point.x + TEXT_BUTTON_WIDTH + BUTTON_SPACING,
+                select_level_point.y,
+            ]),
+            Color::WHITE,
+            default_text_button_size,
+            "Stats".to_string(),
+            Color::BLACK,
+            BUTTON_TEXT_SIZE,
+            "button_font".to_string(),
+        )?;
+
+        let howtoplay_point = start_point_of_button_in_set(3, 300.0);
+        let achievements_button = TextButton::new(
+            Point2::from_slice(&[
+                howtoplay_point.x + TEXT_BUTTON_WIDTH + BUTTON_SPACING,
+                howtoplay_point.y,
+            ]),
+            Color::WHITE,
+            default_text_button_size,
+            "Achievements".to_string(),
+            Color::BLACK,
+            BUTTON_TEXT_SIZE,
+            "button_font".to_string(),
+        )?;
+
         let exit_button = TextButton::new(
-            start_point_of_button_in_set(3, 300.0),
+            start_point_of_button_in_set(6, 300.0),
             Color::WHITE,
             default_text_button_size,
             "Exit".to_string(),
@@ -227,24 +412,187 @@ impl GameState {
             "button_font".to_string(),
         )?;
 
-        let levels = get_levels();
+        let reset_controls_button = TextButton::new(
+            Point2::from_slice(&[WINDOW_WIDTH / 2.0 + 50.0, 620.0]),
+            Color::WHITE,
+            default_text_button_size,
+            "Reset".to_string(),
+            Color::BLACK,
+            BUTTON_TEXT_SIZE,
+            "button_font".to_string(),
+        )?;
+
+        let versus_button = TextButton::new(
+            start_point_of_button_in_set(0, 300.0),
+            Color::WHITE,
+            default_text_button_size,
+            "Versus".to_string(),
+            Color::BLACK,
+            BUTTON_TEXT_SIZE,
+            "button_font".to_string(),
+        )?;
+
+        let coop_button = TextButton::new(
+            start_point_of_button_in_set(1, 300.0),
+            Color::WHITE,
+            default_text_button_size,
+            "Co-op".to_string(),
+            Color::BLACK,
+            BUTTON_TEXT_SIZE,
+            "button_font".to_string(),
+        )?;
+
+        let host_button = TextButton::new(
+            start_point_of_button_in_set(2, 300.0),
+            Color::WHITE,
+            default_text_button_size,
+            "Host Online".to_string(),
+            Color::BLACK,
+            BUTTON_TEXT_SIZE,
+            "button_font".to_string(),
+        )?;
+
+        let join_button = TextButton::new(
+            start_point_of_button_in_set(3, 300.0),
+            Color::WHITE,
+            default_text_button_size,
+            "Join Online".to_string(),
+            Color::BLACK,
+            BUTTON_TEXT_SIZE,
+            "button_font".to_string(),
+        )?;
+
+        let connect_button = TextButton::new(
+            start_point_of_button_in_set(0, 400.0),
+            Color::WHITE,
+            default_text_button_size,
+            "Connect".to_string(),
+            Color::BLACK,
+            BUTTON_TEXT_SIZE,
+            "button_font".to_string(),
+        )?;
+
+        let play_level_button = TextButton::new(
+            start_point_of_button_in_set(0, 600.0),
+            Color::WHITE,
+            default_text_button_size,
+            "Play".to_string(),
+            Color::BLACK,
+            BUTTON_TEXT_SIZE,
+            "button_font".to_string(),
+        )?;
+
+        let retry_button = TextButton::new(
+            start_point_of_button_in_set(0, 500.0),
+            Color::WHITE,
+            default_text_button_size,
+            "Retry".to_string(),
+            Color::BLACK,
+            BUTTON_TEXT_SIZE,
+            "button_font".to_string(),
+        )?;
+
+        let rematch_button = TextButton::new(
+            start_point_of_button_in_set(0, 500.0),
+            Color::WHITE,
+            default_text_button_size,
+            "Rematch".to_string(),
+            Color::BLACK,
+            BUTTON_TEXT_SIZE,
+            "button_font".to_string(),
+        )?;
+
+        let main_menu_button = TextButton::new(
+            start_point_of_button_in_set(1, 500.0),
+            Color::WHITE,
+            default_text_button_size,
+            "Main Menu".to_string(),
+            Color::BLACK,
+            BUTTON_TEXT_SIZE,
+            "button_font".to_string(),
+        )?;
+
+        let new_profile_button = TextButton::new(
+            start_point_of_button_in_set(MAX_PROFILES + 1, 100.0),
+            Color::WHITE,
+            default_text_button_size,
+            "New".to_string(),
+            Color::BLACK,
+            BUTTON_TEXT_SIZE,
+            "button_font".to_string(),
+        )?;
+
+        let rename_profile_button = TextButton::new(
+            start_point_of_button_in_set(MAX_PROFILES + 2, 100.0),
+            Color::WHITE,
+            default_text_button_size,
+            "Rename".to_string(),
+            Color::BLACK,
+            BUTTON_TEXT_SIZE,
+            "button_font".to_string(),
+        )?;
+
+        let confirm_name_button = TextButton::new(
+            start_point_of_button_in_set(0, 400.0),
+            Color::WHITE,
+            default_text_button_size,
+            "OK".to_string(),
+            Color::BLACK,
+            BUTTON_TEXT_SIZE,
+            "button_font".to_string(),
+        )?;
 
         let audio = AudioManager::new(ctx)?;
 
+        // A corrupted profile list or settings file falls back to the defaults instead of preventing the game from starting.
+        let config_dir = ctx.fs.user_config_dir().to_path_buf();
+        let profiles = Profiles::load(&config_dir).unwrap_or_default();
+        let settings_dir = profiles.active().dir(&config_dir);
+        let settings = Settings::load(&settings_dir).unwrap_or_default();
+        let high_scores = HighScores::load(&settings_dir).unwrap_or_default();
+        let progress = Progress::load(&settings_dir).unwrap_or_default();
+        let statistics = Statistics::load(&settings_dir).unwrap_or_default();
+        let achievements = UnlockedAchievements::load(&settings_dir).unwrap_or_default();
+        // A saved game that cannot be continued is ignored and overwritten by the next save.
+        let saved_game = SaveGame::load(&settings_dir, levels.len()).unwrap_or(None);
+
         let game = GameState {
             total_score: 0,
-            level_score: 0,
-            player,
+            world: world.clone(),
+            play_mode: PlayMode::Single,
+            run_difficulty: settings.difficulty,
+            run_adaptive: false,
+            high_scores,
+            progress,
+            statistics,
+            level_tally: LevelTally::default(),
+            countdown_secs: 0.0,
+            objective_met_secs: None,
+            level_summary: None,
+            game_over_report: None,
+            continues: Continues::default(),
+            run_end_pending: false,
+            purchases: Purchases::default(),
+            shop_status: None,
+            achievements,
+            toasts: Vec::new(),
+            saved_game,
+            run_saved: false,
+            movements: vec![MovementInput::default()],
+            dash_requests: vec![false],
+            tick_accumulator: 0.0,
+            net: None,
+            lobby: Lobby::default(),
+            demo: world.clone(),
+            demo_bot: HeuristicBot,
+            demo_accumulator: 0.0,
             current_level,
             levels,
             resources,
-            falling_objects: Vec::new(),
-            last_update: Instant::now(),
-            level_start_time: Instant::now(),
-            paused_time: None,
             audio,
             audio_button,
             start_button,
+            continue_button,
             resume_button,
             exit_button,
             menu_button,
@@ -252,342 +600,2904 @@ impl GameState {
             pause_button,
             next_level_button,
             restart_button,
+            play_level_button,
+            retry_button,
             select_level_button,
             howtoplay_button,
-            lives: LIVES,
+            controls_button,
+            stats_button,
+            achievements_button,
+            reset_controls_button,
+            multiplayer_button,
+            versus_button,
+            coop_button,
+            host_button,
+            join_button,
+            connect_button,
+            rematch_button,
+            main_menu_button,
+            new_profile_button,
+            rename_profile_button,
+            confirm_name_button,
             game_mode: GameMode::Menu,
             level_complete_sound_played: false,
             victory_sound_played: false,
             game_over_sound_played: false,
             game_started: false,
             is_paused: false,
+            menu_focus: MenuFocus::new(),
+            settings,
+            settings_dir,
+            config_dir,
+            profiles,
+            profile_editor: ProfileEditor::default(),
+            confirm_profile_delete: false,
+            rebinding: None,
+            controls_player: 0,
+            gamepads: ctx.gamepad.gamepads().map(|(id, _)| id).collect(),
+            show_debug_overlay: false,
         };
         Ok(game)
     }
 
-    /// **Creates a new falling object and adds it to the game.**
+    /// **Resets the game state for a new level or restart.**
+    ///
+    /// ## Parameters
+    /// `ctx`: the game context.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the reset is successful, or a `DodgerError` if resource loading fails.
+    ///
+    /// ## Behavior
+    /// Resets the level with a new random seed, see `reset_with_seed`.
+    fn reset(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
+        self.reset_with_seed(ctx, rand::random())
+    }
+
+    /// **Resets the game state for a new level or restart with a given seed.**
+    ///
+    /// ## Parameters
+    /// * `ctx`: the game context.
+    /// * `seed`: seed of the level's simulation.
     ///
     /// ## Returns
-    /// `Ok(())` if the object is created successfully, or a `DodgerError` if creation fails.
+    /// `Ok(())` if the reset is successful, or a `DodgerError` if resource loading fails.
     ///
     /// ## Behavior
-    /// * Randomly generates a horizontal position for the object.
-    /// * Determines if the object is "good" or "bad".
-    /// * Assigns a value to "good" objects (`High`, `Medium`, `Low`).
-    /// * Adds the object to the `falling_objects` list.
-    fn create_falling_object(&mut self) -> Result<(), DodgerError> {
-        let mut rng = rand::thread_rng();
-        let x = rng.gen_range(25.0..WINDOW_WIDTH - 25.0);
-        let is_good = self.falling_objects.len() % 5 != 0;
-        let good_object_value = if is_good {
-            match rng.gen_range(0..10) {
-                0 => Some(GoodObjectValue::High),
-                1 | 3 | 5 => Some(GoodObjectValue::Medium),
-                _ => Some(GoodObjectValue::Low),
-            }
+    /// * Resets the score, lives, and timers.
+    /// * Loads resources for the current level.
+    /// * Creates the level's simulation with the players of the current play mode, upgraded by the purchases of the run;
+    ///   versus players are never upgraded, so both game instances simulate the same players.
+    /// * A level retried after failing its objective or losing the run keeps the total score from before it;
+    ///   a new run starts without purchases and continues.
+    /// * In adaptive runs, the difficulty factor reached in the previous level carries over to the next one.
+    /// * Shows the level intro card with the level's name, objective and objects, and starts its countdown;
+    ///   versus games have no objectives and start playing right away.
+    fn reset_with_seed(&mut self, ctx: &mut Context, seed: u64) -> Result<(), DodgerError> {
+        let new_run = self.game_mode == GameMode::LevelSelection;
+        if new_run {
+            self.total_score = 0;
+            self.continues = Continues::default();
+            self.purchases = Purchases::default();
+        } else if !matches!(self.game_mode, GameMode::LevelFailed | GameMode::GameOver) {
+            self.total_score += self.level_score();
+        }
+        let adaptive_factor = if new_run {
+            1.0
         } else {
-            None
+            self.world.adaptive_factor()
         };
+        self.level_complete_sound_played = false;
+        self.victory_sound_played = false;
+        self.game_over_sound_played = false;
+        self.is_paused = false;
+        self.resources = Resources::load_level(ctx, self.current_level, &self.levels)?;
 
-        let object = FallingObject::new(
-            Point2::from_slice(&[x, 0.0]),
-            object_scaling(),
-            is_good,
-            good_object_value,
-            &self.resources,
+        self.world = World::new(
+            &self.levels[self.current_level],
+            self.play_mode,
+            self.run_difficulty,
+            SpriteSizes::from_resources(&self.resources),
+            seed,
         )?;
+        if self.play_mode != PlayMode::Versus {
+            self.world.apply_boosts(&self.purchases.boosts(), true);
+        }
+        if self.run_adaptive {
+            self.world.adaptive = Some(AdaptiveDifficulty::new(adaptive_factor));
+        }
+        self.tick_accumulator = 0.0;
+        self.level_tally = LevelTally::default();
+        self.objective_met_secs = None;
+        self.shop_status = None;
+        let local_players = self.local_player_count();
+        self.movements
+            .resize(local_players, MovementInput::default());
+        self.dash_requests = vec![false; local_players];
 
-        self.falling_objects.push(object);
+        self.countdown_secs = COUNTDOWN_SECS + GO_SECS;
+        self.game_mode = if self.play_mode == PlayMode::Versus {
+            GameMode::Playing
+        } else {
+            GameMode::LevelIntro
+        };
 
         Ok(())
     }
 
-    ///**Handles collisions between the player and falling objects.**
+    /// **Starts a new game from a level.**
     ///
     /// ## Parameters
-    /// `ctx`: the game context.
+    /// * `ctx`: the game context.
+    /// * `play_mode`: the play mode of the new game.
+    /// * `level`: index of the first level (0-based).
     ///
     /// ## Returns
-    /// `Ok(())` if collisions are handled successfully, or a `DodgerError` if sound playback fails.
+    /// `Ok(())` if the game is started successfully, or a `DodgerError` if resource loading fails.
     ///
     /// ## Behavior
-    /// * Checks for collisions between the player and each falling object.
-    /// * Updates the score if the player catches a "good" object.
-    /// * Reduces lives if the player collides with a "bad" object.
-    /// * Plays appropriate sounds for collisions.
-    /// * Removes objects that have been caught or have expired.
-    fn handle_collisions(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
-        let player_rect = self.player.rect();
-
-        for obj in &mut self.falling_objects {
-            if obj.remove_timer.is_some() {
-                continue;
-            }
-
-            let obj_rect = obj.rect();
-
-            if player_rect.overlaps(&obj_rect) {
-                if obj.is_good {
-                    match &obj.good_object_value {
-                        Some(value) => {
-                            self.level_score += value.score();
-                            match value {
-                                GoodObjectValue::High => {
-                                    self.audio
-                                        .play_sound(ctx, "good_collision_high".to_string())?;
-                                }
-                                _ => {
-                                    self.audio.play_sound(ctx, "good_collision".to_string())?;
-                                }
-                            }
-                        }
-                        None => {
-                            self.level_score += 10;
-                            self.audio.play_sound(ctx, "good_collision".to_string())?;
-                        }
-                    }
-                    obj.remove_timer = Some(Instant::now());
-                } else {
-                    self.audio.play_sound(ctx, "bad_collision".to_string())?;
-                    self.lives -= 1;
-                    if self.lives == 0 {
-                        self.game_mode = GameMode::GameOver;
-                    }
-                    obj.remove_timer = Some(Instant::now());
-                    obj.blink_timer = Some(Instant::now());
-                    self.player.blink_timer = Some(Instant::now());
-                }
-            }
+    /// Discards the scores, purchases and continues of the previous game and the saved run, fixes the run's difficulty to the one chosen in the settings, and resets the game state for the level.
+    /// Versus games never use adaptive difficulty, and adaptive runs start at the base difficulty factor.
+    /// The level left by the previous game is added to the statistics, and single-player and co-op games count as played.
+    fn start_game(
+        &mut self,
+        ctx: &mut Context,
+        play_mode: PlayMode,
+        level: usize,
+    ) -> Result<(), DodgerError> {
+        self.record_level_stats(false)?;
+        self.play_mode = play_mode;
+        self.discard_saved_game()?;
+        if play_mode != PlayMode::Versus {
+            self.statistics.games_played += 1;
+            self.statistics.save(&self.settings_dir)?;
         }
-
-        self.falling_objects.retain(|obj| {
-            if let Some(timer) = obj.remove_timer {
-                !(obj.is_good || timer.elapsed() >= Duration::from_secs(1))
-            } else {
-                true
-            }
-        });
-        Ok(())
+        self.run_difficulty = self.settings.difficulty;
+        self.run_adaptive = self.settings.adaptive_difficulty && play_mode != PlayMode::Versus;
+        self.current_level = level;
+        self.total_score = 0;
+        self.continues = Continues::default();
+        self.purchases = Purchases::default();
+        for state in &mut self.world.players {
+            state.level_score = 0;
+        }
+        // The difficulty factor reached by the previous run does not carry over.
+        self.world.adaptive = None;
+        self.reset(ctx)
     }
 
-    /// **Resets the game state for a new level or restart.**
+    /// **Starts a networked versus game.**
     ///
     /// ## Parameters
-    /// `ctx`: the game context.
+    /// * `ctx`: the game context.
+    /// * `connection`: the connection to the other game instance.
+    /// * `local_player`: index of the local player (`0` for the host, `1` for the guest).
+    /// * `seed`: seed of the level's simulation, shared by both instances.
+    /// * `level`: index of the level to play.
+    /// * `difficulty`: the difficulty preset chosen by the host.
     ///
     /// ## Returns
-    /// `Ok(())` if the reset is successful, or a `DodgerError` if resource loading fails.
+    /// `Ok(())` if the game is started successfully, or a `DodgerError` if resource loading fails.
     ///
     /// ## Behavior
-    /// * Resets the score, lives, and timers.
-    /// * Clears the list of falling objects.
-    /// * Loads resources for the current level.
-    /// * Sets the game mode to `Playing`.
-    fn reset(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
-        if self.game_mode == GameMode::GameOver
-            || self.game_mode == GameMode::Victory
-            || self.game_mode == GameMode::LevelSelection
-        {
-            self.total_score = 0;
-        } else {
-            self.total_score += self.level_score;
+    /// The upgrades and continues of an earlier single-player or co-op run are discarded.
+    fn start_net_game(
+        &mut self,
+        ctx: &mut Context,
+        connection: Connection,
+        local_player: usize,
+        seed: u64,
+        level: usize,
+        difficulty: DifficultyPreset,
+    ) -> Result<(), DodgerError> {
+        self.lobby.close();
+        self.lobby.status = None;
+        self.net = Some(NetGame::new(connection, local_player));
+        self.play_mode = PlayMode::Versus;
+        self.run_difficulty = difficulty;
+        self.run_adaptive = false;
+        self.current_level = level;
+        self.continues = Continues::default();
+        self.purchases = Purchases::default();
+        self.reset_with_seed(ctx, seed)?;
+        self.total_score = 0;
+        self.game_started = true;
+        Ok(())
+    }
+
+    /// **Leaves a networked game and returns to the menus.**
+    ///
+    /// ## Parameters
+    /// `error`: the error that ended the game, if any; it is shown on the multiplayer screen.
+    fn leave_net_game(&mut self, error: Option<DodgerError>) {
+        if let Some(mut net) = self.net.take() {
+            net.leave();
         }
-        self.falling_objects.clear();
-        self.level_score = 0;
-        self.lives = LIVES;
-        self.level_start_time = Instant::now();
-        self.last_update = Instant::now();
-        self.level_complete_sound_played = false;
-        self.victory_sound_played = false;
-        self.game_over_sound_played = false;
+        self.game_started = false;
         self.is_paused = false;
-        self.resources = Resources::load_level(ctx, self.current_level, &self.levels)?;
-
-        self.player = Player::new(
-            ctx,
-            Point2::from_slice(&[WINDOW_WIDTH / 2.0, WINDOW_HEIGHT - 175.0]),
-            player_scaling(),
-            &self.resources.player_image,
-        )?;
+        self.play_mode = PlayMode::Single;
+        match error {
+            Some(err) => {
+                self.lobby.status = Some(err.to_string());
+                self.game_mode = GameMode::Multiplayer;
+            }
+            None => self.game_mode = GameMode::Menu,
+        }
+    }
 
-        self.game_mode = GameMode::Playing;
+    /// **Returns the number of players controlled on this game instance.**
+    ///
+    /// ## Behavior
+    /// In networked games only one of the players is local.
+    fn local_player_count(&self) -> usize {
+        match self.net {
+            Some(_) => 1,
+            None => self.play_mode.player_count(),
+        }
+    }
 
-        Ok(())
+    /// **Returns the score of the current level, summed over all players.**
+    fn level_score(&self) -> i32 {
+        self.world.level_score()
     }
 
     /// **Pauses the game.**
     ///
     /// ## Behavior
-    /// * Records the current time as the pause start time.
-    /// * Sets `is_paused` to `true`.
+    /// Sets `is_paused` to `true`; networked games cannot be paused.
     fn pause(&mut self) {
-        if self.game_mode == GameMode::Playing {
-            self.paused_time = Some(Instant::now());
+        if self.game_mode == GameMode::Playing && self.net.is_none() {
             self.is_paused = true;
         }
     }
 
     /// **Resumes the game from pause.**
+    fn resume(&mut self) {
+        self.is_paused = false;
+        self.run_saved = false;
+    }
+
+    /// **Checks whether a single-player or co-op run is being played and can still be continued.**
+    fn run_in_progress(&self) -> bool {
+        self.game_started
+            && self.net.is_none()
+            && self.play_mode != PlayMode::Versus
+            && !self.world.is_lost()
+            && !self.world.is_time_up()
+            && !self.objective_failed()
+    }
+
+    /// **Checks whether a single-player or co-op run waits on the level summary or in the shop before its next level.**
+    fn between_levels(&self) -> bool {
+        self.game_started
+            && self.can_save_run()
+            && matches!(self.game_mode, GameMode::LevelSummary | GameMode::NextLevel)
+            && self.current_level + 1 < self.levels.len()
+    }
+
+    /// **Checks whether the saved run can be continued from the main menu.**
     ///
     /// ## Behavior
-    /// * Calculates the duration of the pause.
-    /// * Adjusts the game timers to account for the pause duration.
-    /// * Sets `is_paused` to `false`.
-    fn resume(&mut self) {
-        if self.is_paused {
-            if let Some(paused_time) = self.paused_time {
-                let pause_duration = paused_time.elapsed();
-                self.level_start_time += pause_duration;
-                self.last_update += pause_duration;
-            }
-            self.paused_time = None;
-            self.is_paused = false;
-        }
+    /// Once a game is started in this session, the main menu resumes it instead.
+    fn can_continue(&self) -> bool {
+        !self.game_started && self.saved_game.is_some()
     }
 
-    /// **Calculates the remaining time for the current level.**
+    /// **Checks whether the game offers to save the run: only single-player and co-op runs on this instance can be saved.**
+    fn can_save_run(&self) -> bool {
+        self.net.is_none() && self.play_mode != PlayMode::Versus
+    }
+
+    /// **Saves the run in progress so it can be continued in a later session.**
     ///
     /// ## Returns
-    /// The remaining time in seconds.
+    /// `Ok(())` if the run is saved or no run is in progress, or a `DodgerError` if the file cannot be written.
     ///
     /// ## Behavior
-    /// * If the game is paused, calculates the remaining time based on the pause start time.
-    /// * If the game is not paused, calculates the remaining time based on the current time.
-    fn get_remaining_time(&self) -> u64 {
-        if self.is_paused {
-            let elapsed = self.last_update.duration_since(self.level_start_time);
-            let remaining = Duration::from_secs(LEVEL_DURATION_SECS).saturating_sub(elapsed);
-            remaining.as_secs()
-        } else {
-            let elapsed = self.level_start_time.elapsed();
-            let remaining = Duration::from_secs(LEVEL_DURATION_SECS).saturating_sub(elapsed);
-            remaining.as_secs()
+    /// A run between two levels is saved with its completed level, and continues in the shop.
+    fn save_run(&mut self) -> Result<(), DodgerError> {
+        let level_completed = self.between_levels();
+        if !self.run_in_progress() && !level_completed {
+            return Ok(());
         }
+        let save = SaveGame {
+            version: SAVE_VERSION,
+            level: self.current_level,
+            play_mode: self.play_mode,
+            difficulty: self.run_difficulty,
+            total_score: self.total_score,
+            purchases: self.purchases.clone(),
+            continues_used: self.continues.used,
+            level_completed,
+            world: self.world.snapshot(),
+        };
+        save.save(&self.settings_dir)?;
+        self.run_saved = true;
+        Ok(())
     }
 
-    /// **Updates the game state when in the main menu.**
+    /// **Continues the saved run.**
     ///
     /// ## Parameters
     /// `ctx`: the game context.
     ///
     /// ## Returns
-    /// `Ok(())` if the update is successful, or a 'DodgerError` if button handling fails.
+    /// `Ok(())` if the run is restored or nothing is saved, or a `DodgerError` if resource loading or restoring the level fails.
     ///
     /// ## Behavior
-    /// Handles button clicks for starting/resuming the game, selecting levels, opening the "How to Play" screen, and exiting the game.
-    fn update_menu(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
-        if !self.game_started {
-            if is_button_clicked(ctx, text_button_rect(&self.start_button)?) {
-                self.level_start_time = Instant::now();
-                self.last_update = Instant::now();
-                self.game_mode = GameMode::Playing;
-            }
-        } else if is_button_clicked(ctx, text_button_rect(&self.resume_button)?) {
-            if let Some(paused_time) = self.paused_time {
-                let pause_duration = paused_time.elapsed();
-                self.last_update += pause_duration;
-                self.level_start_time += pause_duration;
-            }
-            self.paused_time = None;
-            self.game_mode = GameMode::Playing;
-        }
+    /// * Restores the level, scores, lives, upgrades, continues used, elapsed time and falling objects; the run starts paused.
+    /// * A run saved after completing a level continues in the shop before the next level.
+    /// * The save is used up: it is deleted, so each save can be continued once.
+    fn continue_saved_game(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
+        let Some(save) = self.saved_game.take() else {
+            return Ok(());
+        };
+        SaveGame::delete(&self.settings_dir)?;
 
-        if is_button_clicked(ctx, text_button_rect(&self.select_level_button)?) {
-            self.game_mode = GameMode::LevelSelection;
-        }
+        self.current_level = save.level;
+        self.play_mode = save.play_mode;
+        self.run_difficulty = save.difficulty;
+        self.run_adaptive = save.world.adaptive_factor.is_some();
+        self.total_score = save.total_score;
+        self.resources = Resources::load_level(ctx, self.current_level, &self.levels)?;
+        self.world = World::restore(
+            &self.levels[self.current_level],
+            self.play_mode,
+            self.run_difficulty,
+            SpriteSizes::from_resources(&self.resources),
+            rand::random(),
+            &save.purchases.boosts(),
+            &save.world,
+        )?;
+        self.purchases = save.purchases;
+        self.continues = Continues {
+            used: save.continues_used,
+        };
 
-        if is_button_clicked(ctx, text_button_rect(&self.howtoplay_button)?) {
+        self.level_complete_sound_played = false;
+        self.victory_sound_played = false;
+        self.game_over_sound_played = false;
+        self.tick_accumulator = 0.0;
+        self.level_tally = LevelTally::default();
+        self.objective_met_secs = None;
+        let local_players = self.local_player_count();
+        self.movements = vec![MovementInput::default(); local_players];
+        self.dash_requests = vec![false; local_players];
+        self.is_paused = !save.level_completed;
+        if save.level_completed {
+            // The shop is not reached through the playing mode, which marks the game as started.
+            self.game_started = true;
+            self.game_mode = GameMode::NextLevel;
+        } else {
+            self.game_mode = GameMode::Playing;
+        }
+        Ok(())
+    }
+
+    /// **Deletes the saved run, which can no longer be continued once a new run starts or the saved run ends.**
+    fn discard_saved_game(&mut self) -> Result<(), DodgerError> {
+        self.saved_game = None;
+        self.run_saved = false;
+        SaveGame::delete(&self.settings_dir)
+    }
+
+    /// **Creates the button for saving the run, shown while the game is paused.**
+    ///
+    /// ## Returns
+    /// A result containing the button, labeled "Saved" once the run is saved, or a `DodgerError` if it cannot be created.
+    fn save_button(&self) -> Result<TextButton, DodgerError> {
+        let label = if self.run_saved { "Saved" } else { "Save" };
+        TextButton::new(
+            Point2::from_slice(&[WINDOW_WIDTH - 310.0, 10.0]),
+            Color::WHITE,
+            RectSize::from((100.0, 40.0)),
+            label.to_string(),
+            Color::BLACK,
+            BUTTON_TEXT_SIZE,
+            "button_font".to_string(),
+        )
+    }
+
+    /// **Opens the main menu from the playing mode.**
+    ///
+    /// ## Behavior
+    /// * The simulation is not advanced while the menu is open.
+    /// * Networked games cannot wait for the player, so opening the menu leaves them.
+    fn open_menu(&mut self) {
+        if self.net.is_some() {
+            self.leave_net_game(None);
+            return;
+        }
+        self.game_mode = GameMode::Menu;
+    }
+
+    /// **Returns the buttons of the current screen that can be focused, in navigation order.**
+    ///
+    /// ## Returns
+    /// A result containing the list of buttons (only the save button while the game is paused, none while it runs),
+    /// or a `DodgerError` if a button cannot be created.
+    fn focusable_buttons(&self) -> Result<Vec<TextButton>, DodgerError> {
+        let buttons = match self.game_mode {
+            GameMode::Menu => {
+                let first_button = if self.game_started {
+                    self.resume_button.clone()
+                } else {
+                    self.start_button.clone()
+                };
+                let mut buttons = vec![first_button];
+                if self.can_continue() {
+                    buttons.push(self.continue_button.clone());
+                }
+                buttons.extend([
+                    self.multiplayer_button.clone(),
+                    self.profile_button()?,
+                    self.select_level_button.clone(),
+                    self.stats_button.clone(),
+                    self.howtoplay_button.clone(),
+                    self.achievements_button.clone(),
+                    self.controls_button.clone(),
+                    self.difficulty_button()?,
+                    self.adaptive_button()?,
+                    self.exit_button.clone(),
+                ]);
+                buttons
+            }
+            GameMode::Multiplayer => vec![
+                self.versus_button.clone(),
+                self.coop_button.clone(),
+                self.host_button.clone(),
+                self.join_button.clone(),
+                self.back_to_menu_button.clone(),
+            ],
+            GameMode::HostLobby => vec![self.back_to_menu_button.clone()],
+            GameMode::JoinLobby => vec![
+                self.connect_button.clone(),
+                self.back_to_menu_button.clone(),
+            ],
+            GameMode::LevelSelection => (0..self.levels.len())
+                .map(|i| self.level_button(i))
+                .collect::<Result<Vec<_>, _>>()?,
+            GameMode::HowToPlay | GameMode::Stats | GameMode::Achievements => {
+                vec![self.back_to_menu_button.clone()]
+            }
+            GameMode::Profiles => {
+                let mut buttons = self.profile_buttons()?;
+                buttons.push(self.new_profile_button.clone());
+                buttons.push(self.rename_profile_button.clone());
+                buttons.push(self.delete_profile_button()?);
+                buttons.push(self.back_to_menu_button.clone());
+                buttons
+            }
+            GameMode::ProfileName => vec![
+                self.confirm_name_button.clone(),
+                self.back_to_menu_button.clone(),
+            ],
+            GameMode::Controls => {
+                let mut buttons = self.rebind_buttons()?;
+                buttons.push(self.control_scheme_button()?);
+                buttons.push(self.controls_player_button()?);
+                buttons.push(self.reset_controls_button.clone());
+                buttons.push(self.back_to_menu_button.clone());
+                buttons
+            }
+            GameMode::LevelIntro => vec![self.play_level_button.clone()],
+            GameMode::LevelSummary => vec![self.summary_button()?],
+            GameMode::NextLevel => {
+                let mut buttons = self.upgrade_buttons()?;
+                buttons.push(self.next_level_button.clone());
+                buttons
+            }
+            GameMode::LevelFailed => vec![self.retry_button.clone(), self.main_menu_button.clone()],
+            GameMode::GameOver => self.game_over_buttons()?,
+            GameMode::Victory => vec![self.restart_button.clone()],
+            GameMode::VersusResult if self.net.is_some() => vec![self.main_menu_button.clone()],
+            GameMode::VersusResult => {
+                vec![self.rematch_button.clone(), self.main_menu_button.clone()]
+            }
+            GameMode::Playing if self.is_paused && self.can_save_run() => {
+                vec![self.save_button()?]
+            }
+            GameMode::Playing => Vec::new(),
+        };
+        Ok(buttons)
+    }
+
+    /// **Returns the index of the button focused when the current screen is entered.**
+    ///
+    /// ## Behavior
+    /// * The level selection screen focuses the current level.
+    /// * Other screens focus their first button.
+    fn default_focus(&self) -> usize {
+        match self.game_mode {
+            GameMode::LevelSelection => self.current_level,
+            _ => 0,
+        }
+    }
+
+    /// **Attaches the menu focus to the current screen.**
+    fn sync_menu_focus(&mut self) {
+        let default_focus = self.default_focus();
+        self.menu_focus.sync(self.game_mode, default_focus);
+    }
+
+    /// **Handles a menu navigation command from the keyboard or a gamepad.**
+    ///
+    /// ## Parameters
+    /// `command`: the navigation command.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the command is handled successfully, or a `DodgerError` if the screen buttons cannot be created.
+    ///
+    /// ## Behavior
+    /// * `Up`/`Down` move the focus between the buttons of the current screen.
+    /// * `Confirm` activates the focused button; it is handled on the next update.
+    /// * `Back` returns to the main menu from the multiplayer, level selection, "How to Play", controls, statistics, achievements, profiles and playing screens,
+    ///   to the multiplayer screen from the host and join screens, and to the profiles screen from the profile name screen.
+    fn handle_menu_command(&mut self, command: MenuCommand) -> Result<(), DodgerError> {
+        self.sync_menu_focus();
+        let buttons_count = self.focusable_buttons()?.len();
+
+        match command {
+            MenuCommand::Up => self.menu_focus.focus_previous(buttons_count),
+            MenuCommand::Down => self.menu_focus.focus_next(buttons_count),
+            MenuCommand::Confirm => {
+                if buttons_count > 0 {
+                    self.menu_focus.activate();
+                }
+            }
+            MenuCommand::Back => match self.game_mode {
+                GameMode::Multiplayer
+                | GameMode::LevelSelection
+                | GameMode::HowToPlay
+                | GameMode::Controls
+                | GameMode::Stats
+                | GameMode::Achievements
+                | GameMode::Profiles => {
+                    self.game_mode = GameMode::Menu;
+                }
+                GameMode::HostLobby | GameMode::JoinLobby => self.close_lobby(),
+                GameMode::ProfileName => self.game_mode = GameMode::Profiles,
+                GameMode::Playing => self.open_menu(),
+                _ => (),
+            },
+        }
+        Ok(())
+    }
+
+    /// **Handles an input action triggered by the keyboard or a gamepad.**
+    ///
+    /// ## Parameters
+    /// * `player_index`: index of the player who triggered the action.
+    /// * `action`: the triggered action.
+    /// * `source`: the device the action was triggered on.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the action is handled successfully, or a `DodgerError` if menu navigation fails.
+    ///
+    /// ## Behavior
+    /// * Menu actions (`Up`, `Down`, `Confirm`, `Back`) are handled as menu navigation commands.
+    /// * `Pause` pauses or resumes the game.
+    /// * Movement actions are held until released on the same input source; the player is moved on each tick of the simulation.
+    /// * `Dash` makes the player dash on the next tick while the game is running and not paused.
+    /// * Actions of players not taking part in the game are ignored.
+    fn handle_action(
+        &mut self,
+        player_index: usize,
+        action: Action,
+        source: InputSource,
+    ) -> Result<(), DodgerError> {
+        if let Some(command) = MenuCommand::from_action(action) {
+            return self.handle_menu_command(command);
+        }
+
+        match action {
+            Action::Pause => match self.is_paused {
+                true => self.resume(),
+                false => self.pause(),
+            },
+            Action::MoveLeft | Action::MoveRight => {
+                if let Some(movement) = self.movements.get_mut(player_index) {
+                    movement.press(action, source);
+                }
+            }
+            Action::Dash if self.game_mode == GameMode::Playing && !self.is_paused => {
+                if let Some(dash) = self.dash_requests.get_mut(player_index) {
+                    *dash = true;
+                }
+            }
+            _ => (),
+        }
+        Ok(())
+    }
+
+    /// **Handles the release of an input action triggered by the keyboard or a gamepad.**
+    ///
+    /// ## Parameters
+    /// * `player_index`: index of the player who released the action.
+    /// * `action`: the released action.
+    /// * `source`: the device the action was released on.
+    fn handle_action_released(&mut self, player_index: usize, action: Action, source: InputSource) {
+        if let Some(movement) = self.movements.get_mut(player_index) {
+            movement.release(action, source);
+        }
+    }
+
+    /// **Returns the actions bound to a keyboard key, together with the player they belong to.**
+    ///
+    /// ## Parameters
+    /// `keycode`: the pressed or released key.
+    ///
+    /// ## Behavior
+    /// When two players play on this game instance, the second player's bindings take precedence over the first player's.
+    fn actions_for_key(&self, keycode: KeyCode) -> Vec<(usize, Action)> {
+        if self.local_player_count() > 1 {
+            let player_two_actions = self.settings.player_two_bindings.actions_for_key(keycode);
+            if !player_two_actions.is_empty() {
+                return player_two_actions.into_iter().map(|a| (1, a)).collect();
+            }
+        }
+        self.settings
+            .bindings
+            .actions_for_key(keycode)
+            .into_iter()
+            .map(|a| (0, a))
+            .collect()
+    }
+
+    /// **Returns the index of the player controlling a gamepad.**
+    ///
+    /// ## Behavior
+    /// Gamepads are assigned to players in the order they were connected; extra gamepads control the last player.
+    fn gamepad_player(&self, id: GamepadId) -> usize {
+        let position = self.gamepads.iter().position(|pad| *pad == id).unwrap_or(0);
+        position.min(self.local_player_count() - 1)
+    }
+
+    /// **Detects connected and disconnected gamepads.**
+    ///
+    /// ## Parameters
+    /// `ctx`: the game context.
+    ///
+    /// ## Behavior
+    /// * Compares the connected gamepads with the ones seen during the previous update.
+    /// * When a gamepad is disconnected, its held inputs are released and a running game is paused.
+    /// * Newly connected gamepads can be used right away.
+    fn update_gamepads(&mut self, ctx: &mut Context) {
+        let gamepads: Vec<GamepadId> = ctx.gamepad.gamepads().map(|(id, _)| id).collect();
+        let disconnected = self.gamepads.iter().any(|id| !gamepads.contains(id));
+
+        if disconnected {
+            for movement in &mut self.movements {
+                movement.clear();
+            }
+            self.pause();
+        }
+        self.gamepads = gamepads;
+    }
+
+    /// **Binds a new key or button to the action waiting for it on the controls screen.**
+    ///
+    /// ## Parameters
+    /// * `player_index`: index of the player whose bindings are changed.
+    /// * `action`: the action being rebound.
+    /// * `binding`: the pressed key or button.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the settings are stored successfully, or a `DodgerError` if they cannot be written.
+    ///
+    /// ## Behavior
+    /// * Inputs that cannot be stored are ignored, leaving the bindings unchanged.
+    /// * The second player only has keyboard bindings, so gamepad buttons are ignored for them.
+    fn rebind(
+        &mut self,
+        player_index: usize,
+        action: Action,
+        binding: Binding,
+    ) -> Result<(), DodgerError> {
+        if !binding.is_supported() {
+            return Ok(());
+        }
+        match (player_index, binding) {
+            (0, _) => self.settings.bindings.rebind(action, binding),
+            (_, Binding::Key(_)) => self.settings.player_two_bindings.rebind(action, binding),
+            _ => return Ok(()),
+        }
+        for movement in &mut self.movements {
+            movement.clear();
+        }
+        self.settings.save(&self.settings_dir)
+    }
+
+    /// **Returns the bindings of the player shown on the controls screen.**
+    fn controls_bindings(&self) -> &InputBindings {
+        match self.controls_player {
+            0 => &self.settings.bindings,
+            _ => &self.settings.player_two_bindings,
+        }
+    }
+
+    /// **Returns the actions listed on the controls screen for the shown player.**
+    fn controls_actions(&self) -> &'static [Action] {
+        match self.controls_player {
+            0 => &Action::ALL,
+            _ => &Action::PLAYER_TWO,
+        }
+    }
+
+    /// **Updates the game state when in the main menu.**
+    ///
+    /// ## Parameters
+    /// `ctx`: the game context.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the update is successful, or a 'DodgerError` if button handling fails.
+    ///
+    /// ## Behavior
+    /// * Handles button clicks and focused button activations for starting/resuming the game, opening the multiplayer and profiles screens, selecting levels, opening the statistics, "How to Play", achievements and controls screens, and exiting the game.
+    /// * Handles the continue button, shown next to the start button when a saved run can be continued; it shifts the focus indices of the buttons below.
+    /// * Handles the difficulty button, which switches to the next preset and stores it in the settings; the preset applies from the next run on.
+    /// * Handles the adaptive difficulty button, which turns adaptive difficulty on or off for the next run.
+    /// * Before a game is started, advances the demo played by the bot behind the menu.
+    fn update_menu(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
+        if !self.game_started {
+            self.update_demo(ctx)?;
+        }
+        let activated = self.menu_focus.take_activation();
+        let offset = usize::from(self.can_continue());
+
+        if self.can_continue()
+            && (is_button_clicked(ctx, text_button_rect(&self.continue_button)?)
+                || activated == Some(1))
+        {
+            self.continue_saved_game(ctx)?;
+            return Ok(());
+        }
+
+        if !self.game_started {
+            if is_button_clicked(ctx, text_button_rect(&self.start_button)?) || activated == Some(0)
+            {
+                self.start_game(ctx, PlayMode::Single, self.current_level)?;
+            }
+        } else if is_button_clicked(ctx, text_button_rect(&self.resume_button)?)
+            || activated == Some(0)
+        {
+            self.game_mode = GameMode::Playing;
+        }
+
+        if is_button_clicked(ctx, text_button_rect(&self.multiplayer_button)?)
+            || activated == Some(1 + offset)
+        {
+            self.game_mode = GameMode::Multiplayer;
+        }
+
+        if is_button_clicked(ctx, text_button_rect(&self.profile_button()?)?)
+            || activated == Some(2 + offset)
+        {
+            self.confirm_profile_delete = false;
+            self.game_mode = GameMode::Profiles;
+        }
+
+        if is_button_clicked(ctx, text_button_rect(&self.select_level_button)?)
+            || activated == Some(3 + offset)
+        {
+            self.game_mode = GameMode::LevelSelection;
+        }
+
+        if is_button_clicked(ctx, text_button_rect(&self.stats_button)?)
+            || activated == Some(4 + offset)
+        {
+            self.game_mode = GameMode::Stats;
+        }
+
+        if is_button_clicked(ctx, text_button_rect(&self.howtoplay_button)?)
+            || activated == Some(5 + offset)
+        {
             self.game_mode = GameMode::HowToPlay;
         }
 
-        if is_button_clicked(ctx, text_button_rect(&self.exit_button)?) {
-            ctx.request_quit();
-        }
+        if is_button_clicked(ctx, text_button_rect(&self.achievements_button)?)
+            || activated == Some(6 + offset)
+        {
+            self.game_mode = GameMode::Achievements;
+        }
+
+        if is_button_clicked(ctx, text_button_rect(&self.controls_button)?)
+            || activated == Some(7 + offset)
+        {
+            self.game_mode = GameMode::Controls;
+        }
+
+        if is_button_clicked(ctx, text_button_rect(&self.difficulty_button()?)?)
+            || activated == Some(8 + offset)
+        {
+            self.settings.difficulty = self.settings.difficulty.next();
+            self.settings.save(&self.settings_dir)?;
+        }
+
+        if is_button_clicked(ctx, text_button_rect(&self.adaptive_button()?)?)
+            || activated == Some(9 + offset)
+        {
+            self.settings.adaptive_difficulty = !self.settings.adaptive_difficulty;
+            self.settings.save(&self.settings_dir)?;
+        }
+
+        if is_button_clicked(ctx, text_button_rect(&self.exit_button)?)
+            || activated == Some(10 + offset)
+        {
+            ctx.request_quit();
+        }
+        Ok(())
+    }
+
+    /// **Draws the main menu on the canvas.**
+    ///
+    /// ## Parameters
+    /// * `ctx`: the game context.
+    /// * `canvas`: canvas to draw on.
+    ///
+    /// ## Returns
+    /// `Ok(())` if drawing is successful, or a `DodgerError` if button drawing fails.
+    ///
+    /// ## Behavior
+    /// Before a game is started, the demo played by the bot is drawn behind the buttons.
+    fn draw_menu(&mut self, ctx: &mut Context, canvas: &mut Canvas) -> Result<(), DodgerError> {
+        draw_background(canvas, &self.resources.menu_background_image);
+        if !self.game_started {
+            for obj in &self.demo.objects {
+                obj.draw(canvas, &self.resources, self.demo.tick);
+            }
+            for state in &self.demo.players {
+                state.player.draw(
+                    canvas,
+                    &self.resources.player_image,
+                    player_scaling(),
+                    Color::new(1.0, 1.0, 1.0, 0.6),
+                    self.demo.tick,
+                );
+            }
+            draw_button_with_text(ctx, canvas, self.start_button.clone())?;
+            if self.can_continue() {
+                draw_button_with_text(ctx, canvas, self.continue_button.clone())?;
+            }
+        } else {
+            draw_button_with_text(ctx, canvas, self.resume_button.clone())?;
+        }
+        draw_button_with_text(ctx, canvas, self.exit_button.clone())?;
+        draw_button_with_text(ctx, canvas, self.multiplayer_button.clone())?;
+        draw_button_with_text(ctx, canvas, self.profile_button()?)?;
+        draw_button_with_text(ctx, canvas, self.select_level_button.clone())?;
+        draw_button_with_text(ctx, canvas, self.stats_button.clone())?;
+        draw_button_with_text(ctx, canvas, self.howtoplay_button.clone())?;
+        draw_button_with_text(ctx, canvas, self.achievements_button.clone())?;
+        draw_button_with_text(ctx, canvas, self.controls_button.clone())?;
+
+        let difficulty_button = self.difficulty_button()?;
+        let difficulty_text = DrawText::new(
+            Point2::from_slice(&[
+                difficulty_button.coords.x - 130.0,
+                difficulty_button.coords.y + 12.0,
+            ]),
+            "Difficulty:".to_string(),
+            "text_font".to_string(),
+            BUTTON_TEXT_SIZE,
+            Color::WHITE,
+        )?;
+        draw_text(canvas, difficulty_text)?;
+        draw_button_with_text(ctx, canvas, difficulty_button)?;
+        draw_button_with_text(ctx, canvas, self.adaptive_button()?)?;
+        Ok(())
+    }
+
+    /// **Creates the button for switching the difficulty preset in the main menu.**
+    ///
+    /// ## Returns
+    /// A result containing the button labeled with the preset of the next run, or a `DodgerError` if it cannot be created.
+    fn difficulty_button(&self) -> Result<TextButton, DodgerError> {
+        TextButton::new(
+            start_point_of_button_in_set(5, 300.0),
+            Color::WHITE,
+            text_button_rectsize(),
+            self.settings.difficulty.name().to_string(),
+            Color::BLACK,
+            BUTTON_TEXT_SIZE,
+            "button_font".to_string(),
+        )
+    }
+
+    /// **Creates the button for turning adaptive difficulty on or off, next to the difficulty button.**
+    ///
+    /// ## Returns
+    /// A result containing the button labeled with the setting of the next run, or a `DodgerError` if it cannot be created.
+    fn adaptive_button(&self) -> Result<TextButton, DodgerError> {
+        let difficulty_point = start_point_of_button_in_set(5, 300.0);
+        let state = if self.settings.adaptive_difficulty {
+            "On"
+        } else {
+            "Off"
+        };
+        TextButton::new(
+            Point2::from_slice(&[
+                difficulty_point.x + TEXT_BUTTON_WIDTH + BUTTON_SPACING,
+                difficulty_point.y,
+            ]),
+            Color::WHITE,
+            text_button_rectsize(),
+            format!("Adaptive: {}", state),
+            Color::BLACK,
+            BUTTON_TEXT_SIZE,
+            "button_font".to_string(),
+        )
+    }
+
+    /// **Creates the button for opening the profiles screen, next to the multiplayer button.**
+    ///
+    /// ## Returns
+    /// A result containing the button labeled with the name of the active profile, or a `DodgerError` if it cannot be created.
+    fn profile_button(&self) -> Result<TextButton, DodgerError> {
+        let multiplayer_point = start_point_of_button_in_set(1, 300.0);
+        TextButton::new(
+            Point2::from_slice(&[
+                multiplayer_point.x + TEXT_BUTTON_WIDTH + BUTTON_SPACING,
+                multiplayer_point.y,
+            ]),
+            Color::WHITE,
+            text_button_rectsize(),
+            self.profiles.active().name.clone(),
+            Color::BLACK,
+            BUTTON_TEXT_SIZE,
+            "button_font".to_string(),
+        )
+    }
+
+    /// **Advances the demo played by the bot behind the main menu.**
+    ///
+    /// ## Parameters
+    /// `ctx`: the game context.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the update is successful, or a `DodgerError` if the next demo cannot be created.
+    ///
+    /// ## Behavior
+    /// * Steps the demo at `TICK_RATE` ticks per second with the bot's input; the demo is silent.
+    /// * When the demo's level ends, a new one starts with another seed.
+    fn update_demo(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
+        let tick_secs = 1.0 / TICK_RATE as f32;
+        self.demo_accumulator = (self.demo_accumulator + ctx.time.delta().as_secs_f32())
+            .min(tick_secs * MAX_CATCH_UP_TICKS as f32);
+
+        while self.demo_accumulator >= tick_secs {
+            self.demo_accumulator -= tick_secs;
+            let input = self.demo_bot.act(&self.demo, 0);
+            self.demo.step(&[input]);
+
+            if self.demo.is_lost() || self.demo.is_time_up() {
+                self.demo = World::new(
+                    &self.levels[self.current_level],
+                    PlayMode::Single,
+                    DifficultyPreset::Normal,
+                    SpriteSizes::from_resources(&self.resources),
+                    rand::random(),
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// **Updates the game state when in the multiplayer screen.**
+    ///
+    /// ## Parameters
+    /// `ctx`: the game context.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the update is successful, or a `DodgerError` if button handling fails.
+    ///
+    /// ## Behavior
+    /// Handles button clicks and focused button activations for starting a versus or co-op game from the first level, opening the host and join screens, and returning to the main menu.
+    fn update_multiplayer(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
+        let activated = self.menu_focus.take_activation();
+        if is_button_clicked(ctx, text_button_rect(&self.versus_button)?) || activated == Some(0) {
+            self.start_game(ctx, PlayMode::Versus, 0)?;
+        }
+
+        if is_button_clicked(ctx, text_button_rect(&self.coop_button)?) || activated == Some(1) {
+            self.start_game(ctx, PlayMode::Coop, 0)?;
+        }
+
+        if is_button_clicked(ctx, text_button_rect(&self.host_button)?) || activated == Some(2) {
+            match Host::bind(DEFAULT_PORT) {
+                Ok(host) => {
+                    self.lobby.host = Some(host);
+                    self.lobby.status = None;
+                }
+                Err(err) => self.lobby.status = Some(err.to_string()),
+            }
+            self.game_mode = GameMode::HostLobby;
+        }
+
+        if is_button_clicked(ctx, text_button_rect(&self.join_button)?) || activated == Some(3) {
+            self.lobby.status = None;
+            self.game_mode = GameMode::JoinLobby;
+        }
+
+        if is_button_clicked(ctx, text_button_rect(&self.back_to_menu_button)?)
+            || activated == Some(4)
+        {
+            self.lobby.status = None;
+            self.game_mode = GameMode::Menu;
+        }
+        Ok(())
+    }
+
+    /// **Draws the multiplayer screen on the canvas.**
+    ///
+    /// ## Parameters
+    /// * `ctx`: the game context.
+    /// * `canvas`: canvas to draw on.
+    ///
+    /// ## Returns
+    /// `Ok(())` if drawing is successful, or a `DodgerError` if text or button drawing fails.
+    ///
+    /// ## Behavior
+    /// Draws the background, the title, the controls of both players, the last network error if any, and the versus, co-op, host, join and back buttons.
+    fn draw_multiplayer(
+        &mut self,
+        ctx: &mut Context,
+        canvas: &mut Canvas,
+    ) -> Result<(), DodgerError> {
+        draw_background(canvas, &self.resources.menu_background_image);
+
+        let title = DrawText::new(
+            Point2::from_slice(&[WINDOW_WIDTH / 2.0 - 130.0, 100.0]),
+            "Multiplayer".to_string(),
+            "text_font".to_string(),
+            48.0,
+            Color::WHITE,
+        )?;
+        draw_text(canvas, title)?;
+
+        let player_bindings = [&self.settings.bindings, &self.settings.player_two_bindings];
+        for (i, bindings) in player_bindings.into_iter().enumerate() {
+            let line = format!(
+                "Player {}: {} / {} to move, {} to dash",
+                i + 1,
+                bindings.describe(Action::MoveLeft),
+                bindings.describe(Action::MoveRight),
+                bindings.describe(Action::Dash)
+            );
+            let controls_text = DrawText::new(
+                Point2::from_slice(&[60.0, 180.0 + i as f32 * 40.0]),
+                line,
+                "text_font".to_string(),
+                BUTTON_TEXT_SIZE,
+                Color::WHITE,
+            )?;
+            draw_text(canvas, controls_text)?;
+        }
+        self.draw_lobby_status(canvas)?;
+
+        draw_button_with_text(ctx, canvas, self.versus_button.clone())?;
+        draw_button_with_text(ctx, canvas, self.coop_button.clone())?;
+        draw_button_with_text(ctx, canvas, self.host_button.clone())?;
+        draw_button_with_text(ctx, canvas, self.join_button.clone())?;
+        draw_button_with_text(ctx, canvas, self.back_to_menu_button.clone())?;
+        Ok(())
+    }
+
+    /// **Stops hosting or joining and returns to the multiplayer screen.**
+    fn close_lobby(&mut self) {
+        self.lobby.close();
+        self.lobby.status = None;
+        self.game_mode = GameMode::Multiplayer;
+    }
+
+    /// **Draws the last network error or progress message, if any.**
+    ///
+    /// ## Parameters
+    /// `canvas`: canvas to draw on.
+    ///
+    /// ## Returns
+    /// `Ok(())` if drawing is successful, or a `DodgerError` if text drawing fails.
+    fn draw_lobby_status(&self, canvas: &mut Canvas) -> Result<(), DodgerError> {
+        if let Some(status) = &self.lobby.status {
+            let status_text = DrawText::new(
+                Point2::from_slice(&[60.0, 260.0]),
+                status.clone(),
+                "text_font".to_string(),
+                BUTTON_TEXT_SIZE,
+                Color::WHITE,
+            )?;
+            draw_text(canvas, status_text)?;
+        }
+        Ok(())
+    }
+
+    /// **Updates the game state when in the host screen.**
+    ///
+    /// ## Parameters
+    /// `ctx`: the game context.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the update is successful, or a `DodgerError` if button handling or loading the level fails.
+    ///
+    /// ## Behavior
+    /// * Accepts the first player who joins, sends them the level and seed, and starts a networked versus game.
+    /// * Network errors are shown on the screen.
+    /// * Handles button clicks and focused button activations for returning to the multiplayer screen.
+    fn update_host_lobby(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
+        let activated = self.menu_focus.take_activation();
+        if is_button_clicked(ctx, text_button_rect(&self.back_to_menu_button)?)
+            || activated == Some(0)
+        {
+            self.close_lobby();
+            return Ok(());
+        }
+
+        let Some(host) = &self.lobby.host else {
+            return Ok(());
+        };
+        let difficulty = self.settings.difficulty;
+        let accepted = host.accept().and_then(|connection| match connection {
+            Some(mut connection) => {
+                let seed = rand::random();
+                connection.send(&NetMessage::Hello {
+                    version: PROTOCOL_VERSION,
+                    seed,
+                    level: 0,
+                    difficulty,
+                })?;
+                Ok(Some((connection, seed)))
+            }
+            None => Ok(None),
+        });
+
+        match accepted {
+            Ok(Some((connection, seed))) => {
+                self.start_net_game(ctx, connection, 0, seed, 0, difficulty)?
+            }
+            Ok(None) => (),
+            Err(err) => {
+                self.lobby.host = None;
+                self.lobby.status = Some(err.to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// **Draws the host screen on the canvas.**
+    ///
+    /// ## Parameters
+    /// * `ctx`: the game context.
+    /// * `canvas`: canvas to draw on.
+    ///
+    /// ## Returns
+    /// `Ok(())` if drawing is successful, or a `DodgerError` if text or button drawing fails.
+    ///
+    /// ## Behavior
+    /// Draws the background, the title, the port players can join on (or the error that prevents hosting), and the back button.
+    fn draw_host_lobby(
+        &mut self,
+        ctx: &mut Context,
+        canvas: &mut Canvas,
+    ) -> Result<(), DodgerError> {
+        draw_background(canvas, &self.resources.menu_background_image);
+
+        let title = DrawText::new(
+            Point2::from_slice(&[WINDOW_WIDTH / 2.0 - 120.0, 100.0]),
+            "Host Game".to_string(),
+            "text_font".to_string(),
+            48.0,
+            Color::WHITE,
+        )?;
+        draw_text(canvas, title)?;
+
+        if self.lobby.host.is_some() {
+            let waiting_text = DrawText::new(
+                Point2::from_slice(&[60.0, 200.0]),
+                format!("Waiting for a player to join on port {}...", DEFAULT_PORT),
+                "text_font".to_string(),
+                BUTTON_TEXT_SIZE,
+                Color::WHITE,
+            )?;
+            draw_text(canvas, waiting_text)?;
+        }
+        self.draw_lobby_status(canvas)?;
+
+        draw_button_with_text(ctx, canvas, self.back_to_menu_button.clone())?;
+        Ok(())
+    }
+
+    /// **Updates the game state when in the join screen.**
+    ///
+    /// ## Parameters
+    /// `ctx`: the game context.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the update is successful, or a `DodgerError` if button handling or loading the level fails.
+    ///
+    /// ## Behavior
+    /// * Handles button clicks and focused button activations for connecting to the typed address and returning to the multiplayer screen.
+    /// * Takes the connection once it is open, then waits for the host to send the level and seed and starts a networked versus game.
+    /// * Network errors and protocol version mismatches are shown on the screen.
+    fn update_join_lobby(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
+        let activated = self.menu_focus.take_activation();
+        if is_button_clicked(ctx, text_button_rect(&self.connect_button)?) || activated == Some(0) {
+            self.connect_to_host();
+        }
+
+        if is_button_clicked(ctx, text_button_rect(&self.back_to_menu_button)?)
+            || activated == Some(1)
+        {
+            self.close_lobby();
+            return Ok(());
+        }
+
+        if let Some(connecting) = &self.lobby.connecting {
+            match connecting.poll() {
+                Ok(Some(connection)) => {
+                    self.lobby.connecting = None;
+                    self.lobby.pending = Some(connection);
+                    self.lobby.status = Some("Connected, waiting for the host...".to_string());
+                }
+                Ok(None) => (),
+                Err(err) => {
+                    self.lobby.connecting = None;
+                    self.lobby.status = Some(err.to_string());
+                }
+            }
+        }
+
+        let Some(pending) = &mut self.lobby.pending else {
+            return Ok(());
+        };
+        let hello = pending.poll().and_then(|messages| {
+            messages
+                .into_iter()
+                .find_map(|message| match message {
+                    NetMessage::Hello {
+                        version,
+                        seed,
+                        level,
+                        difficulty,
+                    } => Some(if version == PROTOCOL_VERSION {
+                        Ok((seed, level, difficulty))
+                    } else {
+                        Err(DodgerError::Network(format!(
+                            "the host uses protocol version {}, this game uses {}",
+                            version, PROTOCOL_VERSION
+                        )))
+                    }),
+                    _ => None,
+                })
+                .transpose()
+        });
+
+        match hello {
+            Ok(Some((seed, level, difficulty))) => {
+                if let Some(connection) = self.lobby.pending.take() {
+                    self.start_net_game(ctx, connection, 1, seed, level, difficulty)?;
+                }
+            }
+            Ok(None) => (),
+            Err(err) => {
+                self.lobby.pending = None;
+                self.lobby.status = Some(err.to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// **Starts connecting to the address typed on the join screen.**
+    ///
+    /// ## Behavior
+    /// Connecting happens in the background; once connected, the connection waits for the host to start the game.
+    fn connect_to_host(&mut self) {
+        if self.lobby.pending.is_some() || self.lobby.connecting.is_some() {
+            return;
+        }
+        self.lobby.connecting = Some(Connecting::start(&self.lobby.address));
+        self.lobby.status = Some("Connecting...".to_string());
+    }
+
+    /// **Draws the join screen on the canvas.**
+    ///
+    /// ## Parameters
+    /// * `ctx`: the game context.
+    /// * `canvas`: canvas to draw on.
+    ///
+    /// ## Returns
+    /// `Ok(())` if drawing is successful, or a `DodgerError` if text or button drawing fails.
+    ///
+    /// ## Behavior
+    /// Draws the background, the title, the typed address, the connection status, and the connect and back buttons.
+    fn draw_join_lobby(
+        &mut self,
+        ctx: &mut Context,
+        canvas: &mut Canvas,
+    ) -> Result<(), DodgerError> {
+        draw_background(canvas, &self.resources.menu_background_image);
+
+        let title = DrawText::new(
+            Point2::from_slice(&[WINDOW_WIDTH / 2.0 - 110.0, 100.0]),
+            "Join Game".to_string(),
+            "text_font".to_string(),
+            48.0,
+            Color::WHITE,
+        )?;
+        draw_text(canvas, title)?;
+
+        let address_text = DrawText::new(
+            Point2::from_slice(&[60.0, 200.0]),
+            format!("Host address: {}_", self.lobby.address),
+            "text_font".to_string(),
+            TEXT_SIZE,
+            Color::WHITE,
+        )?;
+        draw_text(canvas, address_text)?;
+        self.draw_lobby_status(canvas)?;
+
+        draw_button_with_text(ctx, canvas, self.connect_button.clone())?;
+        draw_button_with_text(ctx, canvas, self.back_to_menu_button.clone())?;
+        Ok(())
+    }
+
+    /// **Updates the game state when in the playing mode.**
+    ///
+    /// ## Parameters
+    /// `ctx`: the game context.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the update is successful, or a `DodgerError` if sound playback fails.
+    ///
+    /// ## Behavior
+    /// * Handles button clicks for toggling audio and returning to the menu, and clicks and focused activations of the save button while the game is paused.
+    /// * Advances the simulation at `TICK_RATE` ticks per second with the inputs of the players; networked games wait for the other player's inputs.
+    /// * Plays the sounds of catches and hits, and unlocks the achievements met during the level.
+    /// * Remembers the time left when the level's objective is met, for the time bonus.
+    /// * Finishes the level when the time is up, when no lives are left, or as soon as its objective can no longer be met.
+    /// * A lost connection ends a networked game and is shown on the multiplayer screen.
+    fn update_playing(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
+        if !self.game_started {
+            self.game_started = true;
+        }
+
+        if is_button_clicked(ctx, icon_button_rect(&self.audio_button)?) {
+            self.audio.is_muted = !self.audio.is_muted;
+        }
+
+        if is_button_clicked(ctx, text_button_rect(&self.menu_button)?) {
+            self.open_menu();
+            return Ok(());
+        }
+
+        if self.is_paused {
+            let activated = self.menu_focus.take_activation();
+            if self.can_save_run()
+                && (is_button_clicked(ctx, text_button_rect(&self.save_button()?)?)
+                    || activated == Some(0))
+            {
+                self.save_run()?;
+            }
+            return Ok(());
+        }
+
+        let tick_secs = 1.0 / TICK_RATE as f32;
+        self.tick_accumulator = (self.tick_accumulator + ctx.time.delta().as_secs_f32())
+            .min(tick_secs * MAX_CATCH_UP_TICKS as f32);
+
+        while self.tick_accumulator >= tick_secs {
+            let inputs = match self.next_inputs(ctx) {
+                Ok(Some(inputs)) => inputs,
+                Ok(None) => break,
+                Err(err) => {
+                    self.leave_net_game(Some(err));
+                    return Ok(());
+                }
+            };
+            self.tick_accumulator -= tick_secs;
+
+            let events = self.world.step(&inputs);
+            self.level_tally.record_tick(&events);
+            self.check_achievements(None)?;
+            self.play_world_events(ctx, &events)?;
+            if self.objective_met_secs.is_none() && self.objective_met() {
+                self.objective_met_secs = Some(self.world.remaining_secs());
+            }
+
+            if self.world.is_lost() || self.world.is_time_up() || self.objective_failed() {
+                self.finish_level()?;
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// **Collects the inputs of the next simulation tick.**
+    ///
+    /// ## Parameters
+    /// `ctx`: the game context.
+    ///
+    /// ## Returns
+    /// * `Ok(Some(inputs))`: the input of each player, ordered by player index.
+    /// * `Ok(None)`: a networked game is waiting for the other player's input.
+    /// * A `DodgerError` if the connection to the other player was lost.
+    ///
+    /// ## Behavior
+    /// * Each local player moves according to their held movement inputs.
+    /// * With the mouse-follow control scheme, the first local player moves toward the mouse pointer while no movement input is held.
+    fn next_inputs(&mut self, ctx: &mut Context) -> Result<Option<Vec<PlayerInput>>, DodgerError> {
+        if self
+            .net
+            .as_ref()
+            .is_some_and(|net| !net.lockstep.needs_local_input())
+        {
+            // The local input for this tick was already sent.
+            let net = self.net.as_mut().ok_or(DodgerError::Unexpected)?;
+            return net.next_inputs(PlayerInput::default());
+        }
+
+        let local_player = self.net.as_ref().map_or(0, |net| net.local_player);
+        let max_distance = self.world.players[local_player].player.speed / TICK_RATE as f32;
+        let mut inputs = Vec::with_capacity(self.movements.len());
+        for (i, movement) in self.movements.iter().enumerate() {
+            let mut axis = movement.axis();
+            if axis == 0.0 && i == 0 && self.settings.control_scheme == ControlScheme::MouseFollow {
+                let player = &self.world.players[local_player].player;
+                axis = mouse_follow_dx(
+                    player.coords.x,
+                    player.size.w,
+                    ctx.mouse.position().x,
+                    max_distance,
+                ) / max_distance;
+            }
+            let dash = std::mem::take(&mut self.dash_requests[i]);
+            inputs.push(PlayerInput { axis, dash });
+        }
+
+        match &mut self.net {
+            Some(net) => net.next_inputs(inputs[0]),
+            None => Ok(Some(inputs)),
+        }
+    }
+
+    /// **Plays the sounds of the events of a simulation tick.**
+    ///
+    /// ## Parameters
+    /// * `ctx`: the game context.
+    /// * `events`: the catches and hits of the tick.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the sounds are played successfully, or a `DodgerError` if sound playback fails.
+    fn play_world_events(
+        &mut self,
+        ctx: &mut Context,
+        events: &[WorldEvent],
+    ) -> Result<(), DodgerError> {
+        for event in events {
+            let sound = match event {
+                WorldEvent::Caught {
+                    value: Some(GoodObjectValue::High) | Some(GoodObjectValue::Coop),
+                    ..
+                } => "good_collision_high",
+                WorldEvent::Caught { .. } => "good_collision",
+                WorldEvent::Hit { .. } => "bad_collision",
+                WorldEvent::Missed { .. } => continue,
+            };
+            self.audio.play_sound(ctx, sound.to_string())?;
+        }
+        Ok(())
+    }
+
+    /// **Leaves the playing mode once the level's simulation is over.**
+    ///
+    /// ## Behavior
+    /// * Versus games always end on the result screen.
+    /// * Otherwise, the game is over when no lives are left, and the retry screen follows when the level's objective failed;
+    ///   when the objective is completed, the level summary follows, and then the next level or the victory screen.
+    /// * Completed levels are recorded in the progress with their score and stars, which unlocks the next level.
+    /// * Completed levels earn a time bonus for the seconds left when the objective was met and a bonus for the lives left,
+    ///   both added to the total score.
+    /// * The level is added to the statistics, and the achievements met by the level are unlocked, see `record_level_end`;
+    ///   a lost level is only added once the player picks an option on the "Game Over" screen, so a continued level is added once.
+    /// * Runs that end on the "Game Over" screen are reported with their cause of death and compared with the personal best.
+    /// * Runs that reach the victory screen are ended, see `end_run`; lost runs only end once the player starts over or leaves
+    ///   the "Game Over" screen, see `end_lost_run`.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the level is finished successfully, or a `DodgerError` if the statistics, the achievements, the progress or the high scores cannot be stored,
+    /// or the saved game cannot be deleted.
+    fn finish_level(&mut self) -> Result<(), DodgerError> {
+        self.game_mode = if self.play_mode == PlayMode::Versus {
+            GameMode::VersusResult
+        } else if self.world.is_lost() {
+            GameMode::GameOver
+        } else if self.objective_failed() {
+            GameMode::LevelFailed
+        } else if self.current_level + 1 < self.levels.len() {
+            GameMode::NextLevel
+        } else {
+            GameMode::Victory
+        };
+
+        let completed = matches!(self.game_mode, GameMode::NextLevel | GameMode::Victory);
+        let tally = self.level_tally;
+        // A lost run can still be continued or its level retried, so it does not end yet.
+        self.run_end_pending = self.game_mode == GameMode::GameOver;
+        if !self.run_end_pending {
+            self.record_level_end(completed)?;
+        }
+
+        if completed {
+            let score = self.level_score();
+            let stars = self.levels[self.current_level].stars(score, self.run_difficulty);
+            if self.progress.record(self.current_level, score, stars) {
+                self.progress.save(&self.settings_dir)?;
+            }
+
+            let lives = self.world.players.iter().map(|state| state.lives).max();
+            let (time_bonus, lives_bonus) = level_bonuses(
+                self.objective_met_secs.unwrap_or(0),
+                lives.unwrap_or(0),
+                self.run_difficulty,
+            );
+            self.total_score += time_bonus + lives_bonus;
+            self.level_summary = Some(LevelSummary {
+                tally,
+                time_bonus,
+                lives_bonus,
+                level_score: score,
+                total_score: self.total_score + score,
+                stars,
+                victory: self.game_mode == GameMode::Victory,
+                elapsed_secs: 0.0,
+            });
+        }
+
+        if self.game_mode == GameMode::GameOver {
+            self.game_over_report = Some(GameOverReport {
+                final_score: self.total_score + self.level_score(),
+                level: self.current_level,
+                cause: self.cause_of_death(),
+                tick: self.world.tick,
+                difficulty: self.run_difficulty,
+                previous_best: self
+                    .high_scores
+                    .best(self.run_difficulty)
+                    .map(|entry| entry.score),
+                ranked: self.run_ranked(),
+            });
+        }
+
+        if self.game_mode == GameMode::Victory {
+            self.end_run()?;
+        }
+
+        if completed {
+            self.game_mode = GameMode::LevelSummary;
+        }
+        Ok(())
+    }
+
+    /// **Ends the run on the "Game Over", retry or victory screen.**
+    ///
+    /// ## Returns
+    /// `Ok(())` if the run is ended, or a `DodgerError` if the high scores cannot be stored or the saved game cannot be deleted.
+    ///
+    /// ## Behavior
+    /// The saved game is deleted, and the run is added to the high scores unless it used adaptive difficulty or continues.
+    fn end_run(&mut self) -> Result<(), DodgerError> {
+        self.run_end_pending = false;
+        self.discard_saved_game()?;
+        let entry = HighScore {
+            score: self.total_score + self.level_score(),
+            level: self.current_level + 1,
+            difficulty: self.run_difficulty,
+        };
+        let ranked =
+            self.continues
+                .add_to_high_scores(self.run_adaptive, entry, &mut self.high_scores);
+        if ranked.is_some() {
+            self.high_scores.save(&self.settings_dir)?;
+        }
+        Ok(())
+    }
+
+    /// **Ends the run lost on the "Game Over" screen, if it has not ended yet.**
+    ///
+    /// ## Returns
+    /// `Ok(())` if the run is ended or has already ended, or a `DodgerError` if ending the run fails.
+    ///
+    /// ## Behavior
+    /// The lost level is added to the statistics before the run ends.
+    fn end_lost_run(&mut self) -> Result<(), DodgerError> {
+        if self.run_end_pending {
+            self.record_level_end(false)?;
+            self.end_run()?;
+        }
+        Ok(())
+    }
+
+    /// **Retries the level the run was lost on.**
+    ///
+    /// ## Parameters
+    /// `ctx`: the game context.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the level is reset, or a `DodgerError` if the statistics or the high scores cannot be stored or resource loading fails.
+    ///
+    /// ## Behavior
+    /// * While continues are left, the retry spends one: the lost level is added to the statistics, and it restarts
+    ///   with the total score from before it less `CONTINUE_SCORE_PENALTY`, keeping the upgrades of the run, which is no longer ranked.
+    /// * Otherwise the lost run ends, and a new run starts at the level.
+    fn retry_lost_level(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
+        if !self.can_use_continue() {
+            self.end_lost_run()?;
+            return self.start_game(ctx, self.play_mode, self.current_level);
+        }
+        let penalty = self.continues.spend(self.total_score).unwrap_or_default();
+        self.record_level_end(false)?;
+        self.run_end_pending = false;
+        self.total_score -= penalty;
+        self.reset(ctx)
+    }
+
+    /// **Adds the level that just ended to the statistics and unlocks the achievements it met.**
+    ///
+    /// ## Parameters
+    /// `completed`: whether the level was completed, rather than lost or failed.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the statistics and achievements are stored, or a `DodgerError` if they cannot be written.
+    fn record_level_end(&mut self, completed: bool) -> Result<(), DodgerError> {
+        self.record_level_stats(completed)?;
+        self.check_achievements(Some(LevelOutcome {
+            level: self.current_level,
+            completed,
+            lives_lost: self.world.any_life_lost(),
+            victory: self.game_mode == GameMode::Victory,
+            preset: self.run_difficulty,
+        }))
+    }
+
+    /// **Checks whether the current run counts for the high scores.**
+    ///
+    /// ## Behavior
+    /// Runs with adaptive difficulty or continues are not ranked.
+    fn run_ranked(&self) -> bool {
+        self.continues.is_ranked(self.run_adaptive)
+    }
+
+    /// **Checks whether the lost run can still be continued.**
+    fn can_use_continue(&self) -> bool {
+        self.continues.left() > 0 && self.play_mode != PlayMode::Versus
+    }
+
+    /// **Spends a continue to resume the lost level.**
+    ///
+    /// ## Behavior
+    /// * The players are revived with their starting lives in the same level, keeping its score, elapsed time and tally;
+    ///   the level resumes after the countdown of its intro card, and the run is no longer ranked in the high scores.
+    /// * Each continue also costs `CONTINUE_SCORE_PENALTY` points of the run's score, or all of it if the score is lower.
+    fn use_continue(&mut self) {
+        let Some(penalty) = self.continues.spend(self.total_score + self.level_score()) else {
+            return;
+        };
+        self.run_end_pending = false;
+        self.total_score -= penalty;
+        self.world.revive();
+        self.game_over_report = None;
+        self.game_over_sound_played = false;
+        self.tick_accumulator = 0.0;
+        self.is_paused = false;
+        self.countdown_secs = COUNTDOWN_SECS + GO_SECS;
+        self.game_mode = GameMode::LevelIntro;
+    }
+
+    /// **Describes what cost the last life of a lost level.**
+    ///
+    /// ## Behavior
+    /// The last life is always lost to a "bad" object; with two players, the player hit last is named.
+    fn cause_of_death(&self) -> String {
+        let last_hit = self
+            .world
+            .players
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, state)| state.player.blink_tick)
+            .map_or(0, |(i, _)| i);
+        if self.world.players.len() > 1 {
+            format!("Player {} hit a bad object", last_hit + 1)
+        } else {
+            "Hit by a bad object".to_string()
+        }
+    }
+
+    /// **Adds the level being played to the statistics.**
+    ///
+    /// ## Parameters
+    /// `completed`: whether the level was completed, rather than lost, failed or left.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the statistics are stored or there is nothing to add, or a `DodgerError` if the file cannot be written.
+    ///
+    /// ## Behavior
+    /// Only single-player and co-op games are recorded; the tally is emptied, so each level is added once.
+    fn record_level_stats(&mut self, completed: bool) -> Result<(), DodgerError> {
+        let tally = std::mem::take(&mut self.level_tally);
+        if tally.ticks == 0 || self.play_mode == PlayMode::Versus {
+            return Ok(());
+        }
+        self.statistics
+            .record_level(self.current_level, &tally, completed);
+        self.statistics.save(&self.settings_dir)
+    }
+
+    /// **Unlocks the achievements met by the active profile and announces them.**
+    ///
+    /// ## Parameters
+    /// `outcome`: how the level ended, once it has ended; the level must then already be added to the statistics.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the achievements are stored or none was unlocked, or a `DodgerError` if the file cannot be written.
+    ///
+    /// ## Behavior
+    /// Only single-player and co-op games unlock achievements; each new achievement is announced by a notification.
+    fn check_achievements(&mut self, outcome: Option<LevelOutcome>) -> Result<(), DodgerError> {
+        if self.play_mode == PlayMode::Versus {
+            return Ok(());
+        }
+        let done = Accomplishments {
+            statistics: &self.statistics,
+            tally: &self.level_tally,
+            outcome,
+        };
+        let unlocked = self.achievements.unlock(&done);
+        if unlocked.is_empty() {
+            return Ok(());
+        }
+        for achievement in unlocked {
+            self.toasts.push(Toast::new(format!(
+                "Achievement unlocked: {}",
+                achievement.name
+            )));
+        }
+        self.achievements.save(&self.settings_dir)
+    }
+
+    /// **Counts down the notification being shown and removes it once it expires.**
+    ///
+    /// ## Parameters
+    /// `ctx`: the game context.
+    fn update_toasts(&mut self, ctx: &mut Context) {
+        if let Some(toast) = self.toasts.first_mut() {
+            toast.remaining_secs -= ctx.time.delta().as_secs_f32();
+            if toast.remaining_secs <= 0.0 {
+                self.toasts.remove(0);
+            }
+        }
+    }
+
+    /// **Checks whether the objective of the current level is met.**
+    ///
+    /// ## Behavior
+    /// Versus games have no objectives and never meet them.
+    fn objective_met(&self) -> bool {
+        self.play_mode != PlayMode::Versus
+            && self.levels[self.current_level]
+                .objective
+                .status(&self.world)
+                == ObjectiveStatus::Completed
+    }
+
+    /// **Checks whether the objective of the current level can no longer be met.**
+    ///
+    /// ## Behavior
+    /// Versus games have no objectives and never fail them.
+    fn objective_failed(&self) -> bool {
+        self.play_mode != PlayMode::Versus
+            && self.levels[self.current_level]
+                .objective
+                .status(&self.world)
+                == ObjectiveStatus::Failed
+    }
+
+    /// **Draws the best score of the run's difficulty preset.**
+    ///
+    /// ## Parameters
+    /// * `canvas`: canvas to draw on.
+    /// * `y`: vertical position of the text.
+    ///
+    /// ## Returns
+    /// `Ok(())` if drawing is successful, or a `DodgerError` if text drawing fails.
+    ///
+    /// ## Behavior
+    /// Runs with adaptive difficulty are not ranked, which is shown instead.
+    fn draw_best_score(&self, canvas: &mut Canvas, y: f32) -> Result<(), DodgerError> {
+        let best = self
+            .high_scores
+            .best(self.run_difficulty)
+            .map_or(0, |entry| entry.score);
+        let text = if self.run_adaptive {
+            "Adaptive run: not ranked".to_string()
+        } else if self.continues.used > 0 {
+            "Continued run: not ranked".to_string()
+        } else {
+            format!("Best on {}: {}", self.run_difficulty.name(), best)
+        };
+        let best_text = DrawText::new(
+            Point2::from_slice(&[WINDOW_WIDTH / 2.0 - 150.0, y]),
+            text,
+            "text_font".to_string(),
+            TEXT_SIZE,
+            Color::WHITE,
+        )?;
+        draw_text(canvas, best_text)
+    }
+
+    /// **Returns the color a player is drawn with.**
+    ///
+    /// ## Parameters
+    /// `player_index`: index of the player.
+    ///
+    /// ## Behavior
+    /// * The second player is tinted blue.
+    /// * In networked games the other instance's player is drawn as a translucent ghost.
+    fn player_tint(&self, player_index: usize) -> Color {
+        let mut tint = match player_index {
+            0 => Color::WHITE,
+            _ => Color::new(0.6, 0.8, 1.0, 1.0),
+        };
+        if self
+            .net
+            .as_ref()
+            .is_some_and(|net| net.local_player != player_index)
+        {
+            tint.a = 0.5;
+        }
+        tint
+    }
+
+    /// **Draws the game state when in the playing mode.**
+    ///
+    /// ## Parameters
+    /// * `ctx`: the game context.
+    /// * `canvas`: canvas to draw on.
+    ///
+    /// ## Returns
+    /// `Ok(())` if drawing is successful, or a `DodgerError` if text or button drawing fails.
+    ///
+    /// ## Behavior
+    /// * Draws the background, the players still in the game, falling objects, and UI elements (score, timer, lives).
+    /// * In versus games, the score and lives of each player are shown on their own side of the screen.
+    /// * In networked games, the other player is drawn as a ghost.
+    fn draw_playing(&mut self, ctx: &mut Context, canvas: &mut Canvas) -> Result<(), DodgerError> {
+        draw_background(canvas, &self.resources.background_image);
+        for (i, state) in self.world.players.iter().enumerate() {
+            if state.is_alive() {
+                // Players with a wider catch area are stretched horizontally.
+                let mut scaling = player_scaling();
+                scaling.x *= state.player.size.w / self.world.sizes.player.w;
+                state.player.draw(
+                    canvas,
+                    &self.resources.player_image,
+                    scaling,
+                    self.player_tint(i),
+                    self.world.tick,
+                );
+            }
+        }
+        draw_button_with_text(ctx, canvas, self.menu_button.clone())?;
+
+        let text = format!("Level {}", self.current_level + 1);
+        let text_to_draw = DrawText::new(
+            Point2::from_slice(&[525.0, 10.0]),
+            text,
+            "text_font".to_string(),
+            TEXT_SIZE,
+            Color::WHITE,
+        )?;
+
+        draw_text(canvas, text_to_draw)?;
+        if self.audio.is_muted {
+            self.audio_button.icon = self.audio.speaker_muted_icon.clone();
+        } else {
+            self.audio_button.icon = self.audio.speaker_icon.clone();
+        };
+        draw_icon(canvas, &self.audio_button)?;
+        for obj in &self.world.objects {
+            obj.draw(canvas, &self.resources, self.world.tick);
+        }
+
+        draw_timer(ctx, canvas, self.world.remaining_secs())?;
+
+        if self.play_mode == PlayMode::Versus {
+            self.draw_versus_hud(canvas)?;
+        } else {
+            self.draw_team_hud(canvas)?;
+        }
+
+        if self.is_paused {
+            draw_icon(canvas, &self.pause_button)?;
+            if self.can_save_run() {
+                draw_button_with_text(ctx, canvas, self.save_button()?)?;
+            }
+        }
+
+        if self.show_debug_overlay {
+            self.draw_debug_overlay(canvas)?;
+        }
+
+        Ok(())
+    }
+
+    /// **Draws the simulation's current difficulty values for tuning and debugging.**
+    ///
+    /// ## Parameters
+    /// `canvas`: canvas to draw on.
+    ///
+    /// ## Returns
+    /// `Ok(())` if drawing is successful, or a `DodgerError` if text drawing fails.
+    ///
+    /// ## Behavior
+    /// Shows the fall speed and spawn interval, and with adaptive difficulty its factor, target and the performance it is based on.
+    fn draw_debug_overlay(&self, canvas: &mut Canvas) -> Result<(), DodgerError> {
+        let mut lines = vec![
+            format!(
+                "{} | t {:.1}s",
+                self.world.preset.name(),
+                self.world.elapsed_secs()
+            ),
+            format!(
+                "Fall speed {:.2} | spawn every {:.2}s",
+                self.world.fall_speed,
+                self.world.spawn_interval_secs()
+            ),
+        ];
+        match &self.world.adaptive {
+            Some(adaptive) => {
+                let stats = adaptive.window_stats();
+                let catch_rate = stats
+                    .catch_rate()
+                    .map_or("-".to_string(), |rate| format!("{:.0}%", rate * 100.0));
+                lines.push(format!(
+                    "Adaptive x{:.2} -> x{:.2}",
+                    adaptive.factor(),
+                    adaptive.target_factor()
+                ));
+                lines.push(format!(
+                    "Catch {} | lives lost {} | combo {}",
+                    catch_rate, stats.lives_lost, adaptive.combo
+                ));
+            }
+            None => lines.push("Adaptive off".to_string()),
+        }
+
+        for (i, line) in lines.into_iter().enumerate() {
+            let text_to_draw = DrawText::new(
+                Point2::from_slice(&[10.0, 135.0 + i as f32 * 24.0]),
+                line,
+                "text_font".to_string(),
+                DEBUG_TEXT_SIZE,
+                YELLOW,
+            )?;
+            draw_text(canvas, text_to_draw)?;
+        }
+        Ok(())
+    }
+
+    /// **Draws the score and lives of a single-player or co-op game.**
+    ///
+    /// ## Parameters
+    /// `canvas`: canvas to draw on.
+    ///
+    /// ## Returns
+    /// `Ok(())` if drawing is successful, or a `DodgerError` if text drawing fails.
+    ///
+    /// ## Behavior
+    /// In co-op games the team score and the shared lives are shown.
+    fn draw_team_hud(&self, canvas: &mut Canvas) -> Result<(), DodgerError> {
+        let prefix = if self.play_mode == PlayMode::Coop {
+            "Team "
+        } else {
+            ""
+        };
+        let level_score = self.level_score();
+        let level_score_text = format!("{}Level Score: {}", prefix, level_score);
+        let level_score_text_to_draw = DrawText::new(
+            Point2::from_slice(&[10.0, 10.0]),
+            level_score_text,
+            "text_font".to_string(),
+            TEXT_SIZE,
+            Color::WHITE,
+        )?;
+        draw_score(canvas, level_score_text_to_draw)?;
+
+        let total_score_text = format!("Total Score: {}", level_score + self.total_score);
+        let total_score_text_to_draw = DrawText::new(
+            Point2::from_slice(&[10.0, 50.0]),
+            total_score_text,
+            "text_font".to_string(),
+            TEXT_SIZE,
+            Color::WHITE,
+        )?;
+        draw_score(canvas, total_score_text_to_draw)?;
+
+        let lives_text_to_draw = DrawText::new(
+            Point2::from_slice(&[530.0, 60.0]),
+            format!("{}Lives: {}", prefix, self.world.players[0].lives),
+            "text_font".to_string(),
+            TEXT_SIZE,
+            Color::WHITE,
+        )?;
+        draw_text(canvas, lives_text_to_draw)?;
+
+        let objective = &self.levels[self.current_level].objective;
+        let objective_color = match objective.status(&self.world) {
+            ObjectiveStatus::Completed => Color::GREEN,
+            _ => Color::WHITE,
+        };
+        let objective_text_to_draw = DrawText::new(
+            Point2::from_slice(&[10.0, 95.0]),
+            objective.progress(&self.world),
+            "text_font".to_string(),
+            BUTTON_TEXT_SIZE,
+            objective_color,
+        )?;
+        draw_text(canvas, objective_text_to_draw)?;
+        Ok(())
+    }
+
+    /// **Draws the score and lives of each player in a versus game.**
+    ///
+    /// ## Parameters
+    /// `canvas`: canvas to draw on.
+    ///
+    /// ## Returns
+    /// `Ok(())` if drawing is successful, or a `DodgerError` if text drawing fails.
+    ///
+    /// ## Behavior
+    /// The first player's stats are shown on the left and the second player's stats on the right, in the player's tint.
+    fn draw_versus_hud(&self, canvas: &mut Canvas) -> Result<(), DodgerError> {
+        let columns = [(10.0, 10.0), (WINDOW_WIDTH - 240.0, 110.0)];
+        for (i, (state, (x, y))) in self.world.players.iter().zip(columns).enumerate() {
+            let lines = [
+                format!("P{} Score: {}", i + 1, state.level_score),
+                format!("P{} Lives: {}", i + 1, state.lives),
+            ];
+            for (line_index, line) in lines.into_iter().enumerate() {
+                let text_to_draw = DrawText::new(
+                    Point2::from_slice(&[x, y + line_index as f32 * 40.0]),
+                    line,
+                    "text_font".to_string(),
+                    TEXT_SIZE,
+                    self.player_tint(i),
+                )?;
+                draw_score(canvas, text_to_draw)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// **Updates the game state when on the level intro card.**
+    ///
+    /// ## Parameters
+    /// `ctx`: the game context.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the update is successful, or a `DodgerError` if button handling fails.
+    ///
+    /// ## Behavior
+    /// The simulation, with its timer and spawns, does not run until the countdown is over
+    /// or the player clicks or activates the play button.
+    fn update_level_intro(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
+        self.countdown_secs -= ctx.time.delta().as_secs_f32();
+
+        let activated = self.menu_focus.take_activation();
+        if self.countdown_secs <= 0.0
+            || is_button_clicked(ctx, text_button_rect(&self.play_level_button)?)
+            || activated == Some(0)
+        {
+            self.game_mode = GameMode::Playing;
+        }
+        Ok(())
+    }
+
+    /// **Draws the level intro card on the canvas.**
+    ///
+    /// ## Parameters
+    /// * `ctx`: the game context.
+    /// * `canvas`: canvas to draw on.
+    ///
+    /// ## Returns
+    /// `Ok(())` if drawing is successful, or a `DodgerError` if text, object or button drawing fails.
+    ///
+    /// ## Behavior
+    /// * Draws the level's background, its number and name, its objective, the objects falling in it with their names and points,
+    ///   the countdown, and the play button.
+    /// * Each level has its own sprites, so every object type looks new on its intro card and all of them are shown;
+    ///   co-op objects are only shown in co-op games, and are named since they share the high-value sprite.
+    fn draw_level_intro(
+        &mut self,
+        ctx: &mut Context,
+        canvas: &mut Canvas,
+    ) -> Result<(), DodgerError> {
+        draw_background(canvas, &self.resources.background_image);
+        let level = &self.levels[self.current_level];
+        let title_text = DrawText::new(
+            Point2::from_slice(&[WINDOW_WIDTH / 2.0 - 220.0, 120.0]),
+            format!("Level {}: {}", self.current_level + 1, level.name),
+            "text_font".to_string(),
+            48.0,
+            Color::WHITE,
+        )?;
+        draw_text(canvas, title_text)?;
+
+        let objective = level.objective.description(self.run_difficulty);
+        let objective_text = DrawText::new(
+            Point2::from_slice(&[WINDOW_WIDTH / 2.0 - 230.0, 200.0]),
+            format!("Goal: {}", objective),
+            "text_font".to_string(),
+            TEXT_SIZE,
+            Color::WHITE,
+        )?;
+        draw_text(canvas, objective_text)?;
+
+        let mut samples: Vec<(Option<GoodObjectValue>, bool, String, &str)> = GoodObjectValue::ALL
+            .iter()
+            .filter(|value| **value != GoodObjectValue::Coop || self.play_mode == PlayMode::Coop)
+            .map(|value| {
+                let points = self.run_difficulty.scaled_score(value.score());
+                (Some(*value), true, format!("+{}", points), value.label())
+            })
+            .collect();
+        samples.push((None, false, "Avoid".to_string(), "Bad"));
+        let spacing = WINDOW_WIDTH / (samples.len() + 1) as f32;
+        for (i, (value, is_good, label, name)) in samples.into_iter().enumerate() {
+            let x = spacing * (i + 1) as f32;
+            let object = FallingObject::new(
+                Point2::from_slice(&[x - 40.0, 280.0]),
+                self.world.sizes.object(value),
+                is_good,
+                value,
+            )?;
+            object.draw(canvas, &self.resources, self.world.tick);
+            let label_text = DrawText::new(
+                Point2::from_slice(&[x + 10.0, 290.0]),
+                label,
+                "text_font".to_string(),
+                BUTTON_TEXT_SIZE,
+                Color::WHITE,
+            )?;
+            draw_text(canvas, label_text)?;
+            let name_text = DrawText::new(
+                Point2::from_slice(&[x - 40.0, 355.0]),
+                name.to_string(),
+                "text_font".to_string(),
+                BUTTON_TEXT_SIZE,
+                Color::WHITE,
+            )?;
+            draw_text(canvas, name_text)?;
+        }
+
+        let countdown_text = DrawText::new(
+            Point2::from_slice(&[WINDOW_WIDTH / 2.0 - 40.0, 420.0]),
+            countdown_label(self.countdown_secs),
+            "text_font".to_string(),
+            96.0,
+            YELLOW,
+        )?;
+        draw_text(canvas, countdown_text)?;
+        draw_button_with_text(ctx, canvas, self.play_level_button.clone())?;
+        Ok(())
+    }
+
+    /// **Updates the game state when the objective of a level was failed.**
+    ///
+    /// ## Parameters
+    /// `ctx`: the game context.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the update is successful, or a `DodgerError` if sound playback, resource loading or ending the run fails.
+    ///
+    /// ## Behavior
+    /// * Plays the "game over" sound.
+    /// * Handles button clicks and focused button activations for retrying the level and returning to the main menu.
+    /// * Returning to the main menu ends the run, see `end_run`.
+    fn update_level_failed(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
+        if !self.game_over_sound_played {
+            self.audio.play_sound(ctx, "game_over".to_string())?;
+            self.game_over_sound_played = true;
+        }
+
+        let activated = self.menu_focus.take_activation();
+        if is_button_clicked(ctx, text_button_rect(&self.retry_button)?) || activated == Some(0) {
+            self.reset(ctx)?;
+        }
+
+        if is_button_clicked(ctx, text_button_rect(&self.main_menu_button)?) || activated == Some(1)
+        {
+            self.end_run()?;
+            self.game_started = false;
+            self.play_mode = PlayMode::Single;
+            self.game_mode = GameMode::Menu;
+        }
+        Ok(())
+    }
+
+    /// **Draws the retry screen shown when the objective of a level was failed.**
+    ///
+    /// ## Parameters
+    /// * `ctx`: the game context.
+    /// * `canvas`: canvas to draw on.
+    ///
+    /// ## Returns
+    /// `Ok(())` if drawing is successful, or a `DodgerError` if text or button drawing fails.
+    ///
+    /// ## Behavior
+    /// Draws the background, the failed objective with the progress made, and the retry and main menu buttons.
+    fn draw_level_failed(
+        &mut self,
+        ctx: &mut Context,
+        canvas: &mut Canvas,
+    ) -> Result<(), DodgerError> {
+        draw_background(canvas, &self.resources.background_image);
+        let title_text = DrawText::new(
+            Point2::from_slice(&[WINDOW_WIDTH / 2.0 - 175.0, WINDOW_HEIGHT / 2.0 - 200.0]),
+            "Objective Failed".to_string(),
+            "text_font".to_string(),
+            48.0,
+            Color::WHITE,
+        )?;
+        draw_text(canvas, title_text)?;
+
+        let objective = &self.levels[self.current_level].objective;
+        let lines = [
+            format!("Goal: {}", objective.description(self.run_difficulty)),
+            objective.progress(&self.world),
+        ];
+        for (i, line) in lines.into_iter().enumerate() {
+            let line_text = DrawText::new(
+                Point2::from_slice(&[
+                    WINDOW_WIDTH / 2.0 - 230.0,
+                    WINDOW_HEIGHT / 2.0 - 120.0 + i as f32 * 40.0,
+                ]),
+                line,
+                "text_font".to_string(),
+                TEXT_SIZE,
+                Color::WHITE,
+            )?;
+            draw_text(canvas, line_text)?;
+        }
+
+        draw_button_with_text(ctx, canvas, self.retry_button.clone())?;
+        draw_button_with_text(ctx, canvas, self.main_menu_button.clone())?;
+        Ok(())
+    }
+
+    /// **Updates the game state when on the level summary screen.**
+    ///
+    /// ## Parameters
+    /// `ctx`: the game context.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the update is successful, or a `DodgerError` if sound playback fails.
+    ///
+    /// ## Behavior
+    /// * Plays the "level completed" sound, unless the victory screen follows with its own sound.
+    /// * Counts up the summary; the button skips the animation, and once it is over proceeds to the shop or the victory screen.
+    fn update_level_summary(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
+        let Some(summary) = &mut self.level_summary else {
+            self.game_mode = GameMode::NextLevel;
+            return Ok(());
+        };
+        if !self.level_complete_sound_played && !summary.victory {
+            self.audio.play_sound(ctx, "level_completed".to_string())?;
+            self.level_complete_sound_played = true;
+        }
+        summary.elapsed_secs += ctx.time.delta().as_secs_f32();
+
+        let activated = self.menu_focus.take_activation();
+        if is_button_clicked(ctx, text_button_rect(&self.summary_button()?)?)
+            || activated == Some(0)
+        {
+            if let Some(summary) = &mut self.level_summary {
+                if !summary.is_finished() {
+                    summary.skip();
+                } else {
+                    self.game_mode = if summary.victory {
+                        GameMode::Victory
+                    } else {
+                        GameMode::NextLevel
+                    };
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// **Creates the button of the level summary screen.**
+    ///
+    /// ## Returns
+    /// A result containing the button, skipping the animation while the summary is counted up and continuing afterwards,
+    /// or a `DodgerError` if it cannot be created.
+    fn summary_button(&self) -> Result<TextButton, DodgerError> {
+        let finished = self
+            .level_summary
+            .as_ref()
+            .is_none_or(|summary| summary.is_finished());
+        let label = if finished { "Continue" } else { "Skip" };
+        TextButton::new(
+            start_point_of_button_in_set(0, 680.0),
+            Color::WHITE,
+            text_button_rectsize(),
+            label.to_string(),
+            Color::BLACK,
+            BUTTON_TEXT_SIZE,
+            "button_font".to_string(),
+        )
+    }
+
+    /// **Draws the level summary screen on the canvas.**
+    ///
+    /// ## Parameters
+    /// * `ctx`: the game context.
+    /// * `canvas`: canvas to draw on.
+    ///
+    /// ## Returns
+    /// `Ok(())` if drawing is successful, or a `DodgerError` if text, star or button drawing fails.
+    ///
+    /// ## Behavior
+    /// Draws the background, the title, the lines of the summary counted up one after another,
+    /// the star rating once every line is shown, and the button to skip or continue.
+    fn draw_level_summary(
+        &mut self,
+        ctx: &mut Context,
+        canvas: &mut Canvas,
+    ) -> Result<(), DodgerError> {
+        draw_background(canvas, &self.resources.background_image);
+        let title = DrawText::new(
+            Point2::from_slice(&[WINDOW_WIDTH / 2.0 - 160.0, 30.0]),
+            format!("Level {} Complete!", self.current_level + 1),
+            "text_font".to_string(),
+            48.0,
+            Color::WHITE,
+        )?;
+        draw_text(canvas, title)?;
+
+        let Some(summary) = &self.level_summary else {
+            return Ok(());
+        };
+        for (i, (label, value)) in summary.lines().into_iter().enumerate() {
+            let Some(shown) = summary.shown_value(i, value) else {
+                break;
+            };
+            let y = 110.0 + i as f32 * 40.0;
+            let label_text = DrawText::new(
+                Point2::from_slice(&[300.0, y]),
+                label,
+                "text_font".to_string(),
+                BUTTON_TEXT_SIZE,
+                Color::WHITE,
+            )?;
+            draw_text(canvas, label_text)?;
+            let value_text = DrawText::new(
+                Point2::from_slice(&[600.0, y]),
+                shown.to_string(),
+                "text_font".to_string(),
+                BUTTON_TEXT_SIZE,
+                Color::WHITE,
+            )?;
+            draw_text(canvas, value_text)?;
+        }
+
+        if summary.is_finished() {
+            for star in 0..3u8 {
+                let center = Point2::from_slice(&[
+                    WINDOW_WIDTH / 2.0 + (f32::from(star) - 1.0) * 60.0,
+                    590.0,
+                ]);
+                draw_star(ctx, canvas, center, 25.0, star < summary.stars)?;
+            }
+        }
+
+        draw_button_with_text(ctx, canvas, self.summary_button()?)?;
+        Ok(())
+    }
+
+    /// **Updates the game state when in "Next Level" screen, the shop between levels.**
+    ///
+    /// ## Parameters
+    /// `ctx`: the game context.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the update is successful, or a `DodgerError` if resource loading fails.
+    ///
+    /// ## Behavior
+    /// * Buys the upgrade whose button is clicked or activated, paying with the total score including the completed level.
+    /// * Handles button clicks and focused button activations for proceeding to the next level.
+    fn update_next_level(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
+        let activated = self.menu_focus.take_activation();
+        for (i, button) in self.upgrade_buttons()?.iter().enumerate() {
+            if is_button_clicked(ctx, text_button_rect(button)?) || activated == Some(i) {
+                let score = self.total_score + self.level_score();
+                match self.purchases.buy(i, score, self.run_difficulty) {
+                    Ok(price) => {
+                        self.total_score -= price;
+                        self.shop_status = None;
+                    }
+                    Err(err) => self.shop_status = Some(err.to_string()),
+                }
+            }
+        }
+
+        if is_button_clicked(ctx, text_button_rect(&self.next_level_button)?)
+            || activated == Some(UPGRADES.len())
+        {
+            self.current_level += 1;
+            self.reset(ctx)?;
+        }
+
+        Ok(())
+    }
+
+    /// **Creates the buttons for buying each upgrade in the shop.**
+    ///
+    /// ## Returns
+    /// A result containing a button per upgrade, next to its description and labeled with its next price,
+    /// grayed out when it is not sold on the run's preset, sold out or the score does not cover the price,
+    /// or a `DodgerError` if a button cannot be created.
+    fn upgrade_buttons(&self) -> Result<Vec<TextButton>, DodgerError> {
+        let score = self.total_score + self.level_score();
+        UPGRADES
+            .iter()
+            .enumerate()
+            .map(|(i, upgrade)| {
+                let available = upgrade.is_available(self.run_difficulty);
+                let price = self.purchases.next_price(i).filter(|_| available);
+                let label = match price {
+                    Some(price) => format!("Buy {}", price),
+                    None if available => "Sold Out".to_string(),
+                    None => "Unavailable".to_string(),
+                };
+                let mut button = TextButton::new(
+                    Point2::from_slice(&[650.0, 200.0 + i as f32 * 70.0]),
+                    Color::WHITE,
+                    text_button_rectsize(),
+                    label,
+                    Color::BLACK,
+                    BUTTON_TEXT_SIZE,
+                    "button_font".to_string(),
+                )?;
+                if price.is_none_or(|price| score < price) {
+                    button.button_color = Color::new(0.6, 0.6, 0.6, 1.0);
+                }
+                Ok(button)
+            })
+            .collect()
+    }
+
+    /// **Draws the "Next Level" screen, the shop between levels, on the canvas.**
+    ///
+    /// ## Parameters
+    /// * `ctx`: the game context.
+    /// * `canvas`: canvas to draw on.
+    ///
+    /// ## Returns
+    /// `Ok(())` if drawing is successful, or a `DodgerError` if text or button drawing fails.
+    ///
+    /// ## Behavior
+    /// Draws the background, "Level Complete" text, the score to spend, each upgrade with its description,
+    /// the number bought and a button to buy it, the reason the last purchase failed, and a button to proceed to the next level.
+    fn draw_next_level(
+        &mut self,
+        ctx: &mut Context,
+        canvas: &mut Canvas,
+    ) -> Result<(), DodgerError> {
+        draw_background(canvas, &self.resources.background_image);
+        let level_complete_text = DrawText::new(
+            Point2::from_slice(&[WINDOW_WIDTH / 2.0 - 160.0, 40.0]),
+            "Level Complete!".to_string(),
+            "text_font".to_string(),
+            48.0,
+            Color::WHITE,
+        )?;
+        draw_text(canvas, level_complete_text)?;
+
+        let score_text = DrawText::new(
+            Point2::from_slice(&[150.0, 130.0]),
+            format!("Score to spend: {}", self.total_score + self.level_score()),
+            "text_font".to_string(),
+            TEXT_SIZE,
+            Color::WHITE,
+        )?;
+        draw_text(canvas, score_text)?;
+
+        for (i, upgrade) in UPGRADES.iter().enumerate() {
+            let line = DrawText::new(
+                Point2::from_slice(&[150.0, 212.0 + i as f32 * 70.0]),
+                format!(
+                    "{}: {} ({}/{})",
+                    upgrade.name,
+                    upgrade.description,
+                    self.purchases.count(i),
+                    upgrade.prices.len()
+                ),
+                "text_font".to_string(),
+                BUTTON_TEXT_SIZE,
+                Color::WHITE,
+            )?;
+            draw_text(canvas, line)?;
+        }
+        for button in self.upgrade_buttons()? {
+            draw_button_with_text(ctx, canvas, button)?;
+        }
+
+        if let Some(status) = &self.shop_status {
+            let status_text = DrawText::new(
+                Point2::from_slice(&[150.0, 500.0]),
+                status.clone(),
+                "text_font".to_string(),
+                BUTTON_TEXT_SIZE,
+                Color::WHITE,
+            )?;
+            draw_text(canvas, status_text)?;
+        }
+
+        draw_button_with_text(ctx, canvas, self.next_level_button.clone())?;
+        Ok(())
+    }
+
+    /// **Updates the game state when in "How to Play" screen.**
+    ///
+    /// ## Parameters
+    /// `ctx`: the game context.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the update is successful, or a `DodgerError` if button handling fails.
+    fn update_how_to_play(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
+        let activated = self.menu_focus.take_activation();
+        if is_button_clicked(ctx, text_button_rect(&self.back_to_menu_button)?)
+            || activated == Some(0)
+        {
+            self.game_mode = GameMode::Menu;
+        }
+        Ok(())
+    }
+
+    /// **Draws the "How to Play" screen on the canvas.**
+    ///
+    /// ## Parameters
+    /// * `ctx`: the game context.
+    /// * `canvas`: canvas to draw on.
+    ///
+    /// ## Returns
+    /// `Ok(())` if drawing is successful, or a `DodgerError` if text or button drawing fails.
+    ///
+    /// ## Behavior
+    /// Draws the background, title, instructions, and a button to return to the main menu.
+    fn draw_how_to_play(
+        &mut self,
+        ctx: &mut Context,
+        canvas: &mut Canvas,
+    ) -> Result<(), DodgerError> {
+        draw_background(canvas, &self.resources.menu_background_image);
+
+        let title = DrawText::new(
+            Point2::from_slice(&[WINDOW_WIDTH / 2.0 - 150.0, 150.0]),
+            "How to Play".to_string(),
+            "text_font".to_string(),
+            48.0,
+            Color::WHITE,
+        )?;
+        draw_text(canvas, title)?;
+
+        let bindings = &self.settings.bindings;
+        let instructions = vec![
+            format!("Move left: {}", bindings.describe(Action::MoveLeft)),
+            format!("Move right: {}", bindings.describe(Action::MoveRight)),
+            format!("Dash: {}", bindings.describe(Action::Dash)),
+            format!("Pause: {}", bindings.describe(Action::Pause)),
+            "Catch good objects to earn points:".to_string(),
+            "  - High value: 30 points".to_string(),
+            "  - Medium value: 15 points".to_string(),
+            "  - Low value: 5 points".to_string(),
+            "Avoid bad objects! They reduce your lives.".to_string(),
+            "Each level lasts 40 seconds.".to_string(),
+        ];
+
+        let mut y_offset = 250.0;
+        for line in instructions {
+            let instruction_text = DrawText::new(
+                Point2::from_slice(&[150.0, y_offset]),
+                line,
+                "text_font".to_string(),
+                TEXT_SIZE,
+                Color::WHITE,
+            )?;
+            draw_text(canvas, instruction_text)?;
+            y_offset += 50.0;
+        }
+
+        draw_button_with_text(ctx, canvas, self.back_to_menu_button.clone())?;
+
+        Ok(())
+    }
+
+    /// **Updates the game state when in the statistics screen.**
+    ///
+    /// ## Parameters
+    /// `ctx`: the game context.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the update is successful, or a `DodgerError` if button handling fails.
+    fn update_stats(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
+        let activated = self.menu_focus.take_activation();
+        if is_button_clicked(ctx, text_button_rect(&self.back_to_menu_button)?)
+            || activated == Some(0)
+        {
+            self.game_mode = GameMode::Menu;
+        }
+        Ok(())
+    }
+
+    /// **Draws the statistics screen on the canvas.**
+    ///
+    /// ## Parameters
+    /// * `ctx`: the game context.
+    /// * `canvas`: canvas to draw on.
+    ///
+    /// ## Returns
+    /// `Ok(())` if drawing is successful, or a `DodgerError` if text or button drawing fails.
+    ///
+    /// ## Behavior
+    /// Draws the background, the title with the profile name, the lifetime totals, the catch accuracy of each level played, and a button to return to the main menu.
+    fn draw_stats(&mut self, ctx: &mut Context, canvas: &mut Canvas) -> Result<(), DodgerError> {
+        draw_background(canvas, &self.resources.menu_background_image);
+
+        let title = DrawText::new(
+            Point2::from_slice(&[60.0, 10.0]),
+            format!("Statistics: {}", self.profiles.active().name),
+            "text_font".to_string(),
+            48.0,
+            Color::WHITE,
+        )?;
+        draw_text(canvas, title)?;
+
+        let stats = &self.statistics;
+        let secs_played = stats.ticks_played / TICK_RATE;
+        let caught = GoodObjectValue::ALL
+            .iter()
+            .map(|value| format!("{} {}", value.label(), stats.caught.get(*value)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut lines = vec![
+            format!("Games played: {}", stats.games_played),
+            format!(
+                "Time played: {}h {:02}m {:02}s",
+                secs_played / 3600,
+                secs_played / 60 % 60,
+                secs_played % 60
+            ),
+            format!("Caught: {}", caught),
+            format!("Bad hits: {}", stats.bad_hits),
+            format!("Missed: {}", stats.misses),
+            format!("Best combo: {}", stats.best_combo),
+            format!("Levels completed: {}", stats.levels_completed),
+        ];
+        for i in 0..self.levels.len() {
+            let level_stats = stats.level(i);
+            if let Some(accuracy) = level_stats.accuracy() {
+                lines.push(format!(
+                    "Level {} accuracy: {:.0}% ({} of {})",
+                    i + 1,
+                    accuracy * 100.0,
+                    level_stats.caught,
+                    level_stats.caught + level_stats.missed
+                ));
+            }
+        }
+
+        for (i, line) in lines.into_iter().enumerate() {
+            let line_text = DrawText::new(
+                Point2::from_slice(&[60.0, 100.0 + i as f32 * 40.0]),
+                line,
+                "text_font".to_string(),
+                BUTTON_TEXT_SIZE,
+                Color::WHITE,
+            )?;
+            draw_text(canvas, line_text)?;
+        }
+
+        draw_button_with_text(ctx, canvas, self.back_to_menu_button.clone())?;
+        Ok(())
+    }
+
+    /// **Updates the game state when in the achievements gallery.**
+    ///
+    /// ## Parameters
+    /// `ctx`: the game context.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the update is successful, or a `DodgerError` if button handling fails.
+    fn update_achievements(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
+        let activated = self.menu_focus.take_activation();
+        if is_button_clicked(ctx, text_button_rect(&self.back_to_menu_button)?)
+            || activated == Some(0)
+        {
+            self.game_mode = GameMode::Menu;
+        }
+        Ok(())
+    }
+
+    /// **Draws the achievements gallery on the canvas.**
+    ///
+    /// ## Parameters
+    /// * `ctx`: the game context.
+    /// * `canvas`: canvas to draw on.
+    ///
+    /// ## Returns
+    /// `Ok(())` if drawing is successful, or a `DodgerError` if text, star or button drawing fails.
+    ///
+    /// ## Behavior
+    /// Draws the background, the title with the number of unlocked achievements, every achievement with its description
+    /// (unlocked ones next to a filled star, locked ones grayed out), and a button to return to the main menu.
+    fn draw_achievements(
+        &mut self,
+        ctx: &mut Context,
+        canvas: &mut Canvas,
+    ) -> Result<(), DodgerError> {
+        draw_background(canvas, &self.resources.menu_background_image);
+
+        let unlocked_count = ACHIEVEMENTS
+            .iter()
+            .filter(|achievement| self.achievements.is_unlocked(achievement))
+            .count();
+        let title = DrawText::new(
+            Point2::from_slice(&[60.0, 10.0]),
+            format!("Achievements: {}/{}", unlocked_count, ACHIEVEMENTS.len()),
+            "text_font".to_string(),
+            48.0,
+            Color::WHITE,
+        )?;
+        draw_text(canvas, title)?;
+
+        for (i, achievement) in ACHIEVEMENTS.iter().enumerate() {
+            let y = 90.0 + i as f32 * 52.0;
+            let unlocked = self.achievements.is_unlocked(achievement);
+            draw_star(
+                ctx,
+                canvas,
+                Point2::from_slice(&[75.0, y + 20.0]),
+                15.0,
+                unlocked,
+            )?;
+
+            let color = if unlocked {
+                Color::WHITE
+            } else {
+                Color::new(0.6, 0.6, 0.6, 1.0)
+            };
+            let line = DrawText::new(
+                Point2::from_slice(&[110.0, y + 6.0]),
+                format!("{}: {}", achievement.name, achievement.description),
+                "text_font".to_string(),
+                BUTTON_TEXT_SIZE,
+                color,
+            )?;
+            draw_text(canvas, line)?;
+        }
+
+        draw_button_with_text(ctx, canvas, self.back_to_menu_button.clone())?;
         Ok(())
     }
 
-    /// **Draws the main menu on the canvas.**
+    /// **Creates the buttons for changing the bindings of each action on the controls screen.**
     ///
-    /// ## Parameters
-    /// * `ctx`: the game context.
-    /// * `canvas`: canvas to draw on.
+    /// ## Returns
+    /// A result containing one button per action of the shown player, or a `DodgerError` if a button cannot be created.
+    fn rebind_buttons(&self) -> Result<Vec<TextButton>, DodgerError> {
+        self.controls_actions()
+            .iter()
+            .enumerate()
+            .map(|(i, action)| {
+                let line = if self.rebinding == Some((self.controls_player, *action)) {
+                    "Press a key".to_string()
+                } else {
+                    "Change".to_string()
+                };
+                get_rebind_button(i, 80.0, line, "button_font".to_string())
+            })
+            .collect()
+    }
+
+    /// **Creates the button for switching the control scheme on the controls screen.**
     ///
     /// ## Returns
-    /// `Ok(())` if drawing is successful, or a `DodgerError` if button drawing fails.
-    fn draw_menu(&mut self, ctx: &mut Context, canvas: &mut Canvas) -> Result<(), DodgerError> {
-        draw_background(canvas, &self.resources.menu_background_image);
-        if !self.game_started {
-            draw_button_with_text(ctx, canvas, self.start_button.clone())?;
-        } else {
-            draw_button_with_text(ctx, canvas, self.resume_button.clone())?;
-        }
-        draw_button_with_text(ctx, canvas, self.exit_button.clone())?;
-        draw_button_with_text(ctx, canvas, self.select_level_button.clone())?;
-        draw_button_with_text(ctx, canvas, self.howtoplay_button.clone())?;
-        Ok(())
+    /// A result containing the button placed below the rebinding buttons, or a `DodgerError` if it cannot be created.
+    fn control_scheme_button(&self) -> Result<TextButton, DodgerError> {
+        get_rebind_button(
+            self.controls_actions().len(),
+            80.0,
+            "Switch".to_string(),
+            "button_font".to_string(),
+        )
     }
 
-    /// **Updates the game state when in the playing mode.**
+    /// **Creates the button for switching between the bindings of the first and the second player on the controls screen.**
+    ///
+    /// ## Returns
+    /// A result containing the button labeled with the player shown after switching, or a `DodgerError` if it cannot be created.
+    fn controls_player_button(&self) -> Result<TextButton, DodgerError> {
+        TextButton::new(
+            Point2::from_slice(&[WINDOW_WIDTH / 2.0 - TEXT_BUTTON_WIDTH - 50.0, 620.0]),
+            Color::WHITE,
+            text_button_rectsize(),
+            format!("Player {}", 2 - self.controls_player),
+            Color::BLACK,
+            BUTTON_TEXT_SIZE,
+            "button_font".to_string(),
+        )
+    }
+
+    /// **Updates the game state when in the controls screen.**
     ///
     /// ## Parameters
     /// `ctx`: the game context.
     ///
     /// ## Returns
-    /// `Ok(())` if the update is successful, or a `DodgerError` if object creation or collision handling fails.
+    /// `Ok(())` if the update is successful, or a `DodgerError` if button handling or storing the settings fails.
     ///
     /// ## Behavior
-    /// * Handles button clicks for toggling audio and returning to the menu.
-    /// * Updates falling objects and checks for collisions.
-    /// * Advances to the next level or victory screen if the level is complete.
-    fn update_playing(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
-        if !self.game_started {
-            self.game_started = true;
-        }
-
-        if is_button_clicked(ctx, icon_button_rect(&self.audio_button)?) {
-            self.audio.is_muted = !self.audio.is_muted;
-        }
-
-        if is_button_clicked(ctx, text_button_rect(&self.menu_button)?) {
-            self.game_mode = GameMode::Menu;
-            self.paused_time = Some(Instant::now());
-        }
-
-        if self.is_paused {
+    /// * Handles button clicks and focused button activations for changing the bindings of an action.
+    /// * The next key or gamepad button pressed is then bound to the action; repeats of a held key are ignored.
+    ///   Escape cancels, so it cannot be bound.
+    /// * Handles the button for switching between the standard and the mouse-follow control schemes.
+    /// * Handles the button for switching between the bindings of the first and the second player.
+    /// * Handles the buttons for restoring the shown player's default bindings and returning to the main menu.
+    fn update_controls(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
+        let activated = self.menu_focus.take_activation();
+        if self.rebinding.is_some() {
             return Ok(());
         }
 
-        if self.level_start_time.elapsed() >= Duration::from_secs(LEVEL_DURATION_SECS) {
-            if self.current_level + 1 < self.levels.len() {
-                self.game_mode = GameMode::NextLevel;
-            } else {
-                self.game_mode = GameMode::Victory;
+        let actions = self.controls_actions();
+        for (i, button) in self.rebind_buttons()?.iter().enumerate() {
+            if is_button_clicked(ctx, text_button_rect(button)?) || activated == Some(i) {
+                self.rebinding = Some((self.controls_player, actions[i]));
             }
         }
 
-        if self.last_update.elapsed() >= Duration::from_millis(FALLING_OBJECT_UPDATE_MILLIS) {
-            self.last_update = Instant::now();
-            self.create_falling_object()?;
+        if is_button_clicked(ctx, text_button_rect(&self.control_scheme_button()?)?)
+            || activated == Some(actions.len())
+        {
+            self.settings.control_scheme = self.settings.control_scheme.toggled();
+            self.settings.save(&self.settings_dir)?;
         }
 
-        for obj in &mut self.falling_objects {
-            if obj.remove_timer.is_none() {
-                obj.update(&self.resources, 0.1);
-            }
+        if is_button_clicked(ctx, text_button_rect(&self.controls_player_button()?)?)
+            || activated == Some(actions.len() + 1)
+        {
+            self.controls_player = (self.controls_player + 1) % 2;
         }
 
-        self.handle_collisions(ctx)?;
-
-        if let Some(timer) = self.player.blink_timer {
-            if timer.elapsed() >= Duration::from_secs(1) {
-                self.player.blink_timer = None;
-                self.player.alpha = 1.0;
+        if is_button_clicked(ctx, text_button_rect(&self.reset_controls_button)?)
+            || activated == Some(actions.len() + 2)
+        {
+            match self.controls_player {
+                0 => self.settings.bindings = InputBindings::default(),
+                _ => self.settings.player_two_bindings = InputBindings::player_two(),
             }
+            self.settings.save(&self.settings_dir)?;
+        }
+
+        if is_button_clicked(ctx, text_button_rect(&self.back_to_menu_button)?)
+            || activated == Some(actions.len() + 3)
+        {
+            self.game_mode = GameMode::Menu;
         }
         Ok(())
     }
 
-    /// **Draws the game state when in the playing mode.**
+    /// **Draws the controls screen on the canvas.**
     ///
     /// ## Parameters
     /// * `ctx`: the game context.
@@ -597,96 +3507,229 @@ impl GameState {
     /// `Ok(())` if drawing is successful, or a `DodgerError` if text or button drawing fails.
     ///
     /// ## Behavior
-    /// Draws the background, player, falling objects, and UI elements (score, timer, lives).
-    fn draw_playing(&mut self, ctx: &mut Context, canvas: &mut Canvas) -> Result<(), DodgerError> {
-        draw_background(canvas, &self.resources.background_image);
-        self.player.draw(canvas);
-        draw_button_with_text(ctx, canvas, self.menu_button.clone())?;
+    /// Draws the background, the shown player's bindings of each action with a button to change them, the control scheme with a button to switch it, and the player, reset and back buttons.
+    fn draw_controls(&mut self, ctx: &mut Context, canvas: &mut Canvas) -> Result<(), DodgerError> {
+        draw_background(canvas, &self.resources.menu_background_image);
 
-        let text = format!("Level {}", self.current_level + 1);
-        let text_to_draw = DrawText::new(
-            Point2::from_slice(&[525.0, 10.0]),
-            text,
+        let title = DrawText::new(
+            Point2::from_slice(&[60.0, 10.0]),
+            format!("Controls: Player {}", self.controls_player + 1),
             "text_font".to_string(),
-            TEXT_SIZE,
+            48.0,
             Color::WHITE,
         )?;
+        draw_text(canvas, title)?;
 
-        draw_text(canvas, text_to_draw)?;
-        if self.audio.is_muted {
-            self.audio_button.icon = self.audio.speaker_muted_icon.clone();
-        } else {
-            self.audio_button.icon = self.audio.speaker_icon.clone();
-        };
-        draw_icon(canvas, &self.audio_button)?;
-        for obj in &mut self.falling_objects {
-            obj.draw(canvas);
+        let actions = self.controls_actions();
+        for (button, action) in self.rebind_buttons()?.into_iter().zip(actions) {
+            let line = format!(
+                "{}: {}",
+                action.label(),
+                self.controls_bindings().describe(*action)
+            );
+            let binding_text = DrawText::new(
+                Point2::from_slice(&[60.0, button.coords.y + 10.0]),
+                line,
+                "text_font".to_string(),
+                BUTTON_TEXT_SIZE,
+                Color::WHITE,
+            )?;
+            draw_text(canvas, binding_text)?;
+            draw_button_with_text(ctx, canvas, button)?;
         }
 
-        let level_score_text = format!("Level Score: {}", self.level_score);
-        let level_score_text_to_draw = DrawText::new(
-            Point2::from_slice(&[10.0, 10.0]),
-            level_score_text,
+        let control_scheme_button = self.control_scheme_button()?;
+        let control_scheme_text = DrawText::new(
+            Point2::from_slice(&[60.0, control_scheme_button.coords.y + 10.0]),
+            format!("Control Scheme: {}", self.settings.control_scheme.label()),
             "text_font".to_string(),
-            TEXT_SIZE,
+            BUTTON_TEXT_SIZE,
             Color::WHITE,
         )?;
-        draw_score(canvas, level_score_text_to_draw)?;
+        draw_text(canvas, control_scheme_text)?;
+        draw_button_with_text(ctx, canvas, control_scheme_button)?;
 
-        draw_timer(ctx, canvas, self.get_remaining_time())?;
+        draw_button_with_text(ctx, canvas, self.controls_player_button()?)?;
+        draw_button_with_text(ctx, canvas, self.reset_controls_button.clone())?;
+        draw_button_with_text(ctx, canvas, self.back_to_menu_button.clone())?;
+        Ok(())
+    }
 
-        let total_score_text = format!("Total Score: {}", self.level_score + self.total_score);
-        let total_score_text_to_draw = DrawText::new(
-            Point2::from_slice(&[10.0, 50.0]),
-            total_score_text,
-            "text_font".to_string(),
-            TEXT_SIZE,
-            Color::WHITE,
-        )?;
-        draw_score(canvas, total_score_text_to_draw)?;
+    /// **Updates the game state when in "Game Over" mode.**
+    ///
+    /// ## Parameters
+    /// `ctx`: the game context.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the update is successful, or a `DodgerError` if sound playback or starting a new run fails.
+    ///
+    /// ## Behavior
+    /// * Plays the "game over" sound.
+    /// * Handles button clicks and focused button activations for spending a continue while any is left,
+    ///   retrying the level reached, starting a new run from the first level, and returning to the main menu.
+    /// * Retrying the level spends a continue too, see `retry_lost_level`; starting over and returning to the main menu end the lost run.
+    fn update_game_over(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
+        if !self.game_over_sound_played {
+            self.audio.play_sound(ctx, "game_over".to_string())?;
+            self.game_over_sound_played = true;
+        }
+        let activated = self.menu_focus.take_activation();
+        let mut pressed = None;
+        for (i, button) in self.game_over_buttons()?.iter().enumerate() {
+            if is_button_clicked(ctx, text_button_rect(button)?) || activated == Some(i) {
+                pressed = Some(i);
+            }
+        }
+        let Some(pressed) = pressed else {
+            return Ok(());
+        };
 
-        let lives_text_to_draw = DrawText::new(
-            Point2::from_slice(&[530.0, 60.0]),
-            format!("Lives: {}", self.lives),
+        // The continue button comes first while any continue is left.
+        match pressed.checked_sub(usize::from(self.can_use_continue())) {
+            None => self.use_continue(),
+            Some(0) => self.retry_lost_level(ctx)?,
+            Some(1) => {
+                self.end_lost_run()?;
+                self.start_game(ctx, self.play_mode, 0)?;
+            }
+            _ => {
+                self.end_lost_run()?;
+                self.game_started = false;
+                self.game_mode = GameMode::Menu;
+            }
+        }
+        Ok(())
+    }
+
+    /// **Creates the buttons of the "Game Over" screen.**
+    ///
+    /// ## Returns
+    /// A result containing the buttons for continuing the level while continues are left, retrying the level reached (labeled with its penalty while it spends a continue),
+    /// restarting from the first level and returning to the main menu, or a `DodgerError` if they cannot be created.
+    fn game_over_buttons(&self) -> Result<Vec<TextButton>, DodgerError> {
+        let mut labels = Vec::new();
+        if self.can_use_continue() {
+            labels.push(format!(
+                "Continue ({}) -{}",
+                self.continues.left(),
+                CONTINUE_SCORE_PENALTY
+            ));
+            labels.push(format!("Retry Level -{}", CONTINUE_SCORE_PENALTY));
+        } else {
+            labels.push("Retry Level".to_string());
+        }
+        labels.extend(["From Level 1", "Main Menu"].map(String::from));
+        labels
+            .into_iter()
+            .enumerate()
+            .map(|(i, label)| {
+                TextButton::new(
+                    start_point_of_button_in_set(i, 480.0),
+                    Color::WHITE,
+                    text_button_rectsize(),
+                    label,
+                    Color::BLACK,
+                    BUTTON_TEXT_SIZE,
+                    "button_font".to_string(),
+                )
+            })
+            .collect()
+    }
+
+    /// **Draws the "Game Over" screen on the canvas.**
+    ///
+    /// ## Parameters
+    /// * `ctx`: the game context.
+    /// * `canvas`: canvas to draw on.
+    ///
+    /// ## Returns
+    /// `Ok(())` if drawing is successful, or a `DodgerError` if text or button drawing fails.
+    ///
+    /// ## Behavior
+    /// Draws the background, "Game Over" text, the final score, the level reached, the cause and time of death,
+    /// the comparison with the personal best, and the buttons to continue, retry, restart or return to the main menu.
+    fn draw_game_over(
+        &mut self,
+        ctx: &mut Context,
+        canvas: &mut Canvas,
+    ) -> Result<(), DodgerError> {
+        draw_background(canvas, &self.resources.background_image);
+        let game_over_text = DrawText::new(
+            Point2::from_slice(&[WINDOW_WIDTH / 2.0 - 95.0, 120.0]),
+            "Game Over".to_string(),
             "text_font".to_string(),
-            TEXT_SIZE,
+            48.0,
             Color::WHITE,
         )?;
-        draw_text(canvas, lives_text_to_draw)?;
+        draw_text(canvas, game_over_text)?;
 
-        if self.is_paused {
-            draw_icon(canvas, &self.pause_button)?;
+        if let Some(report) = &self.game_over_report {
+            let lines = [
+                format!("Final Score: {}", report.final_score),
+                format!("Level reached: {}", report.level + 1),
+                format!("{} at {}", report.cause, report.time_of_death()),
+                report.best_comparison(),
+            ];
+            for (i, line) in lines.into_iter().enumerate() {
+                let text = DrawText::new(
+                    Point2::from_slice(&[WINDOW_WIDTH / 2.0 - 200.0, 220.0 + i as f32 * 60.0]),
+                    line,
+                    "text_font".to_string(),
+                    TEXT_SIZE,
+                    Color::WHITE,
+                )?;
+                draw_text(canvas, text)?;
+            }
         }
 
+        for button in self.game_over_buttons()? {
+            draw_button_with_text(ctx, canvas, button)?;
+        }
         Ok(())
     }
 
-    /// **Updates the game state when in the "Next Level" mode.**
+    /// **Updates the game state when in the versus result screen.**
     ///
     /// ## Parameters
     /// `ctx`: the game context.
     ///
     /// ## Returns
-    /// `Ok(())` if the update is successful, or a `DodgerError` if sound playback fails.
+    /// `Ok(())` if the update is successful, or a `DodgerError` if sound playback or resetting the game fails.
     ///
     /// ## Behavior
-    /// * Plays the "level completed" sound.
-    /// * Handles button clicks for proceeding to the next level.
-    fn update_next_level(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
-        if !self.level_complete_sound_played {
-            self.audio.play_sound(ctx, "level_completed".to_string())?;
-            self.level_complete_sound_played = true;
+    /// * Plays the "victory" sound.
+    /// * Handles button clicks and focused button activations for a rematch and for returning to the main menu.
+    /// * Networked games offer no rematch; returning to the main menu closes the connection.
+    fn update_versus_result(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
+        if !self.victory_sound_played {
+            self.audio.play_sound(ctx, "victory".to_string())?;
+            self.victory_sound_played = true;
         }
 
-        if is_button_clicked(ctx, text_button_rect(&self.next_level_button)?) {
-            self.current_level += 1;
-            self.reset(ctx)?;
+        let activated = self.menu_focus.take_activation();
+        if self.net.is_some() {
+            if is_button_clicked(ctx, text_button_rect(&self.main_menu_button)?)
+                || activated == Some(0)
+            {
+                self.leave_net_game(None);
+            }
+            return Ok(());
+        }
+
+        if is_button_clicked(ctx, text_button_rect(&self.rematch_button)?) || activated == Some(0) {
+            self.start_game(ctx, PlayMode::Versus, self.current_level)?;
         }
 
+        if is_button_clicked(ctx, text_button_rect(&self.main_menu_button)?) || activated == Some(1)
+        {
+            self.game_started = false;
+            self.play_mode = PlayMode::Single;
+            self.game_mode = GameMode::Menu;
+        }
         Ok(())
     }
 
-    /// **Draws the "Next Level" screen on the canvas.**
+    /// **Draws the versus result screen on the canvas.**
     ///
     /// ## Parameters
     /// * `ctx`: the game context.
@@ -696,265 +3739,575 @@ impl GameState {
     /// `Ok(())` if drawing is successful, or a `DodgerError` if text or button drawing fails.
     ///
     /// ## Behavior
-    /// Draws the background, "Level Complete" text, and a button to proceed to the next level.
-    fn draw_next_level(
+    /// Draws the background, the winner (or a draw), the score of each player, and the rematch (local games only) and main menu buttons.
+    fn draw_versus_result(
         &mut self,
         ctx: &mut Context,
         canvas: &mut Canvas,
     ) -> Result<(), DodgerError> {
         draw_background(canvas, &self.resources.background_image);
-        let level_complete_text = DrawText::new(
-            Point2::from_slice(&[WINDOW_WIDTH / 2.0 - 160.0, WINDOW_HEIGHT / 2.0 - 100.0]),
-            "Level Complete!".to_string(),
+
+        let results: Vec<(u8, i32)> = self
+            .world
+            .players
+            .iter()
+            .map(|state| (state.lives, state.level_score))
+            .collect();
+        let title = match versus_winner(&results) {
+            Some(winner) => format!("Player {} Wins!", winner + 1),
+            None => "Draw!".to_string(),
+        };
+        let title_text = DrawText::new(
+            Point2::from_slice(&[WINDOW_WIDTH / 2.0 - 150.0, WINDOW_HEIGHT / 2.0 - 200.0]),
+            title,
             "text_font".to_string(),
             48.0,
             Color::WHITE,
         )?;
-        draw_text(canvas, level_complete_text)?;
-        draw_button_with_text(ctx, canvas, self.next_level_button.clone())?;
+        draw_text(canvas, title_text)?;
+
+        for (i, state) in self.world.players.iter().enumerate() {
+            let line = format!(
+                "Player {}: {} points, {} lives left",
+                i + 1,
+                state.level_score,
+                state.lives
+            );
+            let score_text = DrawText::new(
+                Point2::from_slice(&[
+                    WINDOW_WIDTH / 2.0 - 200.0,
+                    WINDOW_HEIGHT / 2.0 - 120.0 + i as f32 * 40.0,
+                ]),
+                line,
+                "text_font".to_string(),
+                TEXT_SIZE,
+                self.player_tint(i),
+            )?;
+            draw_text(canvas, score_text)?;
+        }
+
+        if self.net.is_none() {
+            draw_button_with_text(ctx, canvas, self.rematch_button.clone())?;
+        }
+        draw_button_with_text(ctx, canvas, self.main_menu_button.clone())?;
         Ok(())
     }
-    /// **Updates the game state when in "How to Play" screen.**
+
+    /// **Updates the game state when in "Victory" mode.**
+    ///
+    /// ## Parameters
+    /// `ctx`: the game context.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the update is successful, or a `DodgerError` error if sound playback or starting a new run fails.
+    ///
+    /// ## Behavior
+    /// * Plays the "victory" sound.
+    /// * Handles button clicks and focused button activations for starting a new run from the first level, see `start_game`.
+    fn update_victory(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
+        if !self.victory_sound_played {
+            self.audio.play_sound(ctx, "victory".to_string())?;
+            self.victory_sound_played = true;
+        }
+        let activated = self.menu_focus.take_activation();
+        if is_button_clicked(ctx, text_button_rect(&self.restart_button)?) || activated == Some(0) {
+            self.start_game(ctx, self.play_mode, 0)?;
+        }
+        Ok(())
+    }
+
+    /// **Draws the "Victory" screen on the canvas.**
+    ///
+    /// ## Parameters
+    /// * `ctx`: the game context.
+    /// * `canvas`: ccanvas to draw on.
+    ///
+    /// ## Returns
+    /// `Ok(())` if drawing is successful, or a `DodgerError` if text or button drawing fails.
+    ///
+    /// ## Behavior
+    /// Draws the background, victory text, final score, and a restart button.
+    fn draw_victory(&mut self, ctx: &mut Context, canvas: &mut Canvas) -> Result<(), DodgerError> {
+        draw_background(canvas, &self.resources.background_image);
+        let game_complete_text = DrawText::new(
+            Point2::from_slice(&[WINDOW_WIDTH / 2.0 - 185.0, WINDOW_HEIGHT / 2.0 - 165.0]),
+            "You Win! Game Over".to_string(),
+            "text_font".to_string(),
+            48.0,
+            Color::WHITE,
+        )?;
+        draw_text(canvas, game_complete_text)?;
+
+        let final_score_text = format!("Final Score: {}", self.total_score + self.level_score());
+        let final_score_text_to_draw = DrawText::new(
+            Point2::from_slice(&[WINDOW_WIDTH / 2.0 - 110.0, WINDOW_HEIGHT / 2.0 - 115.0]),
+            final_score_text,
+            "text_font".to_string(),
+            TEXT_SIZE,
+            Color::WHITE,
+        )?;
+        draw_text(canvas, final_score_text_to_draw)?;
+        self.draw_best_score(canvas, WINDOW_HEIGHT / 2.0 - 75.0)?;
+        draw_button_with_text(ctx, canvas, self.restart_button.clone())?;
+        Ok(())
+    }
+
+    /// **Updates the game state when in "Level Selection" mode.**
     ///
     /// ## Parameters
     /// `ctx`: the game context.
     ///
     /// ## Returns
     /// `Ok(())` if the update is successful, or a `DodgerError` if button handling fails.
-    fn update_how_to_play(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
-        if is_button_clicked(ctx, text_button_rect(&self.back_to_menu_button)?) {
-            self.game_mode = GameMode::Menu;
+    ///
+    /// ## Behavior
+    /// * Handles button clicks and focused button activations for selecting a level.
+    /// * Starts a single-player game from the selected level, unless the level is still locked.
+    fn update_select_level(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
+        let activated = self.menu_focus.take_activation();
+        for i in 0..self.levels.len() {
+            let level_button = self.level_button(i)?;
+
+            if (is_button_clicked(ctx, text_button_rect(&level_button)?) || activated == Some(i))
+                && self.progress.is_unlocked(i)
+            {
+                self.start_game(ctx, PlayMode::Single, i)?;
+            }
         }
+
         Ok(())
     }
 
-    /// **Draws the "How to Play" screen on the canvas.**
+    /// **Creates the button of a level on the "Level Selection" screen.**
+    ///
+    /// ## Parameters
+    /// `level_index`: index of the level (0-based).
+    ///
+    /// ## Returns
+    /// A result containing the button, grayed out if the level is locked, or a `DodgerError` if it cannot be created.
+    fn level_button(&self, level_index: usize) -> Result<TextButton, DodgerError> {
+        let mut button = get_level_button(level_index, 100.0, "button_font".to_string())?;
+        if !self.progress.is_unlocked(level_index) {
+            button.button_color = Color::new(0.6, 0.6, 0.6, 1.0);
+        }
+        Ok(button)
+    }
+
+    /// **Draws the "Level Selection" screen on the canvas.**
     ///
     /// ## Parameters
     /// * `ctx`: the game context.
     /// * `canvas`: canvas to draw on.
     ///
     /// ## Returns
-    /// `Ok(())` if drawing is successful, or a `DodgerError` if text or button drawing fails.
+    /// `Ok(())` if drawing is successful, or a `DodgerError` if button drawing fails.
     ///
     /// ## Behavior
-    /// Draws the background, title, instructions, and a button to return to the main menu.
-    fn draw_how_to_play(
+    /// Draws the background and a button for each level: unlocked levels show their stars and best score, locked levels a padlock.
+    fn draw_select_level(
         &mut self,
         ctx: &mut Context,
         canvas: &mut Canvas,
     ) -> Result<(), DodgerError> {
         draw_background(canvas, &self.resources.menu_background_image);
+        for i in 0..self.levels.len() {
+            let level_button = self.level_button(i)?;
+            let right = level_button.coords.x + TEXT_BUTTON_WIDTH + 20.0;
+            let top = level_button.coords.y;
+            draw_button_with_text(ctx, canvas, level_button)?;
 
-        let title = DrawText::new(
-            Point2::from_slice(&[WINDOW_WIDTH / 2.0 - 150.0, 150.0]),
-            "How to Play".to_string(),
-            "text_font".to_string(),
-            48.0,
-            Color::WHITE,
-        )?;
-        draw_text(canvas, title)?;
-
-        let instructions = vec![
-            "Use Left/Right arrows to move the player.",
-            "Press Space to pause the game.",
-            "Catch good objects to earn points:",
-            "  - High value: 30 points",
-            "  - Medium value: 15 points",
-            "  - Low value: 5 points",
-            "Avoid bad objects! They reduce your lives.",
-            "Each level lasts 40 seconds.",
-        ];
+            if !self.progress.is_unlocked(i) {
+                draw_lock(ctx, canvas, Point2::from_slice(&[right, top + 7.0]))?;
+                continue;
+            }
 
-        let mut y_offset = 250.0;
-        for line in instructions {
-            let instruction_text = DrawText::new(
-                Point2::from_slice(&[150.0, y_offset]),
-                line.to_string(),
-                "text_font".to_string(),
-                TEXT_SIZE,
-                Color::WHITE,
-            )?;
-            draw_text(canvas, instruction_text)?;
-            y_offset += 50.0;
+            let level_progress = self.progress.level(i);
+            for star in 0..3u8 {
+                let center = Point2::from_slice(&[right + 15.0 + star as f32 * 36.0, top + 25.0]);
+                draw_star(ctx, canvas, center, 15.0, star < level_progress.stars)?;
+            }
+            if level_progress.is_completed() {
+                let best_text = DrawText::new(
+                    Point2::from_slice(&[right - TEXT_BUTTON_WIDTH - 200.0, top + 12.0]),
+                    format!("Best: {}", level_progress.best_score),
+                    "text_font".to_string(),
+                    BUTTON_TEXT_SIZE,
+                    Color::WHITE,
+                )?;
+                draw_text(canvas, best_text)?;
+            }
         }
 
-        draw_button_with_text(ctx, canvas, self.back_to_menu_button.clone())?;
-
         Ok(())
     }
-    /// **Updates the game state when in "Game Over" mode.**
+
+    /// **Creates a button for each profile on the profiles screen.**
+    ///
+    /// ## Returns
+    /// A result containing the buttons labeled with the profile names, the active profile highlighted, or a `DodgerError` if a button cannot be created.
+    fn profile_buttons(&self) -> Result<Vec<TextButton>, DodgerError> {
+        self.profiles
+            .profiles
+            .iter()
+            .enumerate()
+            .map(|(i, profile)| {
+                let button_color = if i == self.profiles.active {
+                    Color::new(0.7, 1.0, 0.7, 1.0)
+                } else {
+                    Color::WHITE
+                };
+                TextButton::new(
+                    start_point_of_button_in_set(i, 100.0),
+                    button_color,
+                    text_button_rectsize(),
+                    profile.name.clone(),
+                    Color::BLACK,
+                    BUTTON_TEXT_SIZE,
+                    "button_font".to_string(),
+                )
+            })
+            .collect()
+    }
+
+    /// **Creates the button for deleting the active profile on the profiles screen.**
+    ///
+    /// ## Returns
+    /// A result containing the button, asking for confirmation after the first press and grayed out when only one profile is left,
+    /// or a `DodgerError` if it cannot be created.
+    fn delete_profile_button(&self) -> Result<TextButton, DodgerError> {
+        let label = if self.confirm_profile_delete {
+            "Sure?"
+        } else {
+            "Delete"
+        };
+        let mut button = TextButton::new(
+            start_point_of_button_in_set(MAX_PROFILES + 3, 100.0),
+            Color::WHITE,
+            text_button_rectsize(),
+            label.to_string(),
+            Color::BLACK,
+            BUTTON_TEXT_SIZE,
+            "button_font".to_string(),
+        )?;
+        if self.profiles.profiles.len() == 1 {
+            button.button_color = Color::new(0.6, 0.6, 0.6, 1.0);
+        }
+        Ok(button)
+    }
+
+    /// **Makes another profile the active one.**
     ///
     /// ## Parameters
-    /// `ctx`: the game context.
+    /// `index`: index of the profile.
     ///
     /// ## Returns
-    /// `Ok(())` if the update is successful, or a `DodgerError` if sound playback fails.
+    /// `Ok(())` if the profile is activated, or a `DodgerError` if the run in progress or the profile list cannot be stored.
     ///
     /// ## Behavior
-    /// * Plays the "game over" sound.
-    /// * Handles button clicks for restarting the game.
-    fn update_game_over(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
-        if !self.game_over_sound_played {
-            self.audio.play_sound(ctx, "game_over".to_string())?;
-            self.game_over_sound_played = true;
-        }
-        if is_button_clicked(ctx, text_button_rect(&self.restart_button)?) {
-            self.current_level = 0;
-            self.reset(ctx)?;
+    /// The run in progress is saved for the previous profile, which can continue it later, and the main menu starts a new run.
+    /// The level being played is added to the statistics of the previous profile.
+    fn switch_profile(&mut self, index: usize) -> Result<(), DodgerError> {
+        if index == self.profiles.active || index >= self.profiles.profiles.len() {
+            return Ok(());
         }
+        self.save_run()?;
+        self.record_level_stats(false)?;
+        self.profiles.active = index;
+        self.profiles.save(&self.config_dir)?;
+        self.load_profile();
         Ok(())
     }
-    /// **Draws the "Game Over" screen on the canvas.**
+
+    /// **Loads the settings, high scores, progress, statistics, achievements and saved game of the active profile.**
+    ///
+    /// ## Behavior
+    /// * Ends the game started in this session, if any, and selects the first level.
+    /// * Corrupted files fall back to the defaults, as when the game starts.
+    fn load_profile(&mut self) {
+        self.game_started = false;
+        self.is_paused = false;
+        self.run_saved = false;
+        self.current_level = 0;
+        self.settings_dir = self.profiles.active().dir(&self.config_dir);
+        self.settings = Settings::load(&self.settings_dir).unwrap_or_default();
+        self.high_scores = HighScores::load(&self.settings_dir).unwrap_or_default();
+        self.progress = Progress::load(&self.settings_dir).unwrap_or_default();
+        self.statistics = Statistics::load(&self.settings_dir).unwrap_or_default();
+        self.level_tally = LevelTally::default();
+        self.achievements = UnlockedAchievements::load(&self.settings_dir).unwrap_or_default();
+        self.toasts.clear();
+        self.saved_game = SaveGame::load(&self.settings_dir, self.levels.len()).unwrap_or(None);
+        for movement in &mut self.movements {
+            movement.clear();
+        }
+    }
+
+    /// **Opens the profile name screen.**
     ///
     /// ## Parameters
-    /// * `ctx`: the game context.
-    /// * `canvas`: canvas to draw on.
+    /// `target`: index of the profile to rename, or `None` to create a new profile.
+    fn open_profile_editor(&mut self, target: Option<usize>) {
+        let name = target
+            .and_then(|i| self.profiles.profiles.get(i))
+            .map(|profile| profile.name.clone())
+            .unwrap_or_default();
+        self.profile_editor = ProfileEditor {
+            target,
+            name,
+            status: None,
+        };
+        self.game_mode = GameMode::ProfileName;
+    }
+
+    /// **Creates or renames a profile with the name typed on the profile name screen.**
     ///
     /// ## Returns
-    /// `Ok(())` if drawing is successful, or a `DodgerError` if text or button drawing fails.
+    /// `Ok(())` if the name is handled, or a `DodgerError` if the profile list cannot be stored.
     ///
     /// ## Behavior
-    /// Draws the background, "Game Over" text, and a restart button.
-    fn draw_game_over(
-        &mut self,
-        ctx: &mut Context,
-        canvas: &mut Canvas,
-    ) -> Result<(), DodgerError> {
-        draw_background(canvas, &self.resources.background_image);
-        let game_over_text = DrawText::new(
-            Point2::from_slice(&[WINDOW_WIDTH / 2.0 - 95.0, WINDOW_HEIGHT / 2.0 - 75.0]),
-            "Game Over".to_string(),
-            "text_font".to_string(),
-            48.0,
-            Color::WHITE,
-        )?;
-        draw_text(canvas, game_over_text)?;
+    /// * A rejected name is shown on the screen and can be corrected.
+    /// * A new profile becomes the active one; the profiles screen follows.
+    fn confirm_profile_name(&mut self) -> Result<(), DodgerError> {
+        let name = self.profile_editor.name.clone();
+        let result = match self.profile_editor.target {
+            Some(index) => self.profiles.rename(index, &name).map(|()| None),
+            None => self.profiles.create(&name).map(Some),
+        };
+        match result {
+            Ok(created) => {
+                self.profiles.save(&self.config_dir)?;
+                if let Some(index) = created {
+                    self.switch_profile(index)?;
+                }
+                self.game_mode = GameMode::Profiles;
+            }
+            Err(err) => self.profile_editor.status = Some(err.to_string()),
+        }
+        Ok(())
+    }
 
-        draw_button_with_text(ctx, canvas, self.restart_button.clone())?;
+    /// **Deletes the active profile with all its files.**
+    ///
+    /// ## Returns
+    /// `Ok(())` if the profile is deleted or it is the last one, or a `DodgerError` if its files or the profile list cannot be removed or stored.
+    ///
+    /// ## Behavior
+    /// The run in progress is dropped, and the first remaining profile becomes the active one.
+    fn delete_active_profile(&mut self) -> Result<(), DodgerError> {
+        let Some(removed) = self.profiles.remove(self.profiles.active) else {
+            return Ok(());
+        };
+        self.profiles.save(&self.config_dir)?;
+        removed.delete_files(&self.config_dir)?;
+        self.load_profile();
         Ok(())
     }
 
-    /// **Updates the game state when in "Victory" mode.**
+    /// **Updates the game state when in the profiles screen.**
     ///
     /// ## Parameters
     /// `ctx`: the game context.
     ///
     /// ## Returns
-    /// `Ok(())` if the update is successful, or a `DodgerError` error if sound playback fails.
+    /// `Ok(())` if the update is successful, or a `DodgerError` if button handling or storing the profiles fails.
     ///
     /// ## Behavior
-    /// * Plays the "victory" sound.
-    /// * Handles button clicks for restarting the game.
-    fn update_victory(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
-        if !self.victory_sound_played {
-            self.audio.play_sound(ctx, "victory".to_string())?;
-            self.victory_sound_played = true;
+    /// * Handles button clicks and focused button activations for making a profile the active one.
+    /// * Handles the buttons for creating a profile, unless there are `MAX_PROFILES` already, and for renaming the active profile.
+    /// * Handles the button for deleting the active profile, which needs a second press; the last profile cannot be deleted.
+    /// * Handles the button for returning to the main menu.
+    fn update_profiles(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
+        let activated = self.menu_focus.take_activation();
+        let count = self.profiles.profiles.len();
+
+        for (i, button) in self.profile_buttons()?.iter().enumerate() {
+            if is_button_clicked(ctx, text_button_rect(button)?) || activated == Some(i) {
+                self.confirm_profile_delete = false;
+                self.switch_profile(i)?;
+            }
         }
-        if is_button_clicked(ctx, text_button_rect(&self.restart_button)?) {
-            self.current_level = 0;
-            self.reset(ctx)?;
+
+        if (is_button_clicked(ctx, text_button_rect(&self.new_profile_button)?)
+            || activated == Some(count))
+            && count < MAX_PROFILES
+        {
+            self.open_profile_editor(None);
+        }
+
+        if is_button_clicked(ctx, text_button_rect(&self.rename_profile_button)?)
+            || activated == Some(count + 1)
+        {
+            self.open_profile_editor(Some(self.profiles.active));
+        }
+
+        if is_button_clicked(ctx, text_button_rect(&self.delete_profile_button()?)?)
+            || activated == Some(count + 2)
+        {
+            if self.confirm_profile_delete {
+                self.delete_active_profile()?;
+            }
+            self.confirm_profile_delete = !self.confirm_profile_delete && count > 1;
+        }
+
+        if is_button_clicked(ctx, text_button_rect(&self.back_to_menu_button)?)
+            || activated == Some(count + 3)
+        {
+            self.game_mode = GameMode::Menu;
+        }
+
+        if self.game_mode != GameMode::Profiles {
+            self.confirm_profile_delete = false;
         }
         Ok(())
     }
 
-    /// **Draws the "Victory" screen on the canvas.**
+    /// **Draws the profiles screen on the canvas.**
     ///
     /// ## Parameters
     /// * `ctx`: the game context.
-    /// * `canvas`: ccanvas to draw on.
+    /// * `canvas`: canvas to draw on.
     ///
     /// ## Returns
     /// `Ok(())` if drawing is successful, or a `DodgerError` if text or button drawing fails.
     ///
     /// ## Behavior
-    /// Draws the background, victory text, final score, and a restart button.
-    fn draw_victory(&mut self, ctx: &mut Context, canvas: &mut Canvas) -> Result<(), DodgerError> {
-        draw_background(canvas, &self.resources.background_image);
-        let game_complete_text = DrawText::new(
-            Point2::from_slice(&[WINDOW_WIDTH / 2.0 - 185.0, WINDOW_HEIGHT / 2.0 - 125.0]),
-            "You Win! Game Over".to_string(),
+    /// Draws the background, the title, a button for each profile with the active one highlighted, and the new, rename, delete and back buttons.
+    fn draw_profiles(&mut self, ctx: &mut Context, canvas: &mut Canvas) -> Result<(), DodgerError> {
+        draw_background(canvas, &self.resources.menu_background_image);
+
+        let title = DrawText::new(
+            Point2::from_slice(&[60.0, 10.0]),
+            "Profiles".to_string(),
             "text_font".to_string(),
             48.0,
             Color::WHITE,
         )?;
-        draw_text(canvas, game_complete_text)?;
+        draw_text(canvas, title)?;
 
-        let final_score_text = format!("Final Score: {}", self.total_score + self.level_score);
-        let final_score_text_to_draw = DrawText::new(
-            Point2::from_slice(&[WINDOW_WIDTH / 2.0 - 110.0, WINDOW_HEIGHT / 2.0 - 75.0]),
-            final_score_text,
-            "text_font".to_string(),
-            TEXT_SIZE,
-            Color::WHITE,
-        )?;
-        draw_text(canvas, final_score_text_to_draw)?;
-        draw_button_with_text(ctx, canvas, self.restart_button.clone())?;
+        for button in self.profile_buttons()? {
+            draw_button_with_text(ctx, canvas, button)?;
+        }
+
+        let mut new_profile_button = self.new_profile_button.clone();
+        if self.profiles.profiles.len() >= MAX_PROFILES {
+            new_profile_button.button_color = Color::new(0.6, 0.6, 0.6, 1.0);
+        }
+        draw_button_with_text(ctx, canvas, new_profile_button)?;
+        draw_button_with_text(ctx, canvas, self.rename_profile_button.clone())?;
+        draw_button_with_text(ctx, canvas, self.delete_profile_button()?)?;
+        draw_button_with_text(ctx, canvas, self.back_to_menu_button.clone())?;
         Ok(())
     }
 
-    /// **Updates the game state when in "Level Selection" mode.**
+    /// **Updates the game state when in the profile name screen.**
     ///
     /// ## Parameters
     /// `ctx`: the game context.
     ///
     /// ## Returns
-    /// `Ok(())` if the update is successful, or a `DodgerError` if button handling fails.
+    /// `Ok(())` if the update is successful, or a `DodgerError` if button handling or storing the profiles fails.
     ///
     /// ## Behavior
-    /// * Handles button clicks for selecting a level.
-    /// * Resets the game state to start the selected level.
-    fn update_select_level(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
-        let levels = self.levels.clone();
-        for (i, _) in levels.iter().enumerate() {
-            let level_button = get_level_button(i, 100.0, "button_font".to_string())?;
-
-            if is_button_clicked(ctx, text_button_rect(&level_button)?) {
-                self.current_level = i;
-                self.reset(ctx)?;
-            }
+    /// Handles button clicks and focused button activations for confirming the typed name and returning to the profiles screen.
+    fn update_profile_name(&mut self, ctx: &mut Context) -> Result<(), DodgerError> {
+        let activated = self.menu_focus.take_activation();
+        if is_button_clicked(ctx, text_button_rect(&self.confirm_name_button)?)
+            || activated == Some(0)
+        {
+            self.confirm_profile_name()?;
+            return Ok(());
         }
 
+        if is_button_clicked(ctx, text_button_rect(&self.back_to_menu_button)?)
+            || activated == Some(1)
+        {
+            self.game_mode = GameMode::Profiles;
+        }
         Ok(())
     }
 
-    /// **Draws the "Level Selection" screen on the canvas.**
+    /// **Draws the profile name screen on the canvas.**
     ///
     /// ## Parameters
     /// * `ctx`: the game context.
     /// * `canvas`: canvas to draw on.
     ///
     /// ## Returns
-    /// `Ok(())` if drawing is successful, or a `DodgerError` if button drawing fails.
+    /// `Ok(())` if drawing is successful, or a `DodgerError` if text or button drawing fails.
     ///
     /// ## Behavior
-    /// Draws the background and buttons for each available level.
-    fn draw_select_level(
+    /// Draws the background, the title, the typed name, the reason the last name was rejected if any, and the confirm and back buttons.
+    fn draw_profile_name(
         &mut self,
         ctx: &mut Context,
         canvas: &mut Canvas,
     ) -> Result<(), DodgerError> {
         draw_background(canvas, &self.resources.menu_background_image);
-        for (i, _) in self.levels.iter().enumerate() {
-            let level_button = get_level_button(i, 100.0, "button_font".to_string())?;
 
-            draw_button_with_text(ctx, canvas, level_button)?;
+        let title = match self.profile_editor.target {
+            Some(_) => "Rename Profile",
+            None => "New Profile",
+        };
+        let title_text = DrawText::new(
+            Point2::from_slice(&[WINDOW_WIDTH / 2.0 - 150.0, 100.0]),
+            title.to_string(),
+            "text_font".to_string(),
+            48.0,
+            Color::WHITE,
+        )?;
+        draw_text(canvas, title_text)?;
+
+        let name_text = DrawText::new(
+            Point2::from_slice(&[60.0, 200.0]),
+            format!("Name: {}_", self.profile_editor.name),
+            "text_font".to_string(),
+            TEXT_SIZE,
+            Color::WHITE,
+        )?;
+        draw_text(canvas, name_text)?;
+
+        if let Some(status) = &self.profile_editor.status {
+            let status_text = DrawText::new(
+                Point2::from_slice(&[60.0, 260.0]),
+                status.clone(),
+                "text_font".to_string(),
+                BUTTON_TEXT_SIZE,
+                Color::WHITE,
+            )?;
+            draw_text(canvas, status_text)?;
         }
 
+        draw_button_with_text(ctx, canvas, self.confirm_name_button.clone())?;
+        draw_button_with_text(ctx, canvas, self.back_to_menu_button.clone())?;
         Ok(())
     }
 }
 
 impl EventHandler<GameError> for GameState {
     fn update(&mut self, ctx: &mut Context) -> GameResult<()> {
+        self.update_gamepads(ctx);
+        self.update_toasts(ctx);
+        self.sync_menu_focus();
         match self.game_mode {
             GameMode::Menu => self.update_menu(ctx),
+            GameMode::LevelIntro => self.update_level_intro(ctx),
             GameMode::Playing => self.update_playing(ctx),
             GameMode::GameOver => self.update_game_over(ctx),
+            GameMode::LevelSummary => self.update_level_summary(ctx),
             GameMode::NextLevel => self.update_next_level(ctx),
+            GameMode::LevelFailed => self.update_level_failed(ctx),
             GameMode::Victory => self.update_victory(ctx),
             GameMode::LevelSelection => self.update_select_level(ctx),
             GameMode::HowToPlay => self.update_how_to_play(ctx),
+            GameMode::Stats => self.update_stats(ctx),
+            GameMode::Achievements => self.update_achievements(ctx),
+            GameMode::Controls => self.update_controls(ctx),
+            GameMode::Multiplayer => self.update_multiplayer(ctx),
+            GameMode::HostLobby => self.update_host_lobby(ctx),
+            GameMode::JoinLobby => self.update_join_lobby(ctx),
+            GameMode::Profiles => self.update_profiles(ctx),
+            GameMode::ProfileName => self.update_profile_name(ctx),
+            GameMode::VersusResult => self.update_versus_result(ctx),
         }?;
         Ok(())
     }
@@ -964,40 +4317,199 @@ impl EventHandler<GameError> for GameState {
 
         match self.game_mode {
             GameMode::Menu => self.draw_menu(ctx, &mut canvas),
+            GameMode::LevelIntro => self.draw_level_intro(ctx, &mut canvas),
             GameMode::Playing => self.draw_playing(ctx, &mut canvas),
             GameMode::GameOver => self.draw_game_over(ctx, &mut canvas),
+            GameMode::LevelSummary => self.draw_level_summary(ctx, &mut canvas),
             GameMode::NextLevel => self.draw_next_level(ctx, &mut canvas),
+            GameMode::LevelFailed => self.draw_level_failed(ctx, &mut canvas),
             GameMode::Victory => self.draw_victory(ctx, &mut canvas),
             GameMode::LevelSelection => self.draw_select_level(ctx, &mut canvas),
             GameMode::HowToPlay => self.draw_how_to_play(ctx, &mut canvas),
+            GameMode::Stats => self.draw_stats(ctx, &mut canvas),
+            GameMode::Achievements => self.draw_achievements(ctx, &mut canvas),
+            GameMode::Controls => self.draw_controls(ctx, &mut canvas),
+            GameMode::Multiplayer => self.draw_multiplayer(ctx, &mut canvas),
+            GameMode::HostLobby => self.draw_host_lobby(ctx, &mut canvas),
+            GameMode::JoinLobby => self.draw_join_lobby(ctx, &mut canvas),
+            GameMode::Profiles => self.draw_profiles(ctx, &mut canvas),
+            GameMode::ProfileName => self.draw_profile_name(ctx, &mut canvas),
+            GameMode::VersusResult => self.draw_versus_result(ctx, &mut canvas),
         }?;
 
+        self.sync_menu_focus();
+        if let Some(button) = self.focusable_buttons()?.get(self.menu_focus.focused()) {
+            draw_focus_frame(ctx, &mut canvas, text_button_rect(button)?)?;
+        }
+        if let Some(toast) = self.toasts.first() {
+            draw_toast(ctx, &mut canvas, toast)?;
+        }
+
         canvas.finish(&mut ctx.gfx)?;
         Ok(())
     }
 
+    fn quit_event(&mut self, _ctx: &mut Context) -> GameResult<bool> {
+        // The run in progress or between two levels is saved so it can be continued in the next session.
+        self.save_run()?;
+        self.end_lost_run()?;
+        self.record_level_stats(false)?;
+        Ok(false)
+    }
+
     fn key_down_event(
         &mut self,
         _ctx: &mut Context,
         input: KeyInput,
-        _repeat: bool,
+        repeat: bool,
     ) -> GameResult<()> {
-        match input.keycode {
-            Some(KeyCode::Space) => match self.is_paused {
-                true => self.resume(),
-                false => self.pause(),
-            },
-            Some(KeyCode::Left) => {
-                if self.player.coords.x > 0.0 {
-                    self.player.move_left();
-                }
+        let Some(keycode) = input.keycode else {
+            return Ok(());
+        };
+
+        // A key still held from pressing "Change" repeats, and must not be bound to the action.
+        if self.rebinding.is_some() && repeat {
+            return Ok(());
+        }
+        // Escape cancels the rebinding, so it can never be bound to an action.
+        if let Some((player_index, action)) = self.rebinding.take() {
+            if keycode != KeyCode::Escape {
+                self.rebind(player_index, action, Binding::Key(keycode))?;
             }
-            Some(KeyCode::Right) => {
-                if self.player.coords.x < WINDOW_WIDTH - self.player.size.w {
-                    self.player.move_right();
+            return Ok(());
+        }
+
+        if matches!(self.game_mode, GameMode::JoinLobby | GameMode::ProfileName) {
+            // The address or the profile name is typed on these screens, so key bindings are not used.
+            let command = match keycode {
+                KeyCode::Back => {
+                    match self.game_mode {
+                        GameMode::ProfileName => self.profile_editor.name.pop(),
+                        _ => self.lobby.address.pop(),
+                    };
+                    None
                 }
+                KeyCode::Up => Some(MenuCommand::Up),
+                KeyCode::Down | KeyCode::Tab => Some(MenuCommand::Down),
+                KeyCode::Return | KeyCode::NumpadEnter => Some(MenuCommand::Confirm),
+                KeyCode::Escape => Some(MenuCommand::Back),
+                _ => None,
+            };
+            if let Some(command) = command {
+                self.handle_menu_command(command)?;
+            }
+            return Ok(());
+        }
+
+        if keycode == KeyCode::F3 {
+            self.show_debug_overlay = !self.show_debug_overlay;
+            return Ok(());
+        }
+
+        for (player_index, action) in self.actions_for_key(keycode) {
+            self.handle_action(player_index, action, InputSource::Keyboard)?;
+        }
+        Ok(())
+    }
+
+    fn text_input_event(&mut self, _ctx: &mut Context, character: char) -> GameResult<()> {
+        if self.game_mode == GameMode::JoinLobby
+            && character.is_ascii_graphic()
+            && self.lobby.address.len() < MAX_ADDRESS_LENGTH
+        {
+            self.lobby.address.push(character);
+        }
+        if self.game_mode == GameMode::ProfileName
+            && (character.is_alphanumeric() || character == ' ')
+            && self.profile_editor.name.chars().count() < MAX_PROFILE_NAME_LENGTH
+        {
+            self.profile_editor.name.push(character);
+        }
+        Ok(())
+    }
+
+    fn key_up_event(&mut self, _ctx: &mut Context, input: KeyInput) -> GameResult<()> {
+        if let Some(keycode) = input.keycode {
+            for (player_index, action) in self.actions_for_key(keycode) {
+                self.handle_action_released(player_index, action, InputSource::Keyboard);
+            }
+        }
+        Ok(())
+    }
+
+    fn mouse_motion_event(
+        &mut self,
+        _ctx: &mut Context,
+        x: f32,
+        y: f32,
+        _dx: f32,
+        _dy: f32,
+    ) -> GameResult<()> {
+        self.sync_menu_focus();
+        let hovered = self
+            .focusable_buttons()?
+            .iter()
+            .map(text_button_rect)
+            .position(|rect| rect.is_ok_and(|rect| rect.contains(Point2 { x, y })));
+
+        if let Some(index) = hovered {
+            self.menu_focus.set_focused(index);
+        }
+        Ok(())
+    }
+
+    fn gamepad_button_down_event(
+        &mut self,
+        _ctx: &mut Context,
+        btn: Button,
+        id: GamepadId,
+    ) -> GameResult<()> {
+        if let Some((player_index, action)) = self.rebinding.take() {
+            self.rebind(player_index, action, Binding::Button(btn))?;
+            return Ok(());
+        }
+
+        let player_index = self.gamepad_player(id);
+        for action in self.settings.bindings.actions_for_button(btn) {
+            self.handle_action(player_index, action, InputSource::Gamepad)?;
+        }
+        Ok(())
+    }
+
+    fn gamepad_button_up_event(
+        &mut self,
+        _ctx: &mut Context,
+        btn: Button,
+        id: GamepadId,
+    ) -> GameResult<()> {
+        let player_index = self.gamepad_player(id);
+        for action in self.settings.bindings.actions_for_button(btn) {
+            self.handle_action_released(player_index, action, InputSource::Gamepad);
+        }
+        Ok(())
+    }
+
+    fn gamepad_axis_event(
+        &mut self,
+        _ctx: &mut Context,
+        axis: Axis,
+        value: f32,
+        id: GamepadId,
+    ) -> GameResult<()> {
+        if let Axis::LeftStickX | Axis::DPadX = axis {
+            let player_index = self.gamepad_player(id);
+            if let Some(movement) = self.movements.get_mut(player_index) {
+                movement.set_stick(value);
+            }
+        }
+        Ok(())
+    }
+
+    fn focus_event(&mut self, _ctx: &mut Context, gained: bool) -> GameResult<()> {
+        if !gained {
+            for movement in &mut self.movements {
+                movement.clear();
             }
-            _ => (),
         }
         Ok(())
     }
@@ -0,0 +1,125 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    difficulty::DifficultyPreset,
+    errors::DodgerError,
+    storage::{load_json, save_json},
+};
+
+/// Number of high scores kept.
+pub const MAX_HIGH_SCORES: usize = 10;
+
+/// **A finished run in the high score table.**
+///
+/// ## Fields
+/// * `score`: the total score of the run.
+/// * `level`: number of the last level played (1-based).
+/// * `difficulty`: the difficulty preset the run was played on.
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub struct HighScore {
+    pub score: i32,
+    pub level: usize,
+    pub difficulty: DifficultyPreset,
+}
+
+/// **The best runs, persisted between game sessions.**
+///
+/// ## Fields
+/// `entries`: up to `MAX_HIGH_SCORES` runs, the best first.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HighScores {
+    pub entries: Vec<HighScore>,
+}
+
+impl HighScores {
+    /// **Returns the path of the high score file in a directory.**
+    pub fn path(dir: &Path) -> PathBuf {
+        dir.join("highscores.json")
+    }
+
+    /// **Loads the high scores stored in a directory.**
+    ///
+    /// ## Parameters
+    /// `dir`: directory containing the high score file.
+    ///
+    /// ## Returns
+    /// A result containing the stored high scores (or an empty table if nothing is stored yet), or a `DodgerError` if the file cannot be read or parsed.
+    pub fn load(dir: &Path) -> Result<Self, DodgerError> {
+        Ok(load_json(&Self::path(dir))?.unwrap_or_default())
+    }
+
+    /// **Stores the high scores in a directory.**
+    ///
+    /// ## Parameters
+    /// `dir`: directory to store the high score file in.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the high scores are stored successfully, or a `DodgerError` if the file cannot be written.
+    pub fn save(&self, dir: &Path) -> Result<(), DodgerError> {
+        save_json(&Self::path(dir), self)
+    }
+
+    /// **Adds a finished run to the table.**
+    ///
+    /// ## Parameters
+    /// `entry`: the finished run.
+    ///
+    /// ## Returns
+    /// The rank of the run in the table (0-based), or `None` if it is not good enough to be kept.
+    ///
+    /// ## Behavior
+    /// Runs with the same score keep the order they were added in; only the best `MAX_HIGH_SCORES` runs are kept.
+    pub fn add(&mut self, entry: HighScore) -> Option<usize> {
+        let rank = self
+            .entries
+            .iter()
+            .position(|other| other.score < entry.score)
+            .unwrap_or(self.entries.len());
+        if rank >= MAX_HIGH_SCORES {
+            return None;
+        }
+        self.entries.insert(rank, entry);
+        self.entries.truncate(MAX_HIGH_SCORES);
+        Some(rank)
+    }
+
+    /// **Returns the best run played on a difficulty preset, if any.**
+    pub fn best(&self, difficulty: DifficultyPreset) -> Option<&HighScore> {
+        self.entries
+            .iter()
+            .find(|entry| entry.difficulty == difficulty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(score: i32, difficulty: DifficultyPreset) -> HighScore {
+        HighScore {
+            score,
+            level: 1,
+            difficulty,
+        }
+    }
+
+    #[test]
+    fn test_high_scores_are_sorted_and_capped() {
+        let mut scores = HighScores::default();
+        for score in 0..MAX_HIGH_SCORES as i32 {
+            scores.add(entry(score * 10, DifficultyPreset::Normal));
+        }
+        assert_eq!(scores.add(entry(-5, DifficultyPreset::Easy)), None);
+        assert_eq!(scores.add(entry(45, DifficultyPreset::Hard)), Some(5));
+        assert_eq!(scores.entries.len(), MAX_HIGH_SCORES);
+        assert_eq!(scores.entries[0].score, 90);
+        assert_eq!(
+            scores.best(DifficultyPreset::Hard).map(|e| e.score),
+            Some(45)
+        );
+        assert_eq!(scores.best(DifficultyPreset::Easy), None);
+    }
+}
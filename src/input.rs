@@ -0,0 +1,529 @@
+use std::{collections::BTreeMap, fmt};
+
+use ggez::{event::Button, input::keyboard::KeyCode};
+use serde::{Deserialize, Serialize};
+
+use crate::consts::STICK_DEADZONE;
+
+/// An action the player can trigger with the keyboard or a gamepad.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+pub enum Action {
+    MoveLeft,
+    MoveRight,
+    Dash,
+    Pause,
+    Up,
+    Down,
+    Confirm,
+    Back,
+}
+
+impl Action {
+    /// All actions in the order they are listed on the controls screen.
+    pub const ALL: [Action; 8] = [
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::Dash,
+        Action::Pause,
+        Action::Up,
+        Action::Down,
+        Action::Confirm,
+        Action::Back,
+    ];
+
+    /// Actions the second player can rebind in local multiplayer.
+    pub const PLAYER_TWO: [Action; 3] = [Action::MoveLeft, Action::MoveRight, Action::Dash];
+
+    /// **Returns the human-readable name of the action.**
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::MoveLeft => "Move Left",
+            Action::MoveRight => "Move Right",
+            Action::Dash => "Dash",
+            Action::Pause => "Pause",
+            Action::Up => "Menu Up",
+            Action::Down => "Menu Down",
+            Action::Confirm => "Confirm",
+            Action::Back => "Back",
+        }
+    }
+}
+
+/// Declares the keys and gamepad buttons that can be bound, together with their stored names.
+macro_rules! named_inputs {
+    ($table:ident: $ty:ident { $($variant:ident),* $(,)? }) => {
+        const $table: &[($ty, &str)] = &[$(($ty::$variant, stringify!($variant))),*];
+    };
+}
+
+named_inputs!(NAMED_KEYS: KeyCode {
+    A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+    Key0, Key1, Key2, Key3, Key4, Key5, Key6, Key7, Key8, Key9,
+    Numpad0, Numpad1, Numpad2, Numpad3, Numpad4, Numpad5, Numpad6, Numpad7, Numpad8, Numpad9,
+    Left, Right, Up, Down, Space, Return, NumpadEnter, Escape, Tab, Back,
+    LShift, RShift, LControl, RControl, LAlt, RAlt,
+    Comma, Period, Slash, Semicolon, Apostrophe, Minus, Equals, LBracket, RBracket,
+});
+
+named_inputs!(NAMED_BUTTONS: Button {
+    South, East, North, West, C, Z,
+    LeftTrigger, LeftTrigger2, RightTrigger, RightTrigger2,
+    Select, Start, Mode, LeftThumb, RightThumb,
+    DPadUp, DPadDown, DPadLeft, DPadRight,
+});
+
+/// A keyboard key or a gamepad button bound to an action.
+///
+/// Bindings are stored as strings such as `"Key:Left"` or `"Button:South"`.
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Binding {
+    Key(KeyCode),
+    Button(Button),
+}
+
+impl Binding {
+    /// **Returns the name of the bound key or button.**
+    ///
+    /// ## Returns
+    /// The name used for display and storage, or `"?"` for inputs that cannot be bound.
+    pub fn name(&self) -> &'static str {
+        let name = match self {
+            Binding::Key(key) => NAMED_KEYS
+                .iter()
+                .find(|(named_key, _)| named_key == key)
+                .map(|(_, name)| *name),
+            Binding::Button(button) => NAMED_BUTTONS
+                .iter()
+                .find(|(named_button, _)| named_button == button)
+                .map(|(_, name)| *name),
+        };
+        name.unwrap_or("?")
+    }
+
+    /// **Checks whether the key or button can be bound and stored.**
+    pub fn is_supported(&self) -> bool {
+        self.name() != "?"
+    }
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl From<Binding> for String {
+    fn from(binding: Binding) -> Self {
+        match binding {
+            Binding::Key(_) => format!("Key:{}", binding.name()),
+            Binding::Button(_) => format!("Button:{}", binding.name()),
+        }
+    }
+}
+
+impl TryFrom<String> for Binding {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let parsed = match value.split_once(':') {
+            Some(("Key", name)) => NAMED_KEYS
+                .iter()
+                .find(|(_, key_name)| *key_name == name)
+                .map(|(key, _)| Binding::Key(*key)),
+            Some(("Button", name)) => NAMED_BUTTONS
+                .iter()
+                .find(|(_, button_name)| *button_name == name)
+                .map(|(button, _)| Binding::Button(*button)),
+            _ => None,
+        };
+        parsed.ok_or(format!("Unknown input binding: {}", value))
+    }
+}
+
+/// **Maps keyboard keys and gamepad buttons to actions.**
+///
+/// An action can have several bindings, and a key or button is bound to at most one action.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct InputBindings {
+    bindings: BTreeMap<Action, Vec<Binding>>,
+}
+
+impl InputBindings {
+    /// **Returns the actions bound to a keyboard key.**
+    pub fn actions_for_key(&self, key: KeyCode) -> Vec<Action> {
+        self.actions_for(Binding::Key(key))
+    }
+
+    /// **Returns the actions bound to a gamepad button.**
+    pub fn actions_for_button(&self, button: Button) -> Vec<Action> {
+        self.actions_for(Binding::Button(button))
+    }
+
+    fn actions_for(&self, binding: Binding) -> Vec<Action> {
+        self.bindings
+            .iter()
+            .filter(|(_, bindings)| bindings.contains(&binding))
+            .map(|(action, _)| *action)
+            .collect()
+    }
+
+    /// **Returns the bindings of an action.**
+    pub fn bindings(&self, action: Action) -> &[Binding] {
+        self.bindings.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// **Binds a key or button to an action.**
+    ///
+    /// ## Parameters
+    /// * `action`: the action to bind.
+    /// * `binding`: the new key or button.
+    ///
+    /// ## Behavior
+    /// * Replaces the bindings of the same kind (keyboard or gamepad) of the action, keeping the other kind.
+    /// * Removes the binding from any other action, so that one input never triggers two actions.
+    pub fn rebind(&mut self, action: Action, binding: Binding) {
+        for bindings in self.bindings.values_mut() {
+            bindings.retain(|bound| *bound != binding);
+        }
+
+        let bindings = self.bindings.entry(action).or_default();
+        bindings.retain(|bound| {
+            matches!(
+                (bound, binding),
+                (Binding::Key(_), Binding::Button(_)) | (Binding::Button(_), Binding::Key(_))
+            )
+        });
+        bindings.insert(0, binding);
+    }
+
+    /// **Returns a text listing the bindings of an action, keyboard first.**
+    ///
+    /// ## Returns
+    /// Names of the bound inputs separated with "/", or `"-"` if the action is not bound.
+    pub fn describe(&self, action: Action) -> String {
+        let mut names: Vec<&str> = self
+            .bindings(action)
+            .iter()
+            .filter(|binding| matches!(binding, Binding::Key(_)))
+            .map(Binding::name)
+            .collect();
+        names.extend(
+            self.bindings(action)
+                .iter()
+                .filter(|binding| matches!(binding, Binding::Button(_)))
+                .map(Binding::name),
+        );
+
+        if names.is_empty() {
+            "-".to_string()
+        } else {
+            names.join("/")
+        }
+    }
+}
+
+impl InputBindings {
+    /// **Returns the default keyboard bindings of the second player in local multiplayer.**
+    ///
+    /// ## Behavior
+    /// The second player moves with the arrows and dashes with the right Control key. Gamepads are assigned to players in the order they were connected and use the first player's button bindings.
+    pub fn player_two() -> Self {
+        let bindings = BTreeMap::from([
+            (Action::MoveLeft, vec![Binding::Key(KeyCode::Left)]),
+            (Action::MoveRight, vec![Binding::Key(KeyCode::Right)]),
+            (Action::Dash, vec![Binding::Key(KeyCode::RControl)]),
+        ]);
+        Self { bindings }
+    }
+}
+
+impl Default for InputBindings {
+    /// Arrows and WASD on the keyboard, D-pad and face buttons on a gamepad.
+    fn default() -> Self {
+        use Binding::{Button as B, Key as K};
+
+        let bindings = BTreeMap::from([
+            (
+                Action::MoveLeft,
+                vec![K(KeyCode::Left), K(KeyCode::A), B(Button::DPadLeft)],
+            ),
+            (
+                Action::MoveRight,
+                vec![K(KeyCode::Right), K(KeyCode::D), B(Button::DPadRight)],
+            ),
+            (
+                Action::Dash,
+                vec![
+                    K(KeyCode::LShift),
+                    K(KeyCode::RShift),
+                    B(Button::West),
+                    B(Button::RightTrigger),
+                ],
+            ),
+            (
+                Action::Pause,
+                vec![K(KeyCode::Space), K(KeyCode::P), B(Button::Start)],
+            ),
+            (
+                Action::Up,
+                vec![K(KeyCode::Up), K(KeyCode::W), B(Button::DPadUp)],
+            ),
+            (
+                Action::Down,
+                vec![K(KeyCode::Down), K(KeyCode::S), B(Button::DPadDown)],
+            ),
+            (
+                Action::Confirm,
+                vec![
+                    K(KeyCode::Return),
+                    K(KeyCode::NumpadEnter),
+                    B(Button::South),
+                ],
+            ),
+            (Action::Back, vec![K(KeyCode::Escape), B(Button::East)]),
+        ]);
+        Self { bindings }
+    }
+}
+
+/// The way the player steers during the game.
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub enum ControlScheme {
+    /// The player is moved with the keyboard or a gamepad only.
+    #[default]
+    Standard,
+    /// The player also moves toward the horizontal position of the mouse pointer.
+    MouseFollow,
+}
+
+impl ControlScheme {
+    /// **Returns the human-readable name of the control scheme.**
+    pub fn label(&self) -> &'static str {
+        match self {
+            ControlScheme::Standard => "Keyboard/Gamepad",
+            ControlScheme::MouseFollow => "Mouse Follow",
+        }
+    }
+
+    /// **Returns the other control scheme.**
+    pub fn toggled(&self) -> Self {
+        match self {
+            ControlScheme::Standard => ControlScheme::MouseFollow,
+            ControlScheme::MouseFollow => ControlScheme::Standard,
+        }
+    }
+}
+
+/// **Calculates how far the player moves toward the mouse pointer in one update.**
+///
+/// ## Parameters
+/// * `player_x`: the x-coordinate of the player's left edge.
+/// * `player_width`: width of the player.
+/// * `mouse_x`: the x-coordinate of the mouse pointer.
+/// * `max_distance`: the largest distance the player may cover in this update.
+///
+/// ## Returns
+/// The horizontal offset that brings the player's center closer to the pointer, limited to `max_distance` in either direction.
+pub fn mouse_follow_dx(player_x: f32, player_width: f32, mouse_x: f32, max_distance: f32) -> f32 {
+    let distance = mouse_x - (player_x + player_width / 2.0);
+    distance.clamp(-max_distance, max_distance)
+}
+
+/// The kind of device a digital input comes from.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InputSource {
+    Keyboard,
+    Gamepad,
+}
+
+impl InputSource {
+    /// Number of input sources.
+    const COUNT: usize = 2;
+}
+
+/// **Horizontal movement requested by the player with any input device.**
+///
+/// ## Fields
+/// * `left_held`: whether a binding of `MoveLeft` is held down, for each input source.
+/// * `right_held`: whether a binding of `MoveRight` is held down, for each input source.
+/// * `stick_x`: position of the analog stick on the horizontal axis, from -1.0 to 1.0.
+///
+/// Keyboard keys, D-pad buttons and analog sticks all feed this state, so the player is moved by the same code for every device.
+/// Each input source is tracked on its own, so releasing a key does not stop a move still held on the gamepad.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct MovementInput {
+    left_held: [bool; InputSource::COUNT],
+    right_held: [bool; InputSource::COUNT],
+    stick_x: f32,
+}
+
+impl MovementInput {
+    /// **Registers a pressed movement action.**
+    ///
+    /// ## Parameters
+    /// * `action`: the pressed action; actions other than `MoveLeft` and `MoveRight` are ignored.
+    /// * `source`: the device the action was pressed on.
+    pub fn press(&mut self, action: Action, source: InputSource) {
+        self.set_held(action, source, true);
+    }
+
+    /// **Registers a released movement action.**
+    ///
+    /// ## Parameters
+    /// * `action`: the released action; actions other than `MoveLeft` and `MoveRight` are ignored.
+    /// * `source`: the device the action was released on.
+    pub fn release(&mut self, action: Action, source: InputSource) {
+        self.set_held(action, source, false);
+    }
+
+    /// **Records whether a movement action is held down on an input source.**
+    fn set_held(&mut self, action: Action, source: InputSource, held: bool) {
+        match action {
+            Action::MoveLeft => self.left_held[source as usize] = held,
+            Action::MoveRight => self.right_held[source as usize] = held,
+            _ => (),
+        }
+    }
+
+    /// **Registers the position of the analog stick on the horizontal axis.**
+    ///
+    /// ## Parameters
+    /// `value`: the raw axis value reported by the gamepad, from -1.0 to 1.0.
+    ///
+    /// ## Behavior
+    /// Values inside `STICK_DEADZONE` are treated as a centered stick, and the rest of the range is rescaled to start from 0.0, so that movement is proportional to the tilt.
+    pub fn set_stick(&mut self, value: f32) {
+        let magnitude = value.abs().min(1.0);
+        self.stick_x = if magnitude <= STICK_DEADZONE {
+            0.0
+        } else {
+            value.signum() * (magnitude - STICK_DEADZONE) / (1.0 - STICK_DEADZONE)
+        };
+    }
+
+    /// **Releases all movement inputs.**
+    ///
+    /// ## Behavior
+    /// Used when an input device may have missed release events, e.g. when a gamepad is disconnected or the window loses focus.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// **Returns the requested movement direction and strength.**
+    ///
+    /// ## Returns
+    /// A value from -1.0 (full speed to the left) to 1.0 (full speed to the right).
+    /// Held digital inputs take precedence over the analog stick; holding both directions cancels out.
+    pub fn axis(&self) -> f32 {
+        let left_held = self.left_held.contains(&true);
+        let right_held = self.right_held.contains(&true);
+        match (left_held, right_held) {
+            (true, false) => -1.0,
+            (false, true) => 1.0,
+            (true, true) => 0.0,
+            (false, false) => self.stick_x,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_bindings_include_arrows_and_wasd() {
+        let bindings = InputBindings::default();
+        assert_eq!(
+            bindings.actions_for_key(KeyCode::Left),
+            vec![Action::MoveLeft]
+        );
+        assert_eq!(bindings.actions_for_key(KeyCode::A), vec![Action::MoveLeft]);
+        assert_eq!(
+            bindings.actions_for_key(KeyCode::D),
+            vec![Action::MoveRight]
+        );
+        assert_eq!(
+            bindings.actions_for_button(Button::South),
+            vec![Action::Confirm]
+        );
+        assert!(bindings.actions_for_key(KeyCode::F1).is_empty());
+    }
+
+    #[test]
+    fn test_rebind_moves_binding_between_actions() {
+        let mut bindings = InputBindings::default();
+        bindings.rebind(Action::Dash, Binding::Key(KeyCode::A));
+
+        assert_eq!(bindings.actions_for_key(KeyCode::A), vec![Action::Dash]);
+        assert_eq!(bindings.describe(Action::MoveLeft), "Left/DPadLeft");
+        assert_eq!(bindings.describe(Action::Dash), "A/West/RightTrigger");
+    }
+
+    #[test]
+    fn test_bindings_round_trip_through_json() {
+        let mut bindings = InputBindings::default();
+        bindings.rebind(Action::Pause, Binding::Button(Button::Select));
+
+        let json = serde_json::to_string(&bindings).unwrap();
+        assert!(json.contains("\"Button:Select\""));
+        let restored: InputBindings = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, bindings);
+    }
+
+    #[test]
+    fn test_unknown_binding_is_rejected() {
+        assert!(Binding::try_from("Key:Unknown".to_string()).is_err());
+        assert!(Binding::try_from("Left".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_stick_movement_is_proportional() {
+        let mut movement = MovementInput::default();
+        movement.set_stick(0.1);
+        assert_eq!(movement.axis(), 0.0);
+
+        movement.set_stick(-1.0);
+        assert_eq!(movement.axis(), -1.0);
+
+        movement.set_stick(0.6);
+        assert!((movement.axis() - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_digital_movement_overrides_stick() {
+        let mut movement = MovementInput::default();
+        movement.set_stick(0.6);
+        movement.press(Action::MoveLeft, InputSource::Keyboard);
+        assert_eq!(movement.axis(), -1.0);
+
+        movement.press(Action::MoveRight, InputSource::Keyboard);
+        assert_eq!(movement.axis(), 0.0);
+
+        movement.release(Action::MoveLeft, InputSource::Keyboard);
+        movement.release(Action::MoveRight, InputSource::Keyboard);
+        assert!(movement.axis() > 0.0);
+
+        movement.clear();
+        assert_eq!(movement.axis(), 0.0);
+    }
+
+    #[test]
+    fn test_input_sources_are_held_separately() {
+        let mut movement = MovementInput::default();
+        movement.press(Action::MoveRight, InputSource::Keyboard);
+        movement.press(Action::MoveRight, InputSource::Gamepad);
+        movement.release(Action::MoveRight, InputSource::Keyboard);
+        assert_eq!(movement.axis(), 1.0);
+
+        movement.release(Action::MoveRight, InputSource::Gamepad);
+        assert_eq!(movement.axis(), 0.0);
+    }
+
+    #[test]
+    fn test_mouse_follow_speed_is_capped() {
+        assert_eq!(mouse_follow_dx(100.0, 50.0, 900.0, 10.0), 10.0);
+        assert_eq!(mouse_follow_dx(500.0, 50.0, 0.0, 10.0), -10.0);
+        assert_eq!(mouse_follow_dx(100.0, 50.0, 128.0, 10.0), 3.0);
+    }
+}
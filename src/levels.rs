@@ -1,30 +1,111 @@
+use crate::{
+    difficulty::{Curve, DifficultyCurve, DifficultyPreset},
+    objectives::Objective,
+};
+
 #[derive(Clone)]
-/// Represents a game level with an image template and falling speed.
+/// Represents a game level with a name shown on its intro card, an image template, a falling speed, a difficulty curve, an objective,
+/// and the level scores needed for the second and third star (`star_scores`); completing the level earns the first.
 pub struct Level {
+    pub name: &'static str,
     pub image_template: &'static str,
     pub fall_speed: f32,
+    pub difficulty: DifficultyCurve,
+    pub objective: Objective,
+    pub star_scores: [i32; 2],
+}
+
+impl Level {
+    /// **Returns the stars earned by completing the level with a score.**
+    ///
+    /// ## Parameters
+    /// * `score`: the level score.
+    /// * `preset`: the difficulty preset of the run; the star scores are scaled by its score multiplier.
+    ///
+    /// ## Returns
+    /// 1 star for completing the level, plus one for each star score reached.
+    pub fn stars(&self, score: i32, preset: DifficultyPreset) -> u8 {
+        1 + self
+            .star_scores
+            .iter()
+            .filter(|points| score >= preset.scaled_score(**points))
+            .count() as u8
+    }
 }
 
 /// Returns a vector of predefined game levels.
 ///
-/// Each level is characterized by an image template and a falling speed.
+/// Each level is characterized by an image template, a falling speed at its start,
+/// how the difficulty builds up toward its end, and what the player has to do to complete it.
 pub fn get_levels() -> Vec<Level> {
     vec![
         Level {
+            name: "Brick Alley",
             image_template: "/Level1",
             fall_speed: 2.5,
+            difficulty: DifficultyCurve {
+                spawn_interval: Curve::Linear {
+                    start: 1.0,
+                    end: 0.85,
+                },
+                fall_speed: Curve::Linear {
+                    start: 1.0,
+                    end: 1.2,
+                },
+                bad_ratio: Curve::Constant(0.2),
+            },
+            objective: Objective::Survive,
+            star_scores: [150, 250],
         },
         Level {
+            name: "Blue Planks",
             image_template: "/Level2",
             fall_speed: 3.0,
+            difficulty: DifficultyCurve {
+                spawn_interval: Curve::Stepped(vec![(0.0, 1.0), (15.0, 0.9), (30.0, 0.8)]),
+                fall_speed: Curve::Stepped(vec![(0.0, 1.0), (15.0, 1.1), (30.0, 1.25)]),
+                bad_ratio: Curve::Linear {
+                    start: 0.2,
+                    end: 0.25,
+                },
+            },
+            objective: Objective::NoLivesLost,
+            star_scores: [175, 275],
         },
         Level {
+            name: "Macaron Shop",
             image_template: "/Level3",
             fall_speed: 3.5,
+            difficulty: DifficultyCurve {
+                spawn_interval: Curve::Keyframes(vec![(0.0, 1.0), (20.0, 0.85), (35.0, 0.7)]),
+                fall_speed: Curve::Keyframes(vec![(0.0, 1.0), (20.0, 1.1), (35.0, 1.3)]),
+                bad_ratio: Curve::Linear {
+                    start: 0.2,
+                    end: 0.3,
+                },
+            },
+            objective: Objective::CatchHighValue(2),
+            star_scores: [200, 300],
         },
         Level {
+            name: "Bamboo Grove",
             image_template: "/Level4",
             fall_speed: 4.0,
+            difficulty: DifficultyCurve {
+                spawn_interval: Curve::Linear {
+                    start: 0.9,
+                    end: 0.65,
+                },
+                fall_speed: Curve::Keyframes(vec![
+                    (0.0, 1.0),
+                    (10.0, 1.1),
+                    (30.0, 1.25),
+                    (40.0, 1.4),
+                ]),
+                bad_ratio: Curve::Keyframes(vec![(0.0, 0.2), (25.0, 0.3), (40.0, 0.35)]),
+            },
+            objective: Objective::ReachScore(200),
+            star_scores: [250, 350],
         },
     ]
 }
@@ -1,12 +1,34 @@
+pub mod achievements;
+pub mod adaptive;
+pub mod agent;
+pub mod analysis;
 pub mod buttons;
 pub mod consts;
+pub mod continues;
+pub mod difficulty;
+pub mod env;
 pub mod errors;
 pub mod gamestate;
+pub mod highscores;
+pub mod input;
 pub mod levels;
 pub mod modes;
+pub mod navigation;
+pub mod net;
+pub mod objectives;
 pub mod objects;
 pub mod player;
+pub mod profiles;
+pub mod progress;
 pub mod resources;
+pub mod savegame;
+pub mod settings;
+pub mod shop;
+pub mod simulation;
 pub mod sound;
+pub mod spawn;
+pub mod stats;
+pub mod storage;
+pub mod summary;
 pub mod ui;
 pub mod utils;
@@ -1,10 +1,99 @@
-#[derive(PartialEq, Debug)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum GameMode {
     Menu,
+    Multiplayer,
+    HostLobby,
+    JoinLobby,
     LevelSelection,
+    LevelIntro,
     Playing,
     HowToPlay,
+    Controls,
+    Stats,
+    Achievements,
+    Profiles,
+    ProfileName,
+    LevelSummary,
     NextLevel,
+    LevelFailed,
     GameOver,
     Victory,
+    VersusResult,
+}
+
+/// How many players take part in a game and how they play together.
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub enum PlayMode {
+    #[default]
+    Single,
+    /// Two players on the same screen compete for the same falling objects.
+    Versus,
+    /// Two players on the same screen share their lives and score.
+    Coop,
+}
+
+impl PlayMode {
+    /// **Returns the number of players taking part in a game of this mode.**
+    pub fn player_count(&self) -> usize {
+        match self {
+            PlayMode::Single => 1,
+            PlayMode::Versus | PlayMode::Coop => 2,
+        }
+    }
+
+    /// **Returns whether the players of this mode share a single pool of lives.**
+    pub fn shares_lives(&self) -> bool {
+        *self == PlayMode::Coop
+    }
+}
+
+/// **Determines the winner of a versus game.**
+///
+/// ## Parameters
+/// `results`: the remaining lives and the level score of each player.
+///
+/// ## Returns
+/// The index of the winning player, or `None` if the game is a draw.
+///
+/// ## Behavior
+/// Players who still have lives rank above eliminated players; among them, the higher score wins.
+pub fn versus_winner(results: &[(u8, i32)]) -> Option<usize> {
+    let rank = |(lives, score): (u8, i32)| (lives > 0, score);
+
+    let best = results.iter().map(|result| rank(*result)).max()?;
+    let mut leaders = results
+        .iter()
+        .enumerate()
+        .filter(|(_, result)| rank(**result) == best);
+
+    match (leaders.next(), leaders.next()) {
+        (Some((index, _)), None) => Some(index),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_coop_shares_lives() {
+        assert!(PlayMode::Coop.shares_lives());
+        assert!(!PlayMode::Versus.shares_lives());
+        assert_eq!(PlayMode::Coop.player_count(), 2);
+    }
+
+    #[test]
+    fn test_versus_winner_by_score() {
+        assert_eq!(versus_winner(&[(3, 40), (1, 55)]), Some(1));
+        assert_eq!(versus_winner(&[(2, 30), (5, 30)]), None);
+    }
+
+    #[test]
+    fn test_versus_survivor_beats_eliminated_player() {
+        assert_eq!(versus_winner(&[(0, 120), (1, 15)]), Some(1));
+        assert_eq!(versus_winner(&[(0, 120), (0, 15)]), Some(0));
+    }
 }
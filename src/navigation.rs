@@ -0,0 +1,158 @@
+use crate::{input::Action, modes::GameMode};
+
+/// A navigation command issued on a menu screen by the keyboard or a gamepad.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MenuCommand {
+    Up,
+    Down,
+    Confirm,
+    Back,
+}
+
+impl MenuCommand {
+    /// **Maps an input action to a menu command.**
+    ///
+    /// ## Parameters
+    /// `action`: the triggered action.
+    ///
+    /// ## Returns
+    /// The matching `MenuCommand`, or `None` if the action is not used for menu navigation.
+    pub fn from_action(action: Action) -> Option<Self> {
+        match action {
+            Action::Up => Some(MenuCommand::Up),
+            Action::Down => Some(MenuCommand::Down),
+            Action::Confirm => Some(MenuCommand::Confirm),
+            Action::Back => Some(MenuCommand::Back),
+            _ => None,
+        }
+    }
+}
+
+/// **Tracks which button is focused on the current menu screen.**
+///
+/// ## Fields
+/// * `mode`: the screen the focus belongs to; a different screen resets the focus.
+/// * `focused`: index of the focused button in the screen's button list.
+/// * `activated`: whether the focused button was activated and not yet handled.
+pub struct MenuFocus {
+    mode: Option<GameMode>,
+    focused: usize,
+    activated: bool,
+}
+
+impl MenuFocus {
+    /// **Creates a focus tracker not attached to any screen.**
+    pub fn new() -> Self {
+        Self {
+            mode: None,
+            focused: 0,
+            activated: false,
+        }
+    }
+
+    /// **Attaches the focus to a screen.**
+    ///
+    /// ## Parameters
+    /// * `mode`: the screen being shown.
+    /// * `default_focus`: index of the button focused when the screen is entered.
+    ///
+    /// ## Behavior
+    /// The focus is only reset when the screen changes, so it is kept between frames.
+    pub fn sync(&mut self, mode: GameMode, default_focus: usize) {
+        if self.mode != Some(mode) {
+            self.mode = Some(mode);
+            self.focused = default_focus;
+            self.activated = false;
+        }
+    }
+
+    /// **Returns the index of the focused button.**
+    pub fn focused(&self) -> usize {
+        self.focused
+    }
+
+    /// **Focuses the button with the given index.**
+    pub fn set_focused(&mut self, index: usize) {
+        self.focused = index;
+    }
+
+    /// **Moves the focus to the previous button, wrapping around at the top.**
+    ///
+    /// ## Parameters
+    /// `len`: number of focusable buttons on the screen.
+    pub fn focus_previous(&mut self, len: usize) {
+        if len > 0 {
+            self.focused = (self.focused + len - 1) % len;
+        }
+    }
+
+    /// **Moves the focus to the next button, wrapping around at the bottom.**
+    ///
+    /// ## Parameters
+    /// `len`: number of focusable buttons on the screen.
+    pub fn focus_next(&mut self, len: usize) {
+        if len > 0 {
+            self.focused = (self.focused + 1) % len;
+        }
+    }
+
+    /// **Marks the focused button as activated.**
+    pub fn activate(&mut self) {
+        self.activated = true;
+    }
+
+    /// **Takes a pending activation.**
+    ///
+    /// ## Returns
+    /// The index of the activated button, or `None` if nothing was activated since the last call.
+    pub fn take_activation(&mut self) -> Option<usize> {
+        if self.activated {
+            self.activated = false;
+            Some(self.focused)
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for MenuFocus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_focus_wraps_around() {
+        let mut focus = MenuFocus::new();
+        focus.sync(GameMode::Menu, 0);
+        focus.focus_previous(4);
+        assert_eq!(focus.focused(), 3);
+        focus.focus_next(4);
+        assert_eq!(focus.focused(), 0);
+    }
+
+    #[test]
+    fn test_focus_resets_on_screen_change() {
+        let mut focus = MenuFocus::new();
+        focus.sync(GameMode::Menu, 0);
+        focus.focus_next(4);
+        focus.sync(GameMode::Menu, 0);
+        assert_eq!(focus.focused(), 1);
+
+        focus.sync(GameMode::LevelSelection, 2);
+        assert_eq!(focus.focused(), 2);
+    }
+
+    #[test]
+    fn test_activation_is_taken_once() {
+        let mut focus = MenuFocus::new();
+        focus.sync(GameMode::GameOver, 0);
+        focus.activate();
+        assert_eq!(focus.take_activation(), Some(0));
+        assert_eq!(focus.take_activation(), None);
+    }
+}
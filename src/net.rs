@@ -0,0 +1,469 @@
+use std::{
+    collections::VecDeque,
+    io::{BufRead, BufReader, ErrorKind, Write},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    consts::{CONNECT_TIMEOUT_MILLIS, DEFAULT_PORT, INPUT_DELAY_TICKS},
+    difficulty::DifficultyPreset,
+    errors::DodgerError,
+    simulation::PlayerInput,
+};
+
+/// Version of the network protocol; both game instances must use the same one.
+pub const PROTOCOL_VERSION: u32 = 2;
+
+/// A message exchanged between two game instances, sent as one JSON object per line.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NetMessage {
+    /// Sent by the host when a player joins: the level to play, the seed of its simulation and the difficulty preset.
+    Hello {
+        version: u32,
+        seed: u64,
+        level: usize,
+        #[serde(default)]
+        difficulty: DifficultyPreset,
+    },
+    /// The sender's input for its next simulation tick.
+    Input { input: PlayerInput },
+    /// The sender leaves the game.
+    Leave,
+}
+
+/// **A connection to another game instance.**
+///
+/// ## Fields
+/// * `stream`: the TCP stream messages are written to.
+/// * `incoming`: messages read from the stream by a background thread.
+///
+/// ## Behavior
+/// Reading happens on a background thread, so polling for messages never blocks the game loop.
+pub struct Connection {
+    stream: TcpStream,
+    incoming: Receiver<Result<NetMessage, DodgerError>>,
+}
+
+impl Connection {
+    /// **Wraps a connected TCP stream.**
+    ///
+    /// ## Parameters
+    /// `stream`: the connected stream.
+    ///
+    /// ## Returns
+    /// A result containing the connection, or a `DodgerError` if the stream cannot be configured.
+    pub fn new(stream: TcpStream) -> Result<Self, DodgerError> {
+        stream
+            .set_nodelay(true)
+            .map_err(|err| DodgerError::Network(err.to_string()))?;
+        let reader = stream
+            .try_clone()
+            .map_err(|err| DodgerError::Network(err.to_string()))?;
+
+        let (sender, incoming) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(reader).lines() {
+                let message = match line {
+                    Ok(line) => serde_json::from_str(&line)
+                        .map_err(|err| DodgerError::Network(err.to_string())),
+                    Err(_) => Err(DodgerError::Disconnected),
+                };
+                let failed = message.is_err();
+                if sender.send(message).is_err() || failed {
+                    return;
+                }
+            }
+            let _ = sender.send(Err(DodgerError::Disconnected));
+        });
+
+        Ok(Self { stream, incoming })
+    }
+
+    /// **Connects to a hosting game instance.**
+    ///
+    /// ## Parameters
+    /// `address`: host name or IP address with a port, e.g. `127.0.0.1:7878`.
+    ///
+    /// ## Returns
+    /// A result containing the connection, or a `DodgerError` if the address is invalid or nobody answers within `CONNECT_TIMEOUT_MILLIS`.
+    pub fn connect(address: &str) -> Result<Self, DodgerError> {
+        let addresses: Vec<SocketAddr> = address
+            .to_socket_addrs()
+            .map_err(|err| DodgerError::Network(format!("{}: {}", address, err)))?
+            .collect();
+
+        let mut last_error = DodgerError::Network(format!("{}: no address found", address));
+        for socket_address in addresses {
+            let timeout = Duration::from_millis(CONNECT_TIMEOUT_MILLIS);
+            match TcpStream::connect_timeout(&socket_address, timeout) {
+                Ok(stream) => return Self::new(stream),
+                Err(err) => last_error = DodgerError::Network(format!("{}: {}", address, err)),
+            }
+        }
+        Err(last_error)
+    }
+
+    /// **Sends a message.**
+    ///
+    /// ## Returns
+    /// `Ok(())` if the message is sent, or `DodgerError::Disconnected` if the connection is closed.
+    pub fn send(&mut self, message: &NetMessage) -> Result<(), DodgerError> {
+        let mut line =
+            serde_json::to_string(message).map_err(|err| DodgerError::Network(err.to_string()))?;
+        line.push('\n');
+        self.stream
+            .write_all(line.as_bytes())
+            .map_err(|_| DodgerError::Disconnected)
+    }
+
+    /// **Takes the messages received since the last call.**
+    ///
+    /// ## Returns
+    /// A result containing the received messages in order, or a `DodgerError` if the connection was closed or a message cannot be parsed.
+    pub fn poll(&mut self) -> Result<Vec<NetMessage>, DodgerError> {
+        let mut messages = Vec::new();
+        loop {
+            match self.incoming.try_recv() {
+                Ok(message) => messages.push(message?),
+                Err(TryRecvError::Empty) => return Ok(messages),
+                Err(TryRecvError::Disconnected) => return Err(DodgerError::Disconnected),
+            }
+        }
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        let _ = self.stream.shutdown(std::net::Shutdown::Both);
+    }
+}
+
+/// **A connection to a hosting game instance being opened.**
+///
+/// ## Behavior
+/// Connecting happens on a background thread, so an unreachable host never blocks the game loop.
+pub struct Connecting {
+    result: Receiver<Result<Connection, DodgerError>>,
+}
+
+impl Connecting {
+    /// **Starts connecting to a hosting game instance.**
+    ///
+    /// ## Parameters
+    /// `address`: host name or IP address with a port, e.g. `127.0.0.1:7878`.
+    pub fn start(address: &str) -> Self {
+        let address = address.to_string();
+        let (sender, result) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = sender.send(Connection::connect(&address));
+        });
+        Self { result }
+    }
+
+    /// **Takes the connection once it is open.**
+    ///
+    /// ## Returns
+    /// A result containing the connection, `None` while still connecting, or a `DodgerError` if connecting failed.
+    pub fn poll(&self) -> Result<Option<Connection>, DodgerError> {
+        match self.result.try_recv() {
+            Ok(connection) => connection.map(Some),
+            Err(TryRecvError::Empty) => Ok(None),
+            Err(TryRecvError::Disconnected) => Err(DodgerError::Disconnected),
+        }
+    }
+}
+
+/// **Waits for another game instance to join.**
+pub struct Host {
+    listener: TcpListener,
+}
+
+impl Host {
+    /// **Starts listening for a player on a port.**
+    ///
+    /// ## Parameters
+    /// `port`: the TCP port to listen on; `0` picks a free port.
+    ///
+    /// ## Returns
+    /// A result containing the host, or a `DodgerError` if the port cannot be used.
+    pub fn bind(port: u16) -> Result<Self, DodgerError> {
+        let listener = TcpListener::bind(("0.0.0.0", port))
+            .map_err(|err| DodgerError::Network(format!("port {}: {}", port, err)))?;
+        listener
+            .set_nonblocking(true)
+            .map_err(|err| DodgerError::Network(err.to_string()))?;
+        Ok(Self { listener })
+    }
+
+    /// **Returns the port the host listens on.**
+    pub fn port(&self) -> Result<u16, DodgerError> {
+        self.listener
+            .local_addr()
+            .map(|address| address.port())
+            .map_err(|err| DodgerError::Network(err.to_string()))
+    }
+
+    /// **Accepts a waiting player, if any.**
+    ///
+    /// ## Returns
+    /// A result containing the connection to the player who joined, `None` if nobody is waiting, or a `DodgerError` if accepting fails.
+    pub fn accept(&self) -> Result<Option<Connection>, DodgerError> {
+        match self.listener.accept() {
+            Ok((stream, _)) => {
+                stream
+                    .set_nonblocking(false)
+                    .map_err(|err| DodgerError::Network(err.to_string()))?;
+                Connection::new(stream).map(Some)
+            }
+            Err(err) if err.kind() == ErrorKind::WouldBlock => Ok(None),
+            Err(err) => Err(DodgerError::Network(err.to_string())),
+        }
+    }
+}
+
+/// **Pairs the inputs of the local and the remote player tick by tick.**
+///
+/// ## Fields
+/// * `local`: local inputs sent to the other instance and not yet simulated.
+/// * `remote`: inputs received from the other instance and not yet simulated.
+/// * `delay`: number of ticks between capturing a local input and simulating it.
+///
+/// ## Behavior
+/// Both instances start with `delay` idle inputs for each player, so a tick is only simulated once both inputs for it are known, and the inputs have `delay` ticks to travel over the network.
+pub struct Lockstep {
+    local: VecDeque<PlayerInput>,
+    remote: VecDeque<PlayerInput>,
+    delay: usize,
+}
+
+impl Lockstep {
+    /// **Creates a lockstep with the given input delay.**
+    pub fn new(delay: usize) -> Self {
+        Self {
+            local: VecDeque::from(vec![PlayerInput::default(); delay]),
+            remote: VecDeque::from(vec![PlayerInput::default(); delay]),
+            delay,
+        }
+    }
+
+    /// **Checks whether a new local input should be captured and sent.**
+    ///
+    /// ## Behavior
+    /// At most `delay` local inputs wait for their tick, so the local instance never runs ahead of the remote one by more than the delay.
+    pub fn needs_local_input(&self) -> bool {
+        self.local.len() < self.delay.max(1)
+    }
+
+    /// **Queues an input of the local player.**
+    pub fn push_local(&mut self, input: PlayerInput) {
+        self.local.push_back(input);
+    }
+
+    /// **Queues an input received from the remote player.**
+    pub fn push_remote(&mut self, input: PlayerInput) {
+        self.remote.push_back(input);
+    }
+
+    /// **Takes the inputs of the next tick.**
+    ///
+    /// ## Returns
+    /// The local and the remote input of the next tick, or `None` if the remote input has not arrived yet.
+    pub fn next_inputs(&mut self) -> Option<(PlayerInput, PlayerInput)> {
+        if self.local.is_empty() || self.remote.is_empty() {
+            return None;
+        }
+        Some((self.local.pop_front()?, self.remote.pop_front()?))
+    }
+}
+
+impl Default for Lockstep {
+    fn default() -> Self {
+        Self::new(INPUT_DELAY_TICKS)
+    }
+}
+
+/// **A versus game played against another game instance.**
+///
+/// ## Fields
+/// * `connection`: the connection to the other instance.
+/// * `lockstep`: the inputs waiting to be simulated.
+/// * `local_player`: index of the local player in the world (`0` for the host, `1` for the guest).
+pub struct NetGame {
+    pub connection: Connection,
+    pub lockstep: Lockstep,
+    pub local_player: usize,
+}
+
+impl NetGame {
+    /// **Creates a networked game.**
+    ///
+    /// ## Parameters
+    /// * `connection`: the connection to the other instance.
+    /// * `local_player`: index of the local player in the world.
+    pub fn new(connection: Connection, local_player: usize) -> Self {
+        Self {
+            connection,
+            lockstep: Lockstep::default(),
+            local_player,
+        }
+    }
+
+    /// **Exchanges inputs with the other instance and returns the inputs of the next tick.**
+    ///
+    /// ## Parameters
+    /// `local_input`: the current input of the local player.
+    ///
+    /// ## Returns
+    /// * `Ok(Some(inputs))`: the inputs of both players, ordered by player index.
+    /// * `Ok(None)`: the other instance's input has not arrived yet; the tick must wait.
+    /// * A `DodgerError` if the other player left or the connection was lost.
+    pub fn next_inputs(
+        &mut self,
+        local_input: PlayerInput,
+    ) -> Result<Option<Vec<PlayerInput>>, DodgerError> {
+        for message in self.connection.poll()? {
+            match message {
+                NetMessage::Input { input } => self.lockstep.push_remote(input),
+                NetMessage::Leave => return Err(DodgerError::Disconnected),
+                NetMessage::Hello { .. } => (),
+            }
+        }
+
+        if self.lockstep.needs_local_input() {
+            self.connection
+                .send(&NetMessage::Input { input: local_input })?;
+            self.lockstep.push_local(local_input);
+        }
+
+        Ok(self.lockstep.next_inputs().map(|(local, remote)| {
+            if self.local_player == 0 {
+                vec![local, remote]
+            } else {
+                vec![remote, local]
+            }
+        }))
+    }
+
+    /// **Tells the other instance that the local player leaves.**
+    pub fn leave(&mut self) {
+        let _ = self.connection.send(&NetMessage::Leave);
+    }
+}
+
+/// **The state of the host and join screens.**
+///
+/// ## Fields
+/// * `host`: the host waiting for a player, while hosting.
+/// * `connecting`: the connection to a host being opened, while connecting.
+/// * `pending`: the connection to a host, while waiting for the game to start.
+/// * `address`: the address of the host typed on the join screen.
+/// * `status`: the last connection error or progress message shown to the player.
+pub struct Lobby {
+    pub host: Option<Host>,
+    pub connecting: Option<Connecting>,
+    pub pending: Option<Connection>,
+    pub address: String,
+    pub status: Option<String>,
+}
+
+impl Lobby {
+    /// **Stops hosting and closes a pending connection, abandoning a connection being opened.**
+    pub fn close(&mut self) {
+        self.host = None;
+        self.connecting = None;
+        self.pending = None;
+    }
+}
+
+impl Default for Lobby {
+    fn default() -> Self {
+        Self {
+            host: None,
+            connecting: None,
+            pending: None,
+            address: format!("127.0.0.1:{}", DEFAULT_PORT),
+            status: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn moving(axis: f32) -> PlayerInput {
+        PlayerInput { axis, dash: false }
+    }
+
+    #[test]
+    fn test_lockstep_waits_for_remote_input() {
+        let mut lockstep = Lockstep::new(1);
+        assert!(!lockstep.needs_local_input());
+        assert_eq!(
+            lockstep.next_inputs(),
+            Some((PlayerInput::default(), PlayerInput::default()))
+        );
+
+        assert!(lockstep.needs_local_input());
+        lockstep.push_local(moving(1.0));
+        assert!(!lockstep.needs_local_input());
+        assert_eq!(lockstep.next_inputs(), None);
+
+        lockstep.push_remote(moving(0.5));
+        assert_eq!(lockstep.next_inputs(), Some((moving(1.0), moving(0.5))));
+    }
+
+    #[test]
+    fn test_games_exchange_inputs_over_loopback() {
+        let host = Host::bind(0).unwrap();
+        let port = host.port().unwrap();
+        let connecting = Connecting::start(&format!("127.0.0.1:{}", port));
+        let host_connection = loop {
+            if let Some(connection) = host.accept().unwrap() {
+                break connection;
+            }
+            thread::sleep(Duration::from_millis(5));
+        };
+        let guest_connection = loop {
+            if let Some(connection) = connecting.poll().unwrap() {
+                break connection;
+            }
+            thread::sleep(Duration::from_millis(5));
+        };
+
+        let mut host_game = NetGame::new(host_connection, 0);
+        let mut guest_game = NetGame::new(guest_connection, 1);
+        let mut host_ticks = Vec::new();
+        let mut guest_ticks = Vec::new();
+        for _ in 0..2000 {
+            if let Some(inputs) = host_game.next_inputs(moving(1.0)).unwrap() {
+                host_ticks.push(inputs);
+            }
+            if let Some(inputs) = guest_game.next_inputs(moving(-1.0)).unwrap() {
+                guest_ticks.push(inputs);
+            }
+            if host_ticks.len() >= 10 && guest_ticks.len() >= 10 {
+                break;
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        assert!(host_ticks.len() >= 10 && guest_ticks.len() >= 10);
+        let ticks = host_ticks.len().min(guest_ticks.len());
+        assert_eq!(host_ticks[..ticks], guest_ticks[..ticks]);
+        assert_eq!(host_ticks[ticks - 1], vec![moving(1.0), moving(-1.0)]);
+
+        guest_game.leave();
+        let disconnected = (0..2000).find_map(|_| {
+            thread::sleep(Duration::from_millis(1));
+            host_game.next_inputs(moving(0.0)).err()
+        });
+        assert!(matches!(disconnected, Some(DodgerError::Disconnected)));
+    }
+}
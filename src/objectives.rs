@@ -0,0 +1,161 @@
+use crate::{difficulty::DifficultyPreset, simulation::World};
+
+/// What the player has to do to complete a level.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Objective {
+    /// Keep at least one life until the time is up.
+    Survive,
+    /// Reach a level score; the target is scaled by the preset's score multiplier.
+    ReachScore(i32),
+    /// Catch a number of `High` value objects.
+    CatchHighValue(u32),
+    /// Finish the level without losing a single life.
+    NoLivesLost,
+}
+
+/// How far the player is with the level's objective.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ObjectiveStatus {
+    /// The objective can still be completed or failed.
+    InProgress,
+    /// The objective is met; the level is won once the time is up.
+    Completed,
+    /// The objective can no longer be met.
+    Failed,
+}
+
+impl Objective {
+    /// **Returns the objective as shown on the level intro card.**
+    ///
+    /// ## Parameters
+    /// `preset`: the difficulty preset of the run, which scales score targets.
+    pub fn description(&self, preset: DifficultyPreset) -> String {
+        match self {
+            Objective::Survive => "Survive until the time runs out".to_string(),
+            Objective::ReachScore(target) => {
+                format!("Score {} points", preset.scaled_score(*target))
+            }
+            Objective::CatchHighValue(count) => format!("Catch {} high-value objects", count),
+            Objective::NoLivesLost => "Don't lose a single life".to_string(),
+        }
+    }
+
+    /// **Returns a short summary of the progress towards the objective, shown while playing.**
+    pub fn progress(&self, world: &World) -> String {
+        match self {
+            Objective::Survive => "Goal: survive".to_string(),
+            Objective::ReachScore(target) => format!(
+                "Goal: {}/{} points",
+                world.level_score(),
+                world.preset.scaled_score(*target)
+            ),
+            Objective::CatchHighValue(count) => {
+                format!("Goal: {}/{} high-value", world.high_value_caught(), count)
+            }
+            Objective::NoLivesLost => "Goal: no lives lost".to_string(),
+        }
+    }
+
+    /// **Evaluates the objective in a world.**
+    ///
+    /// ## Parameters
+    /// `world`: the level being played.
+    ///
+    /// ## Returns
+    /// The status of the objective.
+    ///
+    /// ## Behavior
+    /// * Losing every life fails any objective.
+    /// * Score and catch targets are completed as soon as they are reached, and failed if the time is up before.
+    /// * `Survive` and `NoLivesLost` are only completed when the time is up; `NoLivesLost` fails on the first life lost.
+    pub fn status(&self, world: &World) -> ObjectiveStatus {
+        if world.is_lost() {
+            return ObjectiveStatus::Failed;
+        }
+        let met = match self {
+            Objective::Survive => world.is_time_up(),
+            Objective::ReachScore(target) => {
+                world.level_score() >= world.preset.scaled_score(*target)
+            }
+            Objective::CatchHighValue(count) => world.high_value_caught() >= *count,
+            Objective::NoLivesLost if world.any_life_lost() => return ObjectiveStatus::Failed,
+            Objective::NoLivesLost => world.is_time_up(),
+        };
+        if met {
+            ObjectiveStatus::Completed
+        } else if world.is_time_up() {
+            ObjectiveStatus::Failed
+        } else {
+            ObjectiveStatus::InProgress
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        agent::{Agent, HeuristicBot, IdleBot},
+        levels::get_levels,
+        modes::PlayMode,
+        simulation::SpriteSizes,
+    };
+
+    fn new_world(level_index: usize, seed: u64) -> World {
+        World::new(
+            &get_levels()[level_index],
+            PlayMode::Single,
+            DifficultyPreset::Normal,
+            SpriteSizes::default(),
+            seed,
+        )
+        .unwrap()
+    }
+
+    fn play(level_index: usize, agent: &mut dyn Agent, seed: u64) -> World {
+        let mut world = new_world(level_index, seed);
+        while !world.is_time_up() && !world.is_lost() {
+            let input = agent.act(&world, 0);
+            world.step(&[input]);
+        }
+        world
+    }
+
+    #[test]
+    fn test_bot_completes_every_level_objective() {
+        for (i, level) in get_levels().iter().enumerate() {
+            let world = play(i, &mut HeuristicBot, 7);
+            assert_eq!(
+                level.objective.status(&world),
+                ObjectiveStatus::Completed,
+                "level {}: {}",
+                i + 1,
+                level.objective.progress(&world)
+            );
+        }
+    }
+
+    #[test]
+    fn test_objectives_fail_when_out_of_reach() {
+        let fresh = new_world(0, 2);
+        assert_eq!(
+            Objective::ReachScore(100).status(&fresh),
+            ObjectiveStatus::InProgress
+        );
+        assert_eq!(
+            Objective::Survive.status(&fresh),
+            ObjectiveStatus::InProgress
+        );
+
+        let world = play(1, &mut IdleBot, 2);
+        assert!(world.any_life_lost());
+        assert_eq!(
+            Objective::NoLivesLost.status(&world),
+            ObjectiveStatus::Failed
+        );
+        assert_eq!(
+            Objective::ReachScore(10_000).status(&world),
+            ObjectiveStatus::Failed
+        );
+    }
+}
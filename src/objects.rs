@@ -1,27 +1,23 @@
-use std::time::Instant;
-
 use ggez::{
-    graphics::{Canvas, Color, DrawParam, Image, Rect},
+    graphics::{Canvas, Color, DrawParam, Rect},
     mint::{Point2, Vector2},
 };
+use serde::{Deserialize, Serialize};
 
 use crate::{
+    consts::{BLINK_MILLIS, TICK_RATE},
     errors::DodgerError,
     resources::Resources,
-    utils::{validate_coordinates, RectSize},
+    utils::{millis_to_ticks, object_scaling, validate_coordinates, RectSize},
 };
 
+#[derive(Clone, Debug)]
 pub struct FallingObject {
     pub coords: Point2<f32>,
     pub size: RectSize,
-    pub scaling: Vector2<f32>,
-    pub image: Image,
     pub is_good: bool,
     pub good_object_value: Option<GoodObjectValue>,
-    pub remove_timer: Option<Instant>,
-    pub blink_timer: Option<Instant>,
-    pub alpha: f32,
-    pub pulse_time: f32,
+    pub hit_tick: Option<u64>,
 }
 
 impl FallingObject {
@@ -29,106 +25,105 @@ impl FallingObject {
     ///
     /// ## Parameters
     /// * `coords`: initial position of the object on the screen.
-    /// * `scaling`: scaling factor for the size of the object.
+    /// * `size`: size of the object's sprite on the screen.
     /// * `is_good`: a boolean indicating whether the object is good (`true`) or bad (`false`).
     /// * `good_object_value`: the score value if the object is good.
-    /// * `resources`: a reference to resources.
     ///
     /// ## Returns
     /// A result with the newly created `FallingObject`, or a `DodgerError` if coordinates validation fails.
     pub fn new(
         coords: Point2<f32>,
-        scaling: Vector2<f32>,
+        size: RectSize,
         is_good: bool,
         good_object_value: Option<GoodObjectValue>,
-        resources: &Resources,
     ) -> Result<Self, DodgerError> {
         let validated_coords = validate_coordinates(coords)?;
 
-        let image = if is_good {
-            match good_object_value {
-                Some(GoodObjectValue::High) => &resources.good_object_high_image,
-                Some(GoodObjectValue::Medium) => &resources.good_object_medium_image,
-                Some(GoodObjectValue::Low) => &resources.good_object_low_image,
-                None => &resources.good_object_low_image,
-            }
-        } else {
-            &resources.bad_object_image
-        };
-
-        let w = image.width() as f32 * scaling.x;
-        let h = image.height() as f32 * scaling.y;
-        let size = RectSize::from((w, h));
-
         Ok(FallingObject {
             coords: validated_coords,
             size,
-            scaling,
-            image: image.clone(),
             good_object_value,
             is_good,
-            remove_timer: None,
-            blink_timer: None,
-            alpha: 0.0,
-            pulse_time: 0.0,
+            hit_tick: None,
         })
     }
 
-    /// **Updates position and handles behavior of a falling object.**
+    /// **Updates the position of a falling object.**
     ///
     /// ## Parameters
-    /// * `resources`: a reference to resources.
-    /// * `delta_time`: time since the last object update.
+    /// `fall_speed`: distance the object falls during one tick.
     ///
     /// ## Behavior
-    /// * Updates position of the falling object based on the fall speed.
-    /// * Handles special behavior for high-value good objects (pulsing effects).
-    pub fn update(&mut self, resources: &Resources, delta_time: f32) {
-        let speed = resources.level.fall_speed;
-        self.coords.y += speed;
-
-        if let Some(GoodObjectValue::High) = self.good_object_value {
-            self.pulse_time += delta_time;
-            if self.pulse_time > std::f32::consts::PI * 2.0 {
-                self.pulse_time -= std::f32::consts::PI * 2.0;
-            }
+    /// Objects that were hit stop falling while they blink.
+    pub fn update(&mut self, fall_speed: f32) {
+        if self.hit_tick.is_none() {
+            self.coords.y += fall_speed;
         }
     }
 
+    /// **Checks whether the object has finished blinking after a collision and can be removed.**
+    ///
+    /// ## Parameters
+    /// `tick`: the current simulation tick.
+    pub fn is_expired(&self, tick: u64) -> bool {
+        self.hit_tick
+            .is_some_and(|hit| tick.saturating_sub(hit) >= millis_to_ticks(BLINK_MILLIS))
+    }
+
     /// **Draws a falling object.**
     ///
     /// ## Parameters
-    /// `canvas`: canvas to draw the object on.
+    /// * `canvas`: canvas to draw the object on.
+    /// * `resources`: a reference to resources.
+    /// * `tick`: the current simulation tick.
     ///
     /// ## Behavior
-    /// Adjusts the transparency and scaling for good objects based on their type (blinking, pulsing).
-    pub fn draw(&mut self, canvas: &mut Canvas) {
-        let mut draw_params = DrawParam::default().dest(self.coords).scale(self.scaling);
+    /// Adjusts the transparency and scaling for good objects based on their type (blinking, pulsing, co-op tint).
+    pub fn draw(&self, canvas: &mut Canvas, resources: &Resources, tick: u64) {
+        let image = if self.is_good {
+            match self.good_object_value {
+                Some(GoodObjectValue::High) | Some(GoodObjectValue::Coop) => {
+                    &resources.good_object_high_image
+                }
+                Some(GoodObjectValue::Medium) => &resources.good_object_medium_image,
+                Some(GoodObjectValue::Low) => &resources.good_object_low_image,
+                None => &resources.good_object_low_image,
+            }
+        } else {
+            &resources.bad_object_image
+        };
+
+        let scaling = object_scaling();
+        let mut draw_params = DrawParam::default().dest(self.coords).scale(scaling);
 
-        if let Some(timer) = self.blink_timer {
-            let elapsed = timer.elapsed().as_secs_f32();
+        if let Some(hit) = self.hit_tick {
+            let elapsed = tick.saturating_sub(hit) as f32 / TICK_RATE as f32;
             let blink_speed = 10.0;
 
-            self.alpha = (elapsed * blink_speed * std::f32::consts::PI).sin().abs();
-            draw_params = draw_params.color(Color::new(1.0, 1.0, 1.0, self.alpha));
+            let alpha = (elapsed * blink_speed * std::f32::consts::PI).sin().abs();
+            draw_params = draw_params.color(Color::new(1.0, 1.0, 1.0, alpha));
         }
 
         if self.is_good {
             if let Some(GoodObjectValue::High) = self.good_object_value {
-                let pulse_factor = (self.pulse_time * 0.5).sin().abs() * 0.7 + 1.0;
+                let pulse_time = (tick as f32 * 0.1) % (std::f32::consts::PI * 2.0);
+                let pulse_factor = (pulse_time * 0.5).sin().abs() * 0.7 + 1.0;
 
                 let high_scaling = Vector2 {
-                    x: self.scaling.x * pulse_factor,
-                    y: self.scaling.y * pulse_factor,
+                    x: scaling.x * pulse_factor,
+                    y: scaling.y * pulse_factor,
                 };
 
                 draw_params = draw_params
                     .scale(high_scaling)
                     .color(Color::new(1.0, 1.0, 0.5, 1.0));
             }
+            if let Some(GoodObjectValue::Coop) = self.good_object_value {
+                draw_params = draw_params.color(Color::new(0.5, 1.0, 0.6, 1.0));
+            }
         }
 
-        canvas.draw(&self.image, draw_params)
+        canvas.draw(image, draw_params)
     }
 
     /// **Calculates the rectangular area occupied by the falling object.**
@@ -141,22 +136,44 @@ impl FallingObject {
 }
 
 /// Value of a good falling object.
-#[derive(PartialEq)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub enum GoodObjectValue {
     High,
     Medium,
     Low,
+    /// Only appears in co-op games and is only caught by both players together.
+    Coop,
 }
 
 impl GoodObjectValue {
+    /// All values, from the most to the least valuable to a single player.
+    pub const ALL: [GoodObjectValue; 4] = [
+        GoodObjectValue::High,
+        GoodObjectValue::Medium,
+        GoodObjectValue::Low,
+        GoodObjectValue::Coop,
+    ];
+
+    /// **Returns the name of the value shown in the menus.**
+    pub fn label(&self) -> &'static str {
+        match self {
+            GoodObjectValue::High => "High",
+            GoodObjectValue::Medium => "Medium",
+            GoodObjectValue::Low => "Low",
+            GoodObjectValue::Coop => "Co-op",
+        }
+    }
+
     /// **Returns the score associated with the type of good object.**
     ///
     /// ## Returns
     /// * `30`: for `High` value objects.
     /// * `15`: for `Medium` value objects.
     /// * `5`: for `Low` value objects.
+    /// * `60`: for `Coop` objects.
     pub fn score(&self) -> i32 {
         match self {
+            GoodObjectValue::Coop => 60,
             GoodObjectValue::High => 30,
             GoodObjectValue::Medium => 15,
             GoodObjectValue::Low => 5,
@@ -1,96 +1,134 @@
-use std::time::Instant;
-
 use ggez::{
     graphics::{Canvas, Color, DrawParam, Image, Rect},
     mint::{Point2, Vector2},
-    Context,
 };
 
 use crate::{
-    consts::WINDOW_WIDTH,
+    consts::{
+        BLINK_MILLIS, DASH_COOLDOWN_MILLIS, DASH_DISTANCE, LIVES, PLAYER_SPEED, TICK_RATE,
+        WINDOW_WIDTH,
+    },
     errors::DodgerError,
-    utils::{validate_coordinates, RectSize},
+    utils::{millis_to_ticks, validate_coordinates, RectSize},
 };
 
-#[derive(Clone)]
+/// A player controlled by a person or the bot.
+///
+/// `speed` is the movement speed at full input in pixels per second, and `dash_distance` how far a dash moves the player;
+/// both start at `PLAYER_SPEED` and `DASH_DISTANCE` and are raised by upgrades.
+#[derive(Clone, Debug)]
 pub struct Player {
     pub coords: Point2<f32>,
     pub size: RectSize,
-    pub scaling: Vector2<f32>,
-    pub image: Image,
-    pub blink_timer: Option<Instant>,
-    pub alpha: f32,
+    pub speed: f32,
+    pub dash_distance: f32,
+    pub direction: f32,
+    pub last_dash_tick: Option<u64>,
+    pub blink_tick: Option<u64>,
 }
 
 impl Player {
     /// **Constructs and initializes a new `Player`.**
     ///
     /// ## Parameters
-    /// * `_ctx`: the game context.
     /// * `coords`: initial coordinates of the player.
-    /// * `scaling`: scaling factor to apply to the player's image.
-    /// * `image`: the `Image` representing the player.
+    /// * `size`: size of the player's sprite on the screen.
     ///
     /// ## Returns
     /// A result containing the new `Player`, or a `DodgerError` if coordinates validation fails.
-    pub fn new(
-        _ctx: &mut Context,
-        coords: Point2<f32>,
-        scaling: Vector2<f32>,
-        image: &Image,
-    ) -> Result<Self, DodgerError> {
+    pub fn new(coords: Point2<f32>, size: RectSize) -> Result<Self, DodgerError> {
         let validated_coords = validate_coordinates(coords)?;
-        let w = image.width() as f32 * scaling.x;
-        let h = image.width() as f32 * scaling.x;
-        let size = RectSize::from((w, h));
 
         Ok(Player {
             coords: validated_coords,
             size,
-            scaling,
-            image: image.clone(),
-            blink_timer: None,
-            alpha: 0.0,
+            speed: PLAYER_SPEED,
+            dash_distance: DASH_DISTANCE,
+            direction: 1.0,
+            last_dash_tick: None,
+            blink_tick: None,
         })
     }
 
-    /// **Moves the player to the left by a fixed amount.**
+    /// **Moves the player horizontally, keeping it inside the window.**
+    ///
+    /// ## Parameters
+    /// `dx`: horizontal offset; negative values move the player to the left.
     ///
     /// ## Behavior
-    /// Decreases the x-coordinate of the player by 20.0, ensuring it doesn't go below 0.0.
-    pub fn move_left(&mut self) {
-        self.coords.x -= 20.0_f32.max(0.0);
+    /// * The x-coordinate is clamped between 0.0 and the window width minus the player width.
+    /// * The direction of the move is remembered for dashing.
+    pub fn move_by(&mut self, dx: f32) {
+        self.coords.x = clamp_player_x(self.coords.x + dx, self.size.w);
+        if dx != 0.0 {
+            self.direction = dx.signum();
+        }
     }
 
-    /// **Moves the player to the right by a fixed amount.**
+    /// **Dashes the player in the direction of its last move.**
+    ///
+    /// ## Parameters
+    /// `tick`: the current simulation tick.
     ///
     /// ## Behavior
-    /// Increases the x-coordinate of the player by 20.0, ensuring the player doesn't go beyond the window boundaries.
-    pub fn move_right(&mut self) {
-        self.coords.x += 20.0_f32.min(WINDOW_WIDTH - self.size.w);
+    /// * Moves the player by its `dash_distance`, keeping it inside the window.
+    /// * Does nothing if the previous dash happened less than `DASH_COOLDOWN_MILLIS` ago.
+    pub fn dash(&mut self, tick: u64) {
+        let cooldown = millis_to_ticks(DASH_COOLDOWN_MILLIS);
+        if self
+            .last_dash_tick
+            .is_some_and(|dash| tick.saturating_sub(dash) < cooldown)
+        {
+            return;
+        }
+        self.move_by(self.direction * self.dash_distance);
+        self.last_dash_tick = Some(tick);
+    }
+
+    /// **Checks whether the player is blinking after a collision with a "bad" object.**
+    ///
+    /// ## Parameters
+    /// `tick`: the current simulation tick.
+    pub fn is_blinking(&self, tick: u64) -> bool {
+        self.blink_tick
+            .is_some_and(|start| tick.saturating_sub(start) < millis_to_ticks(BLINK_MILLIS))
     }
 
     /// **Draws the player.**
     ///
     /// ## Parameters
-    /// `canvas`: canvas to draw the player on.
+    /// * `canvas`: canvas to draw the player on.
+    /// * `image`: the `Image` representing the player.
+    /// * `scaling`: scaling factor to apply to the player's image.
+    /// * `tint`: color the image is multiplied with.
+    /// * `tick`: the current simulation tick.
     ///
     /// ## Behavior
-    /// * Draws the player at the current position (`coords`) with a scaling factor (`scaling`).
-    /// * Handles blinking effects if `blink_timer` is active:
+    /// * Draws the player at the current position (`coords`), colored with `tint`.
+    /// * Handles blinking effects while the player is blinking:
     ///   * The alpha transparency of the player oscillates based on the elapsed time to create a blinking effect.
-    pub fn draw(&mut self, canvas: &mut Canvas) {
-        let mut draw_params = DrawParam::default().dest(self.coords).scale(self.scaling);
+    pub fn draw(
+        &self,
+        canvas: &mut Canvas,
+        image: &Image,
+        scaling: Vector2<f32>,
+        tint: Color,
+        tick: u64,
+    ) {
+        let mut draw_params = DrawParam::default()
+            .dest(self.coords)
+            .scale(scaling)
+            .color(tint);
 
-        if let Some(timer) = self.blink_timer {
-            let elapsed = timer.elapsed().as_secs_f32();
+        if let Some(start) = self.blink_tick.filter(|_| self.is_blinking(tick)) {
+            let elapsed = (tick - start) as f32 / TICK_RATE as f32;
             let blink_speed = 10.0; // Blinking frequency (times per second)
 
-            self.alpha = (elapsed * blink_speed * std::f32::consts::PI).sin().abs();
-            draw_params = draw_params.color(Color::new(1.0, 1.0, 1.0, self.alpha));
+            let alpha = (elapsed * blink_speed * std::f32::consts::PI).sin().abs();
+            draw_params = draw_params.color(Color::new(tint.r, tint.g, tint.b, alpha * tint.a));
         }
 
-        canvas.draw(&self.image, draw_params)
+        canvas.draw(image, draw_params)
     }
 
     /// **Calculates the rectangular area occupied by the player.**
@@ -101,3 +139,50 @@ impl Player {
         Rect::new(self.coords.x, self.coords.y, self.size.w, self.size.h)
     }
 }
+
+/// **Clamps the x-coordinate of a player so that it stays inside the window.**
+///
+/// ## Parameters
+/// * `x`: the desired x-coordinate.
+/// * `width`: width of the player.
+///
+/// ## Returns
+/// The x-coordinate limited to the range from 0.0 to the window width minus the player width.
+pub fn clamp_player_x(x: f32, width: f32) -> f32 {
+    x.clamp(0.0, (WINDOW_WIDTH - width).max(0.0))
+}
+
+/// **The state of one player taking part in a game.**
+///
+/// ## Fields
+/// * `player`: the player object.
+/// * `lives`: the number of lives the player has remaining.
+/// * `level_score`: the player's score for the current level.
+#[derive(Clone, Debug)]
+pub struct PlayerState {
+    pub player: Player,
+    pub lives: u8,
+    pub level_score: i32,
+}
+
+impl PlayerState {
+    /// **Creates the state of a player starting a level.**
+    ///
+    /// ## Parameters
+    /// `player`: the player object.
+    ///
+    /// ## Returns
+    /// A `PlayerState` with full lives and no score.
+    pub fn new(player: Player) -> Self {
+        Self {
+            player,
+            lives: LIVES,
+            level_score: 0,
+        }
+    }
+
+    /// **Checks whether the player still has lives left.**
+    pub fn is_alive(&self) -> bool {
+        self.lives > 0
+    }
+}
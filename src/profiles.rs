@@ -0,0 +1,315 @@
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::DodgerError,
+    storage::{load_json, save_json},
+};
+
+/// Maximum length of a profile name, in characters.
+pub const MAX_PROFILE_NAME_LENGTH: usize = 16;
+
+/// Maximum number of profiles, limited by the room on the profiles screen.
+pub const MAX_PROFILES: usize = 6;
+
+/// Name of the profile created when no profile exists yet.
+const DEFAULT_PROFILE_NAME: &str = "Player";
+
+/// Files stored next to the profiles before profiles existed; they are moved into the first profile.
+const LEGACY_FILES: [&str; 4] = [
+    "settings.json",
+    "highscores.json",
+    "progress.json",
+    "savegame.json",
+];
+
+/// **A named player profile.**
+///
+/// ## Fields
+/// * `id`: identifier of the profile, naming its directory; it does not change when the profile is renamed.
+/// * `name`: the name shown in the menus.
+///
+/// ## Behavior
+/// The settings, high scores, progress, statistics, achievements and saved game of a profile are stored as separate files in its directory.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Profile {
+    pub id: u32,
+    pub name: String,
+}
+
+impl Profile {
+    /// **Returns the directory the files of the profile are stored in.**
+    ///
+    /// ## Parameters
+    /// `config_dir`: the directory containing the profile list.
+    pub fn dir(&self, config_dir: &Path) -> PathBuf {
+        config_dir.join("profiles").join(self.id.to_string())
+    }
+
+    /// **Deletes the directory of the profile with all its files, if there is one.**
+    ///
+    /// ## Parameters
+    /// `config_dir`: the directory containing the profile list.
+    ///
+    /// ## Returns
+    /// `Ok(())` if no files of the profile are left, or a `DodgerError` if the directory cannot be removed.
+    pub fn delete_files(&self, config_dir: &Path) -> Result<(), DodgerError> {
+        let dir = self.dir(config_dir);
+        match fs::remove_dir_all(&dir) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(DodgerError::WriteFile(
+                dir.display().to_string(),
+                err.to_string(),
+            )),
+        }
+    }
+}
+
+/// **The name typed on the profile name screen.**
+///
+/// ## Fields
+/// * `target`: index of the profile being renamed, or `None` when a new profile is created.
+/// * `name`: the name typed so far.
+/// * `status`: the reason the last confirmed name was rejected, if any.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct ProfileEditor {
+    pub target: Option<usize>,
+    pub name: String,
+    pub status: Option<String>,
+}
+
+/// **The player profiles sharing the game, persisted between game sessions.**
+///
+/// ## Fields
+/// * `profiles`: the profiles in the order they were created; there is always at least one.
+/// * `active`: index of the profile playing the game.
+/// * `next_id`: the id of the next profile created; it only ever increases, so the id of a deleted profile is never reused.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Profiles {
+    pub profiles: Vec<Profile>,
+    pub active: usize,
+    #[serde(default)]
+    pub next_id: u32,
+}
+
+impl Default for Profiles {
+    fn default() -> Self {
+        Self {
+            profiles: vec![Profile {
+                id: 0,
+                name: DEFAULT_PROFILE_NAME.to_string(),
+            }],
+            active: 0,
+            next_id: 1,
+        }
+    }
+}
+
+impl Profiles {
+    /// **Returns the path of the profile list in a directory.**
+    pub fn path(dir: &Path) -> PathBuf {
+        dir.join("profiles.json")
+    }
+
+    /// **Loads the profile list stored in a directory.**
+    ///
+    /// ## Parameters
+    /// `dir`: directory containing the profile list.
+    ///
+    /// ## Returns
+    /// A result containing the stored profiles, or a `DodgerError` if the list cannot be read or parsed, or the files of the first profile cannot be moved.
+    ///
+    /// ## Behavior
+    /// * When nothing is stored yet, a single default profile is created; the files stored before profiles existed are moved into it.
+    /// * An empty list gets the default profile, and an invalid active index selects the first profile.
+    /// * Lists stored before `next_id` existed continue after the highest id in use.
+    pub fn load(dir: &Path) -> Result<Self, DodgerError> {
+        let Some(mut profiles) = load_json::<Self>(&Self::path(dir))? else {
+            let profiles = Self::default();
+            profiles.adopt_legacy_files(dir)?;
+            profiles.save(dir)?;
+            return Ok(profiles);
+        };
+        if profiles.profiles.is_empty() {
+            profiles = Self {
+                next_id: profiles.next_id,
+                ..Self::default()
+            };
+        }
+        if profiles.active >= profiles.profiles.len() {
+            profiles.active = 0;
+        }
+        let unused_id = profiles.profiles.iter().map(|p| p.id + 1).max();
+        profiles.next_id = profiles.next_id.max(unused_id.unwrap_or(0));
+        Ok(profiles)
+    }
+
+    /// **Stores the profile list in a directory.**
+    ///
+    /// ## Parameters
+    /// `dir`: directory to store the profile list in.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the list is stored successfully, or a `DodgerError` if the file cannot be written.
+    pub fn save(&self, dir: &Path) -> Result<(), DodgerError> {
+        save_json(&Self::path(dir), self)
+    }
+
+    /// **Moves the files stored before profiles existed into the active profile.**
+    fn adopt_legacy_files(&self, dir: &Path) -> Result<(), DodgerError> {
+        let profile_dir = self.active().dir(dir);
+        for file in LEGACY_FILES {
+            let from = dir.join(file);
+            if !from.exists() {
+                continue;
+            }
+            let to = profile_dir.join(file);
+            fs::create_dir_all(&profile_dir)
+                .and_then(|()| fs::rename(&from, &to))
+                .map_err(|err| DodgerError::WriteFile(to.display().to_string(), err.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// **Returns the profile playing the game.**
+    pub fn active(&self) -> &Profile {
+        &self.profiles[self.active]
+    }
+
+    /// **Creates a new profile.**
+    ///
+    /// ## Parameters
+    /// `name`: the name of the new profile.
+    ///
+    /// ## Returns
+    /// A result containing the index of the new profile, or `DodgerError::InvalidProfileName` if the name is empty, too long or already used.
+    pub fn create(&mut self, name: &str) -> Result<usize, DodgerError> {
+        let name = self.validate_name(name, None)?;
+        let id = self.next_id;
+        self.next_id += 1;
+        self.profiles.push(Profile { id, name });
+        Ok(self.profiles.len() - 1)
+    }
+
+    /// **Renames a profile.**
+    ///
+    /// ## Parameters
+    /// * `index`: index of the profile.
+    /// * `name`: the new name of the profile.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the profile is renamed, or `DodgerError::InvalidProfileName` if the name is empty, too long or used by another profile.
+    pub fn rename(&mut self, index: usize, name: &str) -> Result<(), DodgerError> {
+        let name = self.validate_name(name, Some(index))?;
+        if let Some(profile) = self.profiles.get_mut(index) {
+            profile.name = name;
+        }
+        Ok(())
+    }
+
+    /// **Removes a profile from the list.**
+    ///
+    /// ## Parameters
+    /// `index`: index of the profile.
+    ///
+    /// ## Returns
+    /// The removed profile, whose files are left for the caller to delete; `None` if there is no such profile or it is the last one.
+    ///
+    /// ## Behavior
+    /// Removing the active profile activates the first one.
+    pub fn remove(&mut self, index: usize) -> Option<Profile> {
+        if index >= self.profiles.len() || self.profiles.len() == 1 {
+            return None;
+        }
+        let removed = self.profiles.remove(index);
+        if self.active == index {
+            self.active = 0;
+        } else if self.active > index {
+            self.active -= 1;
+        }
+        Some(removed)
+    }
+
+    /// **Checks a profile name and returns it without surrounding whitespace.**
+    ///
+    /// ## Parameters
+    /// * `name`: the name to check.
+    /// * `except`: index of the profile being renamed, which may keep its own name.
+    fn validate_name(&self, name: &str, except: Option<usize>) -> Result<String, DodgerError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(DodgerError::InvalidProfileName(
+                "the name is empty".to_string(),
+            ));
+        }
+        if name.chars().count() > MAX_PROFILE_NAME_LENGTH {
+            return Err(DodgerError::InvalidProfileName(format!(
+                "the name is longer than {} characters",
+                MAX_PROFILE_NAME_LENGTH
+            )));
+        }
+        let taken = self
+            .profiles
+            .iter()
+            .enumerate()
+            .any(|(i, p)| Some(i) != except && p.name.eq_ignore_ascii_case(name));
+        if taken {
+            return Err(DodgerError::InvalidProfileName(format!(
+                "{} is already used",
+                name
+            )));
+        }
+        Ok(name.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_names_are_unique_and_not_empty() {
+        let mut profiles = Profiles::default();
+        assert_eq!(profiles.create("  Anna ").unwrap(), 1);
+        assert_eq!(profiles.profiles[1].name, "Anna");
+        assert!(profiles.create("anna").is_err());
+        assert!(profiles.create("   ").is_err());
+        assert!(profiles
+            .create(&"x".repeat(MAX_PROFILE_NAME_LENGTH + 1))
+            .is_err());
+
+        assert!(profiles.rename(1, "ANNA").is_ok());
+        assert!(profiles.rename(1, DEFAULT_PROFILE_NAME).is_err());
+        assert_eq!(profiles.profiles[1].name, "ANNA");
+    }
+
+    #[test]
+    fn test_removing_profiles_keeps_one_active() {
+        let mut profiles = Profiles::default();
+        profiles.create("Anna").unwrap();
+        profiles.create("Boris").unwrap();
+        profiles.active = 2;
+
+        assert_eq!(profiles.remove(1).map(|p| p.name), Some("Anna".to_string()));
+        assert_eq!(profiles.active().name, "Boris");
+        assert_eq!(profiles.create("Clara").unwrap(), 2);
+        assert_eq!(profiles.profiles[2].id, 3);
+
+        // The newest profile's id is not reused once it is deleted.
+        profiles.remove(2);
+        profiles.create("Dora").unwrap();
+        assert_eq!(profiles.profiles[2].id, 4);
+
+        profiles.remove(1);
+        assert_eq!(profiles.active, 0);
+        profiles.remove(1);
+        assert_eq!(profiles.remove(0), None);
+        assert_eq!(profiles.profiles.len(), 1);
+    }
+}
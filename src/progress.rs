@@ -0,0 +1,139 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::DodgerError,
+    storage::{load_json, save_json},
+};
+
+/// **The best result reached on a level.**
+///
+/// ## Fields
+/// * `best_score`: the best level score among the completed attempts.
+/// * `stars`: the most stars earned on the level, from 1 to 3; 0 if the level was never completed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LevelProgress {
+    pub best_score: i32,
+    pub stars: u8,
+}
+
+impl LevelProgress {
+    /// **Checks whether the level was completed at least once.**
+    pub fn is_completed(&self) -> bool {
+        self.stars > 0
+    }
+}
+
+/// **The player's progress through the levels, persisted between game sessions.**
+///
+/// ## Fields
+/// `levels`: the progress of each level, by level index; levels beyond its end were never completed.
+///
+/// ## Behavior
+/// The first level is always unlocked; every other level is unlocked by completing the level before it.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Progress {
+    pub levels: Vec<LevelProgress>,
+}
+
+impl Progress {
+    /// **Returns the path of the progress file in a directory.**
+    pub fn path(dir: &Path) -> PathBuf {
+        dir.join("progress.json")
+    }
+
+    /// **Loads the progress stored in a directory.**
+    ///
+    /// ## Parameters
+    /// `dir`: directory containing the progress file.
+    ///
+    /// ## Returns
+    /// A result containing the stored progress (or no progress if nothing is stored yet), or a `DodgerError` if the file cannot be read or parsed.
+    pub fn load(dir: &Path) -> Result<Self, DodgerError> {
+        Ok(load_json(&Self::path(dir))?.unwrap_or_default())
+    }
+
+    /// **Stores the progress in a directory.**
+    ///
+    /// ## Parameters
+    /// `dir`: directory to store the progress file in.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the progress is stored successfully, or a `DodgerError` if the file cannot be written.
+    pub fn save(&self, dir: &Path) -> Result<(), DodgerError> {
+        save_json(&Self::path(dir), self)
+    }
+
+    /// **Returns the progress of a level.**
+    pub fn level(&self, level_index: usize) -> LevelProgress {
+        self.levels.get(level_index).copied().unwrap_or_default()
+    }
+
+    /// **Checks whether a level can be played.**
+    pub fn is_unlocked(&self, level_index: usize) -> bool {
+        level_index == 0 || self.level(level_index - 1).is_completed()
+    }
+
+    /// **Records a completed level.**
+    ///
+    /// ## Parameters
+    /// * `level_index`: index of the completed level.
+    /// * `score`: the level score.
+    /// * `stars`: the stars earned, from 1 to 3.
+    ///
+    /// ## Returns
+    /// `true` if the best score or the stars of the level improved.
+    pub fn record(&mut self, level_index: usize, score: i32, stars: u8) -> bool {
+        if self.levels.len() <= level_index {
+            self.levels
+                .resize(level_index + 1, LevelProgress::default());
+        }
+        let level = &mut self.levels[level_index];
+        let previous = *level;
+        if !level.is_completed() || score > level.best_score {
+            level.best_score = score;
+        }
+        level.stars = level.stars.max(stars.clamp(1, 3));
+        *level != previous
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{difficulty::DifficultyPreset, levels::get_levels};
+
+    #[test]
+    fn test_completing_a_level_unlocks_the_next_one() {
+        let mut progress = Progress::default();
+        assert!(progress.is_unlocked(0));
+        assert!(!progress.is_unlocked(1));
+
+        assert!(progress.record(0, 120, 1));
+        assert!(progress.is_unlocked(1));
+        assert!(!progress.is_unlocked(2));
+
+        assert!(!progress.record(0, 80, 1));
+        assert!(progress.record(0, 90, 3));
+        assert_eq!(
+            progress.level(0),
+            LevelProgress {
+                best_score: 120,
+                stars: 3
+            }
+        );
+    }
+
+    #[test]
+    fn test_stars_follow_the_level_thresholds() {
+        let level = &get_levels()[0];
+        let [two, three] = level.star_scores;
+        assert_eq!(level.stars(0, DifficultyPreset::Normal), 1);
+        assert_eq!(level.stars(two, DifficultyPreset::Normal), 2);
+        assert_eq!(level.stars(three, DifficultyPreset::Normal), 3);
+        assert_eq!(level.stars(two, DifficultyPreset::Hardcore), 1);
+    }
+}
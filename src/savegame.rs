@@ -0,0 +1,210 @@
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    difficulty::DifficultyPreset,
+    errors::DodgerError,
+    modes::PlayMode,
+    shop::Purchases,
+    simulation::WorldSnapshot,
+    storage::{load_json, save_json},
+};
+
+/// Version of the save game format; saves of other versions cannot be continued.
+pub const SAVE_VERSION: u32 = 1;
+
+/// **A single-player or co-op run saved to be continued in a later session.**
+///
+/// ## Fields
+/// * `version`: version of the save game format, `SAVE_VERSION` when written by this build.
+/// * `level`: index of the level being played.
+/// * `play_mode`: how the players play together.
+/// * `difficulty`: the difficulty preset of the run.
+/// * `total_score`: the total score of the levels completed before the current one, less the upgrades bought.
+/// * `purchases`: the upgrades bought in the shop during the run.
+/// * `continues_used`: the continues spent during the run.
+/// * `level_completed`: whether the level was completed, so the run continues in the shop before the next level.
+/// * `world`: the state of the current level: scores, lives, elapsed time and the falling objects.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct SaveGame {
+    pub version: u32,
+    pub level: usize,
+    pub play_mode: PlayMode,
+    pub difficulty: DifficultyPreset,
+    pub total_score: i32,
+    #[serde(default)]
+    pub purchases: Purchases,
+    #[serde(default)]
+    pub continues_used: u8,
+    #[serde(default)]
+    pub level_completed: bool,
+    pub world: WorldSnapshot,
+}
+
+impl SaveGame {
+    /// **Returns the path of the save game file in a directory.**
+    pub fn path(dir: &Path) -> PathBuf {
+        dir.join("savegame.json")
+    }
+
+    /// **Loads the saved game stored in a directory.**
+    ///
+    /// ## Parameters
+    /// * `dir`: directory containing the save game file.
+    /// * `level_count`: number of levels in the game.
+    ///
+    /// ## Returns
+    /// * `Ok(Some(save))` if a saved game exists and can be continued.
+    /// * `Ok(None)` if nothing is saved.
+    /// * A `DodgerError` if the file cannot be read or parsed, or the saved game cannot be continued; see `check`.
+    pub fn load(dir: &Path, level_count: usize) -> Result<Option<Self>, DodgerError> {
+        let Some(save) = load_json::<Self>(&Self::path(dir))? else {
+            return Ok(None);
+        };
+        save.check(level_count)?;
+        Ok(Some(save))
+    }
+
+    /// **Checks whether the saved game can be continued by this build.**
+    ///
+    /// ## Parameters
+    /// `level_count`: number of levels in the game.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the save has the current version, refers to an existing level (followed by another one once it is completed)
+    /// and is not a versus game, or `DodgerError::IncompatibleSave` otherwise.
+    pub fn check(&self, level_count: usize) -> Result<(), DodgerError> {
+        if self.version != SAVE_VERSION {
+            return Err(DodgerError::IncompatibleSave(format!(
+                "version {} is not supported, expected {}",
+                self.version, SAVE_VERSION
+            )));
+        }
+        if self.level >= level_count {
+            return Err(DodgerError::IncompatibleSave(format!(
+                "level {} does not exist",
+                self.level + 1
+            )));
+        }
+        if self.level_completed && self.level + 1 >= level_count {
+            return Err(DodgerError::IncompatibleSave(
+                "the last level was completed".to_string(),
+            ));
+        }
+        if self.play_mode == PlayMode::Versus {
+            return Err(DodgerError::IncompatibleSave(
+                "versus games cannot be saved".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// **Stores the saved game in a directory.**
+    ///
+    /// ## Parameters
+    /// `dir`: directory to store the save game file in.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the game is saved successfully, or a `DodgerError` if the file cannot be written.
+    pub fn save(&self, dir: &Path) -> Result<(), DodgerError> {
+        save_json(&Self::path(dir), self)
+    }
+
+    /// **Deletes the saved game from a directory, if there is one.**
+    ///
+    /// ## Returns
+    /// `Ok(())` if no saved game is left, or a `DodgerError` if the file cannot be removed.
+    pub fn delete(dir: &Path) -> Result<(), DodgerError> {
+        let path = Self::path(dir);
+        match fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(DodgerError::WriteFile(
+                path.display().to_string(),
+                err.to_string(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        levels::get_levels,
+        simulation::{SpriteSizes, World},
+    };
+
+    #[test]
+    fn test_only_current_saves_can_be_continued() {
+        let levels = get_levels();
+        let world = World::new(
+            &levels[1],
+            PlayMode::Single,
+            DifficultyPreset::Hard,
+            SpriteSizes::default(),
+            3,
+        )
+        .unwrap();
+        let save = SaveGame {
+            version: SAVE_VERSION,
+            level: 1,
+            play_mode: PlayMode::Single,
+            difficulty: DifficultyPreset::Hard,
+            total_score: 120,
+            purchases: Purchases::default(),
+            continues_used: 0,
+            level_completed: false,
+            world: world.snapshot(),
+        };
+        let json = serde_json::to_string(&save).unwrap();
+        let parsed: SaveGame = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, save);
+        assert!(parsed.check(levels.len()).is_ok());
+
+        let other_version = SaveGame {
+            version: SAVE_VERSION + 1,
+            ..save.clone()
+        };
+        assert!(matches!(
+            other_version.check(levels.len()),
+            Err(DodgerError::IncompatibleSave(_))
+        ));
+        assert!(save.check(1).is_err());
+    }
+
+    #[test]
+    fn test_completed_levels_resume_before_the_next_one() {
+        let levels = get_levels();
+        let last = levels.len() - 1;
+        let world = World::new(
+            &levels[last],
+            PlayMode::Coop,
+            DifficultyPreset::Normal,
+            SpriteSizes::default(),
+            5,
+        )
+        .unwrap();
+        let json = serde_json::json!({
+            "version": SAVE_VERSION,
+            "level": last,
+            "play_mode": PlayMode::Coop,
+            "difficulty": DifficultyPreset::Normal,
+            "total_score": 300,
+            "world": world.snapshot(),
+        });
+        let mut save: SaveGame = serde_json::from_value(json).unwrap();
+        assert!(!save.level_completed);
+        assert!(save.check(levels.len()).is_ok());
+
+        save.level_completed = true;
+        assert!(save.check(levels.len()).is_err());
+        save.level = last - 1;
+        assert!(save.check(levels.len()).is_ok());
+    }
+}
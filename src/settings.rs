@@ -0,0 +1,69 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    difficulty::DifficultyPreset,
+    errors::DodgerError,
+    input::{ControlScheme, InputBindings},
+    storage::{load_json, save_json},
+};
+
+/// **Player settings persisted between game sessions.**
+///
+/// ## Fields
+/// * `bindings`: keyboard and gamepad bindings of the input actions.
+/// * `player_two_bindings`: keyboard bindings of the second player in local multiplayer.
+/// * `control_scheme`: the way the player steers during the game.
+/// * `difficulty`: the difficulty preset of the next run.
+/// * `adaptive_difficulty`: whether the next single-player or co-op run adjusts its difficulty to the player's performance.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub bindings: InputBindings,
+    pub player_two_bindings: InputBindings,
+    pub control_scheme: ControlScheme,
+    pub difficulty: DifficultyPreset,
+    pub adaptive_difficulty: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            bindings: InputBindings::default(),
+            player_two_bindings: InputBindings::player_two(),
+            control_scheme: ControlScheme::default(),
+            difficulty: DifficultyPreset::default(),
+            adaptive_difficulty: false,
+        }
+    }
+}
+
+impl Settings {
+    /// **Returns the path of the settings file in a directory.**
+    pub fn path(dir: &Path) -> PathBuf {
+        dir.join("settings.json")
+    }
+
+    /// **Loads the settings stored in a directory.**
+    ///
+    /// ## Parameters
+    /// `dir`: directory containing the settings file.
+    ///
+    /// ## Returns
+    /// A result containing the stored settings (or the defaults if nothing is stored yet), or a `DodgerError` if the file cannot be read or parsed.
+    pub fn load(dir: &Path) -> Result<Self, DodgerError> {
+        Ok(load_json(&Self::path(dir))?.unwrap_or_default())
+    }
+
+    /// **Stores the settings in a directory.**
+    ///
+    /// ## Parameters
+    /// `dir`: directory to store the settings file in.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the settings are stored successfully, or a `DodgerError` if the file cannot be written.
+    pub fn save(&self, dir: &Path) -> Result<(), DodgerError> {
+        save_json(&Self::path(dir), self)
+    }
+}
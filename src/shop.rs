@@ -0,0 +1,217 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{difficulty::DifficultyPreset, errors::DodgerError};
+
+/// What an upgrade does to the players, for each purchase.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum UpgradeEffect {
+    /// Widens the catch area by a share of the player's width.
+    CatchWidth(f32),
+    /// Raises the movement speed by a share of `PLAYER_SPEED`.
+    Speed(f32),
+    /// Adds lives at the start of each level.
+    ExtraLives(u8),
+    /// Lengthens the dash by a share of `DASH_DISTANCE`.
+    DashDistance(f32),
+}
+
+/// **An upgrade sold in the shop between levels.**
+///
+/// ## Fields
+/// * `name`: the name shown on the shop screen.
+/// * `description`: what one purchase does, shown on the shop screen.
+/// * `prices`: the price of each purchase, in score; the upgrade can be bought once per price.
+/// * `effect`: the effect of each purchase.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Upgrade {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub prices: &'static [i32],
+    pub effect: UpgradeEffect,
+}
+
+impl Upgrade {
+    /// **Checks whether the upgrade is sold on a difficulty preset.**
+    ///
+    /// ## Behavior
+    /// Extra lives are not sold on Hardcore, whose players keep a single life.
+    pub fn is_available(&self, preset: DifficultyPreset) -> bool {
+        preset != DifficultyPreset::Hardcore || !matches!(self.effect, UpgradeEffect::ExtraLives(_))
+    }
+}
+
+/// The upgrades of the shop, in the order they are shown.
+pub const UPGRADES: [Upgrade; 4] = [
+    Upgrade {
+        name: "Wider Catch",
+        description: "+15% catch area",
+        prices: &[100, 200, 300],
+        effect: UpgradeEffect::CatchWidth(0.15),
+    },
+    Upgrade {
+        name: "Faster Moves",
+        description: "+10% speed",
+        prices: &[80, 160, 240],
+        effect: UpgradeEffect::Speed(0.1),
+    },
+    Upgrade {
+        name: "Extra Life",
+        description: "+1 life per level",
+        prices: &[150, 300],
+        effect: UpgradeEffect::ExtraLives(1),
+    },
+    Upgrade {
+        name: "Longer Dash",
+        description: "+25% dash distance",
+        prices: &[60, 120],
+        effect: UpgradeEffect::DashDistance(0.25),
+    },
+];
+
+/// **How the upgrades bought in a run change the players.**
+///
+/// ## Fields
+/// * `catch_width`: multiplier of the player's width.
+/// * `speed`: multiplier of the movement speed.
+/// * `extra_lives`: lives added at the start of each level.
+/// * `dash_distance`: multiplier of the dash distance.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Boosts {
+    pub catch_width: f32,
+    pub speed: f32,
+    pub extra_lives: u8,
+    pub dash_distance: f32,
+}
+
+impl Default for Boosts {
+    /// No upgrades: the players are unchanged.
+    fn default() -> Self {
+        Self {
+            catch_width: 1.0,
+            speed: 1.0,
+            extra_lives: 0,
+            dash_distance: 1.0,
+        }
+    }
+}
+
+/// **The upgrades bought during a run; they are lost when a new run starts.**
+///
+/// ## Fields
+/// `counts`: the number of purchases of each upgrade, by index in `UPGRADES`.
+#[derive(Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Purchases {
+    pub counts: Vec<u8>,
+}
+
+impl Purchases {
+    /// **Returns the number of purchases of an upgrade.**
+    pub fn count(&self, index: usize) -> u8 {
+        self.counts.get(index).copied().unwrap_or(0)
+    }
+
+    /// **Returns the price of the next purchase of an upgrade, or `None` if it is sold out.**
+    pub fn next_price(&self, index: usize) -> Option<i32> {
+        UPGRADES
+            .get(index)?
+            .prices
+            .get(usize::from(self.count(index)))
+            .copied()
+    }
+
+    /// **Buys an upgrade.**
+    ///
+    /// ## Parameters
+    /// * `index`: index of the upgrade in `UPGRADES`.
+    /// * `score`: the score to pay with.
+    /// * `preset`: the difficulty preset of the run.
+    ///
+    /// ## Returns
+    /// The price paid, or `DodgerError::PurchaseFailed` if the upgrade is not sold on the preset, is sold out
+    /// or the score does not cover its price.
+    pub fn buy(
+        &mut self,
+        index: usize,
+        score: i32,
+        preset: DifficultyPreset,
+    ) -> Result<i32, DodgerError> {
+        if UPGRADES
+            .get(index)
+            .is_some_and(|upgrade| !upgrade.is_available(preset))
+        {
+            return Err(DodgerError::PurchaseFailed(format!(
+                "not sold on {}",
+                preset.name()
+            )));
+        }
+        let Some(price) = self.next_price(index) else {
+            return Err(DodgerError::PurchaseFailed("sold out".to_string()));
+        };
+        if score < price {
+            return Err(DodgerError::PurchaseFailed(format!(
+                "{} points needed",
+                price
+            )));
+        }
+        if self.counts.len() <= index {
+            self.counts.resize(index + 1, 0);
+        }
+        self.counts[index] += 1;
+        Ok(price)
+    }
+
+    /// **Returns the combined effect of the upgrades bought.**
+    pub fn boosts(&self) -> Boosts {
+        let mut boosts = Boosts::default();
+        for (index, upgrade) in UPGRADES.iter().enumerate() {
+            let count = self.count(index);
+            let times = f32::from(count);
+            match upgrade.effect {
+                UpgradeEffect::CatchWidth(share) => boosts.catch_width += share * times,
+                UpgradeEffect::Speed(share) => boosts.speed += share * times,
+                UpgradeEffect::ExtraLives(lives) => boosts.extra_lives += lives * count,
+                UpgradeEffect::DashDistance(share) => boosts.dash_distance += share * times,
+            }
+        }
+        boosts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upgrades_sell_out_after_their_last_price() {
+        let mut purchases = Purchases::default();
+        let preset = DifficultyPreset::Normal;
+        assert!(purchases.buy(2, 149, preset).is_err());
+        assert_eq!(purchases.buy(2, 500, preset).unwrap(), 150);
+        assert_eq!(purchases.buy(2, 500, preset).unwrap(), 300);
+        assert_eq!(purchases.next_price(2), None);
+        assert!(purchases.buy(2, 500, preset).is_err());
+        assert_eq!(purchases.count(2), 2);
+    }
+
+    #[test]
+    fn test_hardcore_sells_no_extra_lives() {
+        let mut purchases = Purchases::default();
+        assert!(purchases.buy(2, 500, DifficultyPreset::Hardcore).is_err());
+        assert_eq!(purchases.count(2), 0);
+        assert!(purchases.buy(0, 500, DifficultyPreset::Hardcore).is_ok());
+    }
+
+    #[test]
+    fn test_boosts_add_up_per_purchase() {
+        let mut purchases = Purchases::default();
+        purchases.buy(0, 1000, DifficultyPreset::Normal).unwrap();
+        purchases.buy(0, 1000, DifficultyPreset::Normal).unwrap();
+        purchases.buy(2, 1000, DifficultyPreset::Normal).unwrap();
+
+        let boosts = purchases.boosts();
+        assert!((boosts.catch_width - 1.3).abs() < 1e-6);
+        assert_eq!(boosts.extra_lives, 1);
+        assert_eq!((boosts.speed, boosts.dash_distance), (1.0, 1.0));
+    }
+}
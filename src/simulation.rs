@@ -0,0 +1,865 @@
+use ggez::{graphics::Image, mint::Point2};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    adaptive::AdaptiveDifficulty,
+    consts::{
+        DASH_DISTANCE, FALLING_OBJECT_UPDATE_MILLIS, LEVEL_DURATION_SECS, OBJECT_SCALING,
+        PLAYER_SCALING, PLAYER_SPEED, TICK_RATE, WINDOW_HEIGHT, WINDOW_WIDTH,
+    },
+    difficulty::{DifficultyCurve, DifficultyPreset},
+    errors::DodgerError,
+    levels::Level,
+    modes::PlayMode,
+    objects::{FallingObject, GoodObjectValue},
+    player::{clamp_player_x, Player, PlayerState},
+    resources::Resources,
+    shop::Boosts,
+    spawn::{is_reachable, SpawnPlanner},
+    utils::{millis_to_ticks, RectSize},
+};
+
+/// The input of one player during a single simulation tick.
+///
+/// ## Fields
+/// * `axis`: requested horizontal movement from -1.0 (full speed left) to 1.0 (full speed right).
+/// * `dash`: whether the player dashes during the tick.
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlayerInput {
+    pub axis: f32,
+    pub dash: bool,
+}
+
+/// Something that happened during a simulation tick, used to play sounds and effects.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum WorldEvent {
+    /// A player caught a "good" object.
+    Caught {
+        player: usize,
+        value: Option<GoodObjectValue>,
+    },
+    /// A player collided with a "bad" object.
+    Hit { player: usize },
+    /// A "good" object fell out of the window without being caught.
+    Missed { value: Option<GoodObjectValue> },
+}
+
+/// **A player in a saved world.**
+///
+/// ## Fields
+/// * `x`: horizontal position of the player.
+/// * `direction`: direction of the player's last move, used for dashing.
+/// * `last_dash_tick`, `blink_tick`: the ticks of the player's last dash and last hit, if any.
+/// * `lives`: lives left.
+/// * `level_score`: score of the level so far.
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub struct PlayerSnapshot {
+    pub x: f32,
+    pub direction: f32,
+    pub last_dash_tick: Option<u64>,
+    pub blink_tick: Option<u64>,
+    pub lives: u8,
+    pub level_score: i32,
+}
+
+/// **A falling object in a saved world.**
+///
+/// ## Fields
+/// * `x`, `y`: position of the object.
+/// * `is_good`: whether the object is "good".
+/// * `good_object_value`: the value of a "good" object.
+/// * `hit_tick`: the tick the object was hit on, if any.
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ObjectSnapshot {
+    pub x: f32,
+    pub y: f32,
+    pub is_good: bool,
+    pub good_object_value: Option<GoodObjectValue>,
+    pub hit_tick: Option<u64>,
+}
+
+/// **The state of a world that is needed to continue playing it later.**
+///
+/// ## Fields
+/// * `tick`: number of simulation ticks since the level started.
+/// * `players`: the players taking part in the game.
+/// * `objects`: the objects currently falling.
+/// * `next_spawn_tick`: the tick of the next spawn.
+/// * `bad_credit`: accumulated share of "bad" objects.
+/// * `spawned`: number of objects spawned since the level started.
+/// * `high_value_caught`: number of `High` value objects caught since the level started.
+/// * `adaptive_factor`: the factor of the adaptive difficulty, if it is enabled.
+/// * `revived`: whether the players were revived by a continue during the level.
+///
+/// ## Behavior
+/// Sprite sizes, the level and the difficulty preset are not part of the snapshot; the state of the random
+/// number generator cannot be saved either, so a restored world spawns different objects than the original would have.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct WorldSnapshot {
+    pub tick: u64,
+    pub players: Vec<PlayerSnapshot>,
+    pub objects: Vec<ObjectSnapshot>,
+    pub next_spawn_tick: u64,
+    pub bad_credit: f32,
+    pub spawned: usize,
+    pub high_value_caught: u32,
+    pub adaptive_factor: Option<f32>,
+    #[serde(default)]
+    pub revived: bool,
+}
+
+/// **Sizes of the sprites on the screen, used for collisions.**
+///
+/// ## Fields
+/// * `player`: size of a player.
+/// * `bad`: size of a "bad" object.
+/// * `high`, `medium`, `low`: sizes of "good" objects of each value.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SpriteSizes {
+    pub player: RectSize,
+    pub bad: RectSize,
+    pub high: RectSize,
+    pub medium: RectSize,
+    pub low: RectSize,
+}
+
+impl SpriteSizes {
+    /// **Computes the sprite sizes from the images of a level.**
+    ///
+    /// ## Parameters
+    /// `resources`: the resources of the level.
+    pub fn from_resources(resources: &Resources) -> Self {
+        let object_size = |image: &Image| {
+            RectSize::from((
+                image.width() as f32 * OBJECT_SCALING,
+                image.height() as f32 * OBJECT_SCALING,
+            ))
+        };
+        // The player's hitbox is a square based on the image width.
+        let player_side = resources.player_image.width() as f32 * PLAYER_SCALING;
+
+        Self {
+            player: RectSize::from((player_side, player_side)),
+            bad: object_size(&resources.bad_object_image),
+            high: object_size(&resources.good_object_high_image),
+            medium: object_size(&resources.good_object_medium_image),
+            low: object_size(&resources.good_object_low_image),
+        }
+    }
+
+    /// **Returns the size of a falling object.**
+    ///
+    /// ## Parameters
+    /// `good_object_value`: value of the object, or `None` for "bad" objects.
+    pub fn object(&self, good_object_value: Option<GoodObjectValue>) -> RectSize {
+        match good_object_value {
+            Some(GoodObjectValue::High) | Some(GoodObjectValue::Coop) => self.high,
+            Some(GoodObjectValue::Medium) => self.medium,
+            Some(GoodObjectValue::Low) => self.low,
+            None => self.bad,
+        }
+    }
+}
+
+impl Default for SpriteSizes {
+    /// Sizes of the first level's sprites, used when no images are loaded.
+    fn default() -> Self {
+        Self {
+            player: RectSize::from((166.0, 166.0)),
+            bad: RectSize::from((81.92, 81.92)),
+            high: RectSize::from((45.6, 45.6)),
+            medium: RectSize::from((64.16, 63.2)),
+            low: RectSize::from((30.72, 32.0)),
+        }
+    }
+}
+
+/// **The deterministic state of a level being played, independent of any window or images.**
+///
+/// ## Fields
+/// * `tick`: number of simulation ticks since the level started.
+/// * `players`: the state of each player taking part in the game.
+/// * `objects`: the objects currently falling.
+/// * `play_mode`: how the players play together.
+/// * `preset`: the difficulty preset of the run.
+/// * `fall_speed`: distance the objects fall during one tick.
+/// * `sizes`: sizes of the sprites used for collisions.
+/// * `adaptive`: adjusts the fall speed and spawn interval to how well the players are doing, if enabled.
+/// * `base_fall_speed`: the level's fall speed adjusted by the preset, scaled by the level's difficulty curve.
+/// * `difficulty`: how the spawn interval, fall speed and share of "bad" objects change during the level.
+/// * `next_spawn_tick`: the tick of the next spawn.
+/// * `bad_credit`: accumulated share of "bad" objects; a "bad" object is spawned whenever it reaches 1.0.
+/// * `rng`: the seeded random number generator driving object spawns.
+/// * `spawned`: number of objects spawned since the level started; new objects are appended to `objects`.
+/// * `high_value_caught`: number of `High` value objects caught since the level started.
+/// * `extra_lives`: lives the players start the level with on top of the preset's lives, bought in the shop.
+/// * `revived`: whether the players were revived by a continue, after losing every life.
+///
+/// ## Behavior
+/// Two worlds created with the same level, play mode, sizes and seed stay identical as long as they receive the same inputs.
+#[derive(Clone, Debug)]
+pub struct World {
+    pub tick: u64,
+    pub players: Vec<PlayerState>,
+    pub objects: Vec<FallingObject>,
+    pub play_mode: PlayMode,
+    pub preset: DifficultyPreset,
+    pub fall_speed: f32,
+    pub sizes: SpriteSizes,
+    pub adaptive: Option<AdaptiveDifficulty>,
+    base_fall_speed: f32,
+    difficulty: DifficultyCurve,
+    next_spawn_tick: u64,
+    bad_credit: f32,
+    rng: StdRng,
+    spawned: usize,
+    high_value_caught: u32,
+    extra_lives: u8,
+    revived: bool,
+}
+
+impl World {
+    /// **Creates the world of a level.**
+    ///
+    /// ## Parameters
+    /// * `level`: the level being played.
+    /// * `play_mode`: how the players play together.
+    /// * `preset`: the difficulty preset of the run.
+    /// * `sizes`: sizes of the sprites used for collisions.
+    /// * `seed`: seed of the random number generator.
+    ///
+    /// ## Returns
+    /// A result containing the world with the players spread evenly along the bottom of the window with the preset's lives, or a `DodgerError` if a player cannot be created.
+    pub fn new(
+        level: &Level,
+        play_mode: PlayMode,
+        preset: DifficultyPreset,
+        sizes: SpriteSizes,
+        seed: u64,
+    ) -> Result<Self, DodgerError> {
+        let player_count = play_mode.player_count();
+        let players = (0..player_count)
+            .map(|i| {
+                let x = WINDOW_WIDTH * (i + 1) as f32 / (player_count + 1) as f32;
+                let player = Player::new(
+                    Point2::from_slice(&[
+                        x.min(WINDOW_WIDTH - sizes.player.w),
+                        WINDOW_HEIGHT - 175.0,
+                    ]),
+                    sizes.player,
+                )?;
+                let mut state = PlayerState::new(player);
+                state.lives = preset.lives();
+                Ok(state)
+            })
+            .collect::<Result<Vec<_>, DodgerError>>()?;
+
+        let mut world = Self {
+            tick: 0,
+            players,
+            objects: Vec::new(),
+            play_mode,
+            preset,
+            fall_speed: level.fall_speed,
+            sizes,
+            adaptive: None,
+            base_fall_speed: level.fall_speed * preset.fall_speed_multiplier(),
+            difficulty: level.difficulty.clone(),
+            next_spawn_tick: 0,
+            // The first object of a level is "bad".
+            bad_credit: 1.0,
+            rng: StdRng::seed_from_u64(seed),
+            spawned: 0,
+            high_value_caught: 0,
+            extra_lives: 0,
+            revived: false,
+        };
+        world.fall_speed = world.base_fall_speed * world.difficulty.fall_speed.value_at(0.0);
+        world.next_spawn_tick = world.spawn_interval_ticks();
+        Ok(world)
+    }
+
+    /// **Recreates a saved world.**
+    ///
+    /// ## Parameters
+    /// * `level`: the level being played.
+    /// * `play_mode`: how the players play together.
+    /// * `preset`: the difficulty preset of the run.
+    /// * `sizes`: sizes of the sprites used for collisions.
+    /// * `seed`: seed of the random number generator for the rest of the level.
+    /// * `boosts`: the combined effect of the upgrades bought in the saved run.
+    /// * `snapshot`: the saved state of the world.
+    ///
+    /// ## Returns
+    /// A result containing the restored world, or a `DodgerError` if the snapshot does not fit the play mode or a position is invalid.
+    ///
+    /// ## Behavior
+    /// The upgrades are applied before the saved positions, so upgraded players are restored where they were saved.
+    pub fn restore(
+        level: &Level,
+        play_mode: PlayMode,
+        preset: DifficultyPreset,
+        sizes: SpriteSizes,
+        seed: u64,
+        boosts: &Boosts,
+        snapshot: &WorldSnapshot,
+    ) -> Result<Self, DodgerError> {
+        if snapshot.players.len() != play_mode.player_count() {
+            return Err(DodgerError::IncompatibleSave(format!(
+                "{} players saved for a {}-player game",
+                snapshot.players.len(),
+                play_mode.player_count()
+            )));
+        }
+
+        let mut world = Self::new(level, play_mode, preset, sizes, seed)?;
+        for (state, saved) in world.players.iter_mut().zip(&snapshot.players) {
+            let mut player = Player::new(
+                Point2::from_slice(&[saved.x, state.player.coords.y]),
+                sizes.player,
+            )?;
+            player.direction = saved.direction;
+            player.last_dash_tick = saved.last_dash_tick;
+            player.blink_tick = saved.blink_tick;
+            state.player = player;
+            state.lives = saved.lives;
+            state.level_score = saved.level_score;
+        }
+        world.apply_boosts(boosts, false);
+        for (state, saved) in world.players.iter_mut().zip(&snapshot.players) {
+            state.player.coords.x = clamp_player_x(saved.x, state.player.size.w);
+        }
+        world.objects = snapshot
+            .objects
+            .iter()
+            .map(|saved| {
+                let mut object = FallingObject::new(
+                    Point2::from_slice(&[saved.x, saved.y]),
+                    sizes.object(saved.good_object_value),
+                    saved.is_good,
+                    saved.good_object_value,
+                )?;
+                object.hit_tick = saved.hit_tick;
+                Ok(object)
+            })
+            .collect::<Result<Vec<_>, DodgerError>>()?;
+        world.tick = snapshot.tick;
+        world.next_spawn_tick = snapshot.next_spawn_tick;
+        world.bad_credit = snapshot.bad_credit;
+        world.spawned = snapshot.spawned;
+        world.high_value_caught = snapshot.high_value_caught;
+        world.adaptive = snapshot.adaptive_factor.map(AdaptiveDifficulty::new);
+        world.revived = snapshot.revived;
+        world.fall_speed = world.base_fall_speed
+            * world.difficulty.fall_speed.value_at(world.elapsed_secs())
+            * world.adaptive_factor();
+        Ok(world)
+    }
+
+    /// **Applies the upgrades bought in the shop to the players.**
+    ///
+    /// ## Parameters
+    /// * `boosts`: the combined effect of the upgrades.
+    /// * `fresh_level`: whether the level is just starting, rather than restored from a save.
+    ///
+    /// ## Behavior
+    /// * Widens the players' catch area around their center, keeping them inside the window, and raises their speed and dash distance.
+    /// * On a fresh level, the players' lives are set to the preset's lives plus the extra lives;
+    ///   a restored world keeps the saved lives.
+    pub fn apply_boosts(&mut self, boosts: &Boosts, fresh_level: bool) {
+        self.extra_lives = boosts.extra_lives;
+        let starting_lives = self.starting_lives();
+        for state in &mut self.players {
+            let player = &mut state.player;
+            let width = self.sizes.player.w * boosts.catch_width;
+            let center = player.coords.x + player.size.w / 2.0;
+            player.size.w = width;
+            player.coords.x = clamp_player_x(center - width / 2.0, width);
+            player.speed = PLAYER_SPEED * boosts.speed;
+            player.dash_distance = DASH_DISTANCE * boosts.dash_distance;
+            if fresh_level {
+                state.lives = starting_lives;
+            }
+        }
+    }
+
+    /// **Revives the players after they lost every life, so the level can be continued.**
+    ///
+    /// ## Behavior
+    /// The players get their starting lives back and the falling objects are cleared; the score and the elapsed time are kept.
+    pub fn revive(&mut self) {
+        let starting_lives = self.starting_lives();
+        for state in &mut self.players {
+            state.lives = starting_lives;
+            state.player.blink_tick = None;
+        }
+        self.objects.clear();
+        self.revived = true;
+    }
+
+    /// **Returns the lives each player starts the level with.**
+    pub fn starting_lives(&self) -> u8 {
+        self.preset.lives().saturating_add(self.extra_lives)
+    }
+
+    /// **Captures the state of the world needed to continue playing it later.**
+    pub fn snapshot(&self) -> WorldSnapshot {
+        WorldSnapshot {
+            tick: self.tick,
+            players: self
+                .players
+                .iter()
+                .map(|state| PlayerSnapshot {
+                    x: state.player.coords.x,
+                    direction: state.player.direction,
+                    last_dash_tick: state.player.last_dash_tick,
+                    blink_tick: state.player.blink_tick,
+                    lives: state.lives,
+                    level_score: state.level_score,
+                })
+                .collect(),
+            objects: self
+                .objects
+                .iter()
+                .map(|obj| ObjectSnapshot {
+                    x: obj.coords.x,
+                    y: obj.coords.y,
+                    is_good: obj.is_good,
+                    good_object_value: obj.good_object_value,
+                    hit_tick: obj.hit_tick,
+                })
+                .collect(),
+            next_spawn_tick: self.next_spawn_tick,
+            bad_credit: self.bad_credit,
+            spawned: self.spawned,
+            high_value_caught: self.high_value_caught,
+            adaptive_factor: self.adaptive.as_ref().map(|adaptive| adaptive.factor()),
+            revived: self.revived,
+        }
+    }
+
+    /// **Advances the world by one tick.**
+    ///
+    /// ## Parameters
+    /// `inputs`: the input of each player; missing inputs are treated as idle.
+    ///
+    /// ## Returns
+    /// The events that happened during the tick.
+    ///
+    /// ## Behavior
+    /// * Moves and dashes the players who still have lives.
+    /// * Follows the level's difficulty curve: the fall speed changes over time, and objects spawn
+    ///   `FALLING_OBJECT_UPDATE_MILLIS` apart, scaled by the spawn interval curve.
+    /// * Moves the falling objects and handles collisions.
+    /// * Reports the "good" objects that fell out of the window as missed.
+    /// * Feeds catches, missed "good" objects and lost lives to the adaptive difficulty, if enabled.
+    /// * Removes objects that were caught, finished blinking or left the window.
+    pub fn step(&mut self, inputs: &[PlayerInput]) -> Vec<WorldEvent> {
+        self.tick += 1;
+
+        for (i, state) in self.players.iter_mut().enumerate() {
+            if !state.is_alive() {
+                continue;
+            }
+            let input = inputs.get(i).copied().unwrap_or_default();
+            let max_distance = state.player.speed / TICK_RATE as f32;
+            state
+                .player
+                .move_by(input.axis.clamp(-1.0, 1.0) * max_distance);
+            if input.dash {
+                state.player.dash(self.tick);
+            }
+        }
+
+        let elapsed_secs = self.elapsed_secs();
+        self.fall_speed = self.base_fall_speed
+            * self.difficulty.fall_speed.value_at(elapsed_secs)
+            * self.adaptive_factor();
+        if self.tick >= self.next_spawn_tick {
+            self.spawn_object();
+            self.next_spawn_tick = self.tick + self.spawn_interval_ticks();
+        }
+
+        for obj in &mut self.objects {
+            obj.update(self.fall_speed);
+        }
+
+        let mut events = self.handle_collisions();
+        events.extend(
+            self.objects
+                .iter()
+                .filter(|obj| obj.is_good && obj.hit_tick.is_none() && obj.coords.y > WINDOW_HEIGHT)
+                .map(|obj| WorldEvent::Missed {
+                    value: obj.good_object_value,
+                }),
+        );
+
+        let tick = self.tick;
+        if let Some(adaptive) = &mut self.adaptive {
+            for event in &events {
+                match event {
+                    WorldEvent::Caught { .. } => adaptive.record_catch(tick),
+                    WorldEvent::Hit { .. } => adaptive.record_life_lost(tick),
+                    WorldEvent::Missed { .. } => adaptive.record_miss(tick),
+                }
+            }
+            adaptive.update(tick);
+        }
+
+        self.objects.retain(|obj| {
+            let caught = obj.is_good && obj.hit_tick.is_some();
+            !(caught || obj.is_expired(tick) || obj.coords.y > WINDOW_HEIGHT)
+        });
+        events
+    }
+
+    /// **Returns the number of ticks between two spawns at the current point of the level.**
+    fn spawn_interval_ticks(&self) -> u64 {
+        let scale = (self.difficulty.spawn_interval.value_at(self.elapsed_secs())
+            * self.preset.spawn_interval_multiplier()
+            / self.adaptive_factor())
+        .max(0.0);
+        millis_to_ticks((FALLING_OBJECT_UPDATE_MILLIS as f32 * scale) as u64).max(1)
+    }
+
+    /// **Returns the factor of the adaptive difficulty, or 1.0 if it is disabled.**
+    pub fn adaptive_factor(&self) -> f32 {
+        self.adaptive
+            .as_ref()
+            .map_or(1.0, |adaptive| adaptive.factor())
+    }
+
+    /// **Returns the time between two spawns at the current point of the level, in seconds.**
+    pub fn spawn_interval_secs(&self) -> f32 {
+        self.spawn_interval_ticks() as f32 / TICK_RATE as f32
+    }
+
+    /// **Returns the number of seconds elapsed since the level started.**
+    pub fn elapsed_secs(&self) -> f32 {
+        self.tick as f32 / TICK_RATE as f32
+    }
+
+    /// **Spawns a new falling object at the top of the window.**
+    ///
+    /// ## Behavior
+    /// * The share of "bad" objects follows the level's bad ratio curve: each spawn adds the current ratio to
+    ///   `bad_credit`, and the object is "bad" once the credit reaches 1.0.
+    /// * Assigns a value to "good" objects (`High`, `Medium`, `Low`, and `Coop` in co-op games).
+    /// * The horizontal position is chosen by the `SpawnPlanner`; a "bad" object that cannot be placed fairly is postponed to the next spawn.
+    fn spawn_object(&mut self) {
+        let is_good = self.bad_credit < 1.0;
+
+        let good_object_value = if is_good {
+            match self.rng.gen_range(0..10) {
+                9 if self.play_mode == PlayMode::Coop => Some(GoodObjectValue::Coop),
+                0 => Some(GoodObjectValue::High),
+                1 | 3 | 5 => Some(GoodObjectValue::Medium),
+                _ => Some(GoodObjectValue::Low),
+            }
+        } else {
+            None
+        };
+
+        let size = self.sizes.object(good_object_value);
+        let planner = SpawnPlanner {
+            players: &self.players,
+            objects: &self.objects,
+            fall_speed: self.fall_speed,
+        };
+        let Some(x) = planner.choose_x(&mut self.rng, size, is_good) else {
+            return;
+        };
+        if let Ok(object) = FallingObject::new(
+            Point2::from_slice(&[x, 0.0]),
+            size,
+            is_good,
+            good_object_value,
+        ) {
+            self.objects.push(object);
+            self.spawned += 1;
+            if !is_good {
+                self.bad_credit -= 1.0;
+            }
+            self.bad_credit += (self.difficulty.bad_ratio.value_at(self.elapsed_secs())
+                * self.preset.bad_ratio_multiplier())
+            .clamp(0.0, 1.0);
+        }
+    }
+
+    /// **Handles collisions between the players and falling objects.**
+    ///
+    /// ## Returns
+    /// The catches and hits that happened.
+    ///
+    /// ## Behavior
+    /// * Checks for collisions between the players who still have lives and each falling object.
+    /// * If several players touch the same object, the player whose center is closest to the object's center gets it.
+    /// * Co-op objects are only caught when at least two players touch them at the same time.
+    /// * Updates the player's score if the player catches a "good" object, scaled by the preset's score multiplier.
+    /// * Reduces the player's lives if the player collides with a "bad" object; in co-op games the players share a single pool of lives.
+    fn handle_collisions(&mut self) -> Vec<WorldEvent> {
+        let mut events = Vec::new();
+        for obj in &mut self.objects {
+            if obj.hit_tick.is_some() {
+                continue;
+            }
+
+            let obj_rect = obj.rect();
+            let obj_center = obj_rect.x + obj_rect.w / 2.0;
+
+            let mut touching: Vec<(usize, f32)> = self
+                .players
+                .iter()
+                .enumerate()
+                .filter(|(_, state)| state.is_alive() && state.player.rect().overlaps(&obj_rect))
+                .map(|(i, state)| {
+                    let player_rect = state.player.rect();
+                    (i, (player_rect.x + player_rect.w / 2.0 - obj_center).abs())
+                })
+                .collect();
+            touching.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+
+            let Some(&(catcher, _)) = touching.first() else {
+                continue;
+            };
+            if obj.good_object_value == Some(GoodObjectValue::Coop) && touching.len() < 2 {
+                continue;
+            }
+
+            obj.hit_tick = Some(self.tick);
+            if obj.is_good {
+                let score = obj.good_object_value.map_or(10, |value| value.score());
+                self.players[catcher].level_score +=
+                    (score as f32 * self.preset.score_multiplier()).round() as i32;
+                if obj.good_object_value == Some(GoodObjectValue::High) {
+                    self.high_value_caught += 1;
+                }
+                events.push(WorldEvent::Caught {
+                    player: catcher,
+                    value: obj.good_object_value,
+                });
+            } else {
+                self.players[catcher].player.blink_tick = Some(self.tick);
+                if self.play_mode.shares_lives() {
+                    for state in &mut self.players {
+                        state.lives = state.lives.saturating_sub(1);
+                    }
+                } else {
+                    self.players[catcher].lives -= 1;
+                }
+                events.push(WorldEvent::Hit { player: catcher });
+            }
+        }
+        events
+    }
+
+    /// **Returns the number of objects spawned since the level started.**
+    pub fn spawned(&self) -> usize {
+        self.spawned
+    }
+
+    /// **Returns the number of `High` value objects caught since the level started.**
+    pub fn high_value_caught(&self) -> u32 {
+        self.high_value_caught
+    }
+
+    /// **Checks whether any player has lost a life since the level started, including lives given back by a continue.**
+    pub fn any_life_lost(&self) -> bool {
+        self.revived
+            || self
+                .players
+                .iter()
+                .any(|state| state.lives < self.starting_lives())
+    }
+
+    /// **Checks whether a player can still reach a falling object before it passes them.**
+    ///
+    /// ## Parameters
+    /// * `obj`: the falling object.
+    /// * `player_index`: index of the player.
+    ///
+    /// ## Returns
+    /// `true` if the player, moving at full speed without dashing, can get underneath the object before it falls past them; see `is_reachable`.
+    pub fn is_catchable(&self, obj: &FallingObject, player_index: usize) -> bool {
+        self.players.get(player_index).is_some_and(|state| {
+            let player = &state.player;
+            is_reachable(player.rect(), player.speed, obj.rect(), self.fall_speed)
+        })
+    }
+
+    /// **Returns the score of the level, summed over all players.**
+    pub fn level_score(&self) -> i32 {
+        self.players.iter().map(|state| state.level_score).sum()
+    }
+
+    /// **Checks whether the level's time is up.**
+    pub fn is_time_up(&self) -> bool {
+        self.tick >= LEVEL_DURATION_SECS * TICK_RATE
+    }
+
+    /// **Checks whether no player has lives left.**
+    pub fn is_lost(&self) -> bool {
+        self.players.iter().all(|state| !state.is_alive())
+    }
+
+    /// **Returns the remaining time of the level in whole seconds.**
+    pub fn remaining_secs(&self) -> u64 {
+        (LEVEL_DURATION_SECS * TICK_RATE).saturating_sub(self.tick) / TICK_RATE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::levels::get_levels;
+
+    fn run(seed: u64, ticks: usize) -> World {
+        let mut world = World::new(
+            &get_levels()[0],
+            PlayMode::Versus,
+            DifficultyPreset::Normal,
+            SpriteSizes::default(),
+            seed,
+        )
+        .unwrap();
+        for i in 0..ticks {
+            let axis = if (i / 90) % 2 == 0 { 1.0 } else { -1.0 };
+            let inputs = [
+                PlayerInput {
+                    axis,
+                    dash: i % 70 == 0,
+                },
+                PlayerInput {
+                    axis: -axis,
+                    dash: false,
+                },
+            ];
+            world.step(&inputs);
+        }
+        world
+    }
+
+    #[test]
+    fn test_same_seed_and_inputs_give_same_world() {
+        let first = run(7, 1200);
+        let second = run(7, 1200);
+        assert_eq!(first.tick, second.tick);
+        assert_eq!(first.objects.len(), second.objects.len());
+        for (a, b) in first.players.iter().zip(&second.players) {
+            assert_eq!(a.player.coords.x, b.player.coords.x);
+            assert_eq!((a.lives, a.level_score), (b.lives, b.level_score));
+        }
+    }
+
+    #[test]
+    fn test_difficulty_builds_up_during_the_level() {
+        let start = run(1, 0);
+        let end = run(1, (LEVEL_DURATION_SECS * TICK_RATE) as usize);
+        assert_eq!(start.fall_speed, get_levels()[0].fall_speed);
+        assert!(end.fall_speed > start.fall_speed);
+        assert!(end.spawn_interval_ticks() < start.spawn_interval_ticks());
+    }
+
+    #[test]
+    fn test_adaptive_difficulty_eases_up_for_an_idle_player() {
+        let mut world = World::new(
+            &get_levels()[3],
+            PlayMode::Single,
+            DifficultyPreset::Normal,
+            SpriteSizes::default(),
+            3,
+        )
+        .unwrap();
+        world.adaptive = Some(AdaptiveDifficulty::new(1.0));
+        for _ in 0..20 * TICK_RATE {
+            world.step(&[PlayerInput::default()]);
+        }
+        assert!(world.adaptive_factor() < 1.0);
+    }
+
+    #[test]
+    fn test_restored_world_matches_the_snapshot() {
+        let world = run(4, 900);
+        let snapshot = world.snapshot();
+        let restored = World::restore(
+            &get_levels()[0],
+            PlayMode::Versus,
+            DifficultyPreset::Normal,
+            SpriteSizes::default(),
+            99,
+            &Boosts::default(),
+            &snapshot,
+        )
+        .unwrap();
+        assert_eq!(restored.snapshot(), snapshot);
+        assert_eq!(restored.fall_speed, world.fall_speed);
+
+        let single = World::restore(
+            &get_levels()[0],
+            PlayMode::Single,
+            DifficultyPreset::Normal,
+            SpriteSizes::default(),
+            99,
+            &Boosts::default(),
+            &snapshot,
+        );
+        assert!(matches!(single, Err(DodgerError::IncompatibleSave(_))));
+    }
+
+    #[test]
+    fn test_boosts_add_lives_only_on_a_fresh_level() {
+        let mut world = run(2, 0);
+        let boosts = Boosts {
+            catch_width: 1.5,
+            extra_lives: 2,
+            ..Boosts::default()
+        };
+        world.apply_boosts(&boosts, true);
+        let state = &world.players[0];
+        assert_eq!(state.player.size.w, world.sizes.player.w * 1.5);
+        assert_eq!(state.lives, DifficultyPreset::Normal.lives() + 2);
+        assert!(!world.any_life_lost());
+
+        world.players[0].lives = 1;
+        let snapshot = world.snapshot();
+        let restored = World::restore(
+            &get_levels()[0],
+            PlayMode::Versus,
+            DifficultyPreset::Normal,
+            SpriteSizes::default(),
+            99,
+            &boosts,
+            &snapshot,
+        )
+        .unwrap();
+        assert_eq!(restored.snapshot(), snapshot);
+        assert_eq!(
+            restored.players[0].player.size.w,
+            world.players[0].player.size.w
+        );
+    }
+
+    #[test]
+    fn test_revived_players_continue_the_level() {
+        let mut world = run(5, 600);
+        for state in &mut world.players {
+            state.lives = 0;
+        }
+        assert!(world.is_lost());
+
+        world.revive();
+        assert!(!world.is_lost());
+        assert!(world.objects.is_empty());
+        assert!(world.any_life_lost());
+        assert_eq!(world.tick, 600);
+    }
+
+    #[test]
+    fn test_level_ends_after_its_duration() {
+        let world = run(1, (LEVEL_DURATION_SECS * TICK_RATE) as usize);
+        assert!(world.is_time_up());
+        assert_eq!(world.remaining_secs(), 0);
+    }
+}
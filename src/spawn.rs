@@ -0,0 +1,243 @@
+use ggez::graphics::Rect;
+use rand::Rng;
+
+use crate::{
+    consts::{TICK_RATE, WINDOW_WIDTH},
+    objects::FallingObject,
+    player::PlayerState,
+    utils::RectSize,
+};
+
+/// Number of random positions tried before the planner falls back.
+const SPAWN_ATTEMPTS: usize = 16;
+/// Minimum free space kept around a new object, in pixels.
+const SPAWN_MARGIN: f32 = 10.0;
+/// Extra width a safe gap needs besides the player's width, in pixels.
+const SAFE_GAP_MARGIN: f32 = 20.0;
+
+/// **Checks whether a player can get underneath a falling object before it passes them.**
+///
+/// ## Parameters
+/// * `player`: the player's rectangle.
+/// * `player_speed`: the player's movement speed, in pixels per second.
+/// * `obj`: the object's rectangle.
+/// * `fall_speed`: distance the object falls during one tick.
+///
+/// ## Returns
+/// `true` if the player, moving at full speed without dashing, can overlap the object horizontally before it falls past them.
+pub fn is_reachable(player: Rect, player_speed: f32, obj: Rect, fall_speed: f32) -> bool {
+    let gap = if obj.x + obj.w <= player.x {
+        player.x - (obj.x + obj.w)
+    } else if obj.x >= player.x + player.w {
+        obj.x - (player.x + player.w)
+    } else {
+        0.0
+    };
+    let travel_ticks = (gap / (player_speed / TICK_RATE as f32)).ceil();
+    let fall_ticks = (player.y + player.h - obj.y) / fall_speed.max(f32::EPSILON);
+    travel_ticks <= fall_ticks
+}
+
+/// **Chooses where new objects appear so that every level stays fair.**
+///
+/// ## Fields
+/// * `players`: the players taking part in the game.
+/// * `objects`: the objects currently falling.
+/// * `fall_speed`: distance the objects fall during one tick.
+///
+/// ## Behavior
+/// A position is accepted only if:
+/// * the whole sprite is inside the window;
+/// * the object does not overlap another object near the top of the window;
+/// * a "good" object can be reached by at least one player who still has lives;
+/// * a "bad" object leaves a gap wide enough for a player among the "bad" objects that will pass the players at the same time.
+pub struct SpawnPlanner<'a> {
+    pub players: &'a [PlayerState],
+    pub objects: &'a [FallingObject],
+    pub fall_speed: f32,
+}
+
+impl SpawnPlanner<'_> {
+    /// **Chooses the x-coordinate of a new object spawned at the top of the window.**
+    ///
+    /// ## Parameters
+    /// * `rng`: the random number generator of the level.
+    /// * `size`: size of the new object.
+    /// * `is_good`: whether the new object is "good".
+    ///
+    /// ## Returns
+    /// The x-coordinate, or `None` if a "bad" object cannot be placed fairly and should not be spawned.
+    ///
+    /// ## Behavior
+    /// Tries `SPAWN_ATTEMPTS` random positions. If none is accepted, a "good" object is placed above a random player who still has lives.
+    pub fn choose_x(&self, rng: &mut impl Rng, size: RectSize, is_good: bool) -> Option<f32> {
+        let max_x = (WINDOW_WIDTH - size.w).max(0.0);
+        for _ in 0..SPAWN_ATTEMPTS {
+            let x = rng.gen_range(0.0..=max_x);
+            if self.is_fair(Rect::new(x, 0.0, size.w, size.h), is_good) {
+                return Some(x);
+            }
+        }
+
+        if !is_good {
+            return None;
+        }
+        let alive: Vec<Rect> = self
+            .players
+            .iter()
+            .filter(|state| state.is_alive())
+            .map(|state| state.player.rect())
+            .collect();
+        if alive.is_empty() {
+            return Some(rng.gen_range(0.0..=max_x));
+        }
+        let player = alive[rng.gen_range(0..alive.len())];
+        Some((player.x + (player.w - size.w) / 2.0).clamp(0.0, max_x))
+    }
+
+    /// **Checks whether an object spawned at a position keeps the level fair.**
+    ///
+    /// ## Parameters
+    /// * `rect`: the rectangle of the new object.
+    /// * `is_good`: whether the new object is "good".
+    fn is_fair(&self, rect: Rect, is_good: bool) -> bool {
+        let padded = Rect::new(
+            rect.x - SPAWN_MARGIN,
+            rect.y - SPAWN_MARGIN,
+            rect.w + 2.0 * SPAWN_MARGIN,
+            rect.h + 2.0 * SPAWN_MARGIN,
+        );
+        if self.objects.iter().any(|obj| obj.rect().overlaps(&padded)) {
+            return false;
+        }
+
+        if is_good {
+            self.players.iter().any(|state| {
+                state.is_alive()
+                    && is_reachable(
+                        state.player.rect(),
+                        state.player.speed,
+                        rect,
+                        self.fall_speed,
+                    )
+            })
+        } else {
+            self.has_safe_gap(rect)
+        }
+    }
+
+    /// **Checks whether a player still fits between the "bad" objects if a new "bad" object is added.**
+    ///
+    /// ## Parameters
+    /// `rect`: the rectangle of the new "bad" object.
+    ///
+    /// ## Behavior
+    /// Only "bad" objects close enough to the new one to pass a player at the same time are taken into account.
+    fn has_safe_gap(&self, rect: Rect) -> bool {
+        let player_size = self
+            .players
+            .first()
+            .map_or(RectSize::from((0.0, 0.0)), |state| state.player.size);
+
+        let mut blocked: Vec<(f32, f32)> = self
+            .objects
+            .iter()
+            .filter(|obj| !obj.is_good && obj.hit_tick.is_none())
+            .map(|obj| obj.rect())
+            .filter(|obj| obj.y < rect.y + rect.h + player_size.h)
+            .map(|obj| (obj.x, obj.x + obj.w))
+            .collect();
+        blocked.push((rect.x, rect.x + rect.w));
+        blocked.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let needed = player_size.w + SAFE_GAP_MARGIN;
+        let mut free_from = 0.0;
+        for (start, end) in blocked {
+            if start - free_from >= needed {
+                return true;
+            }
+            free_from = f32::max(free_from, end);
+        }
+        WINDOW_WIDTH - free_from >= needed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        difficulty::DifficultyPreset,
+        levels::get_levels,
+        modes::PlayMode,
+        simulation::{PlayerInput, SpriteSizes, World},
+    };
+    use ggez::mint::Point2;
+
+    #[test]
+    fn test_spawned_objects_are_on_screen_and_good_ones_reachable() {
+        for seed in 0..5 {
+            let mut world = World::new(
+                &get_levels()[3],
+                PlayMode::Single,
+                DifficultyPreset::Normal,
+                SpriteSizes::default(),
+                seed,
+            )
+            .unwrap();
+            for _ in 0..1200 {
+                let spawned = world.spawned();
+                world.step(&[PlayerInput::default()]);
+                if world.spawned() == spawned {
+                    continue;
+                }
+                let obj = world.objects.last().unwrap();
+                assert!(obj.coords.x >= 0.0 && obj.coords.x + obj.size.w <= WINDOW_WIDTH);
+                if obj.is_good {
+                    assert!(world.is_catchable(obj, 0));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_faster_players_reach_farther_objects() {
+        let player = Rect::new(0.0, 500.0, 100.0, 50.0);
+        let obj = Rect::new(400.0, 0.0, 30.0, 30.0);
+        // The object passes the player after 110 ticks; 300 pixels take 120 ticks at 150 pixels per second.
+        assert!(!is_reachable(player, 150.0, obj, 5.0));
+        assert!(is_reachable(player, 300.0, obj, 5.0));
+    }
+
+    #[test]
+    fn test_bad_object_never_closes_the_last_gap() {
+        let world = World::new(
+            &get_levels()[0],
+            PlayMode::Single,
+            DifficultyPreset::Normal,
+            SpriteSizes::default(),
+            0,
+        )
+        .unwrap();
+        let size = world.sizes.bad;
+        // A row of bad objects leaves a single gap just wide enough for the player.
+        let gap = world.sizes.player.w + SAFE_GAP_MARGIN + 1.0;
+        let objects: Vec<FallingObject> = [(0.0, 300.0), (300.0 + gap, WINDOW_WIDTH)]
+            .iter()
+            .flat_map(|&(from, to)| {
+                let count = ((to - from) / size.w) as usize;
+                (0..count).map(move |i| from + i as f32 * size.w)
+            })
+            .map(|x| {
+                FallingObject::new(Point2::from_slice(&[x, 100.0]), size, false, None).unwrap()
+            })
+            .collect();
+
+        let planner = SpawnPlanner {
+            players: &world.players,
+            objects: &objects,
+            fall_speed: world.fall_speed,
+        };
+        assert!(planner.has_safe_gap(Rect::new(0.0, 0.0, size.w, size.h)));
+        assert!(!planner.has_safe_gap(Rect::new(300.0 + gap / 2.0, 0.0, size.w, size.h)));
+    }
+}
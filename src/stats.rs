@@ -0,0 +1,242 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::DodgerError,
+    objects::GoodObjectValue,
+    simulation::WorldEvent,
+    storage::{load_json, save_json},
+};
+
+/// **Number of "good" objects caught, by value.**
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CatchCounts {
+    pub high: u32,
+    pub medium: u32,
+    pub low: u32,
+    pub coop: u32,
+}
+
+impl CatchCounts {
+    /// **Returns the number of objects of a value.**
+    pub fn get(&self, value: GoodObjectValue) -> u32 {
+        match value {
+            GoodObjectValue::High => self.high,
+            GoodObjectValue::Medium => self.medium,
+            GoodObjectValue::Low => self.low,
+            GoodObjectValue::Coop => self.coop,
+        }
+    }
+
+    /// **Counts an object of a value.**
+    pub fn add(&mut self, value: GoodObjectValue) {
+        match value {
+            GoodObjectValue::High => self.high += 1,
+            GoodObjectValue::Medium => self.medium += 1,
+            GoodObjectValue::Low => self.low += 1,
+            GoodObjectValue::Coop => self.coop += 1,
+        }
+    }
+
+    /// **Adds the counts of another tally.**
+    pub fn merge(&mut self, other: &CatchCounts) {
+        self.high += other.high;
+        self.medium += other.medium;
+        self.low += other.low;
+        self.coop += other.coop;
+    }
+
+    /// **Returns the number of objects of all values.**
+    pub fn total(&self) -> u32 {
+        self.high + self.medium + self.low + self.coop
+    }
+}
+
+/// **What happened during the level being played, counted from the events of the simulation.**
+///
+/// ## Fields
+/// * `ticks`: number of simulation ticks played.
+/// * `caught`: "good" objects caught, by value.
+/// * `bad_hits`: collisions with "bad" objects.
+/// * `misses`: "good" objects that fell out of the window.
+/// * `combo`: number of "good" objects caught in a row, without missing one or being hit.
+/// * `best_combo`: the longest combo of the level.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct LevelTally {
+    pub ticks: u64,
+    pub caught: CatchCounts,
+    pub bad_hits: u32,
+    pub misses: u32,
+    pub combo: u32,
+    pub best_combo: u32,
+}
+
+impl LevelTally {
+    /// **Counts a simulation tick and the events that happened during it.**
+    pub fn record_tick(&mut self, events: &[WorldEvent]) {
+        self.ticks += 1;
+        for event in events {
+            match event {
+                WorldEvent::Caught { value, .. } => {
+                    // Plain "good" objects without a value are worth as much as `Low` ones.
+                    self.caught.add(value.unwrap_or(GoodObjectValue::Low));
+                    self.combo += 1;
+                    self.best_combo = self.best_combo.max(self.combo);
+                }
+                WorldEvent::Hit { .. } => {
+                    self.bad_hits += 1;
+                    self.combo = 0;
+                }
+                WorldEvent::Missed { .. } => {
+                    self.misses += 1;
+                    self.combo = 0;
+                }
+            }
+        }
+    }
+}
+
+/// **Catches and misses on a level, over all games.**
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LevelStats {
+    pub caught: u32,
+    pub missed: u32,
+}
+
+impl LevelStats {
+    /// **Returns the share of "good" objects caught, or `None` if no object was caught or missed yet.**
+    pub fn accuracy(&self) -> Option<f32> {
+        let attempts = self.caught + self.missed;
+        (attempts > 0).then(|| self.caught as f32 / attempts as f32)
+    }
+}
+
+/// **Lifetime statistics of a profile, persisted between game sessions.**
+///
+/// ## Fields
+/// * `games_played`: number of single-player and co-op runs started.
+/// * `ticks_played`: number of simulation ticks played in those runs.
+/// * `caught`: "good" objects caught, by value.
+/// * `bad_hits`: collisions with "bad" objects.
+/// * `misses`: "good" objects that fell out of the window.
+/// * `best_combo`: the most "good" objects caught in a row within a level.
+/// * `levels_completed`: number of levels completed.
+/// * `levels`: catches and misses of each level, by level index; levels beyond its end were never played.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Statistics {
+    pub games_played: u32,
+    pub ticks_played: u64,
+    pub caught: CatchCounts,
+    pub bad_hits: u32,
+    pub misses: u32,
+    pub best_combo: u32,
+    pub levels_completed: u32,
+    pub levels: Vec<LevelStats>,
+}
+
+impl Statistics {
+    /// **Returns the path of the statistics file in a directory.**
+    pub fn path(dir: &Path) -> PathBuf {
+        dir.join("stats.json")
+    }
+
+    /// **Loads the statistics stored in a directory.**
+    ///
+    /// ## Parameters
+    /// `dir`: directory containing the statistics file.
+    ///
+    /// ## Returns
+    /// A result containing the stored statistics (or empty statistics if nothing is stored yet), or a `DodgerError` if the file cannot be read or parsed.
+    pub fn load(dir: &Path) -> Result<Self, DodgerError> {
+        Ok(load_json(&Self::path(dir))?.unwrap_or_default())
+    }
+
+    /// **Stores the statistics in a directory.**
+    ///
+    /// ## Parameters
+    /// `dir`: directory to store the statistics file in.
+    ///
+    /// ## Returns
+    /// `Ok(())` if the statistics are stored successfully, or a `DodgerError` if the file cannot be written.
+    pub fn save(&self, dir: &Path) -> Result<(), DodgerError> {
+        save_json(&Self::path(dir), self)
+    }
+
+    /// **Returns the catches and misses of a level.**
+    pub fn level(&self, level_index: usize) -> LevelStats {
+        self.levels.get(level_index).copied().unwrap_or_default()
+    }
+
+    /// **Adds a played level to the statistics.**
+    ///
+    /// ## Parameters
+    /// * `level_index`: index of the level.
+    /// * `tally`: what happened while the level was played.
+    /// * `completed`: whether the level was completed, rather than lost, failed or left.
+    pub fn record_level(&mut self, level_index: usize, tally: &LevelTally, completed: bool) {
+        self.ticks_played += tally.ticks;
+        self.caught.merge(&tally.caught);
+        self.bad_hits += tally.bad_hits;
+        self.misses += tally.misses;
+        self.best_combo = self.best_combo.max(tally.best_combo);
+        if completed {
+            self.levels_completed += 1;
+        }
+
+        if self.levels.len() <= level_index {
+            self.levels.resize(level_index + 1, LevelStats::default());
+        }
+        let level = &mut self.levels[level_index];
+        level.caught += tally.caught.total();
+        level.missed += tally.misses;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tally_counts_combos_until_a_miss_or_hit() {
+        let caught = |value| WorldEvent::Caught {
+            player: 0,
+            value: Some(value),
+        };
+        let mut tally = LevelTally::default();
+        tally.record_tick(&[caught(GoodObjectValue::High), caught(GoodObjectValue::Low)]);
+        tally.record_tick(&[WorldEvent::Missed {
+            value: Some(GoodObjectValue::Medium),
+        }]);
+        tally.record_tick(&[caught(GoodObjectValue::Low)]);
+        tally.record_tick(&[WorldEvent::Hit { player: 0 }]);
+
+        assert_eq!(tally.ticks, 4);
+        assert_eq!(tally.caught.get(GoodObjectValue::Low), 2);
+        assert_eq!(tally.caught.total(), 3);
+        assert_eq!((tally.misses, tally.bad_hits), (1, 1));
+        assert_eq!((tally.combo, tally.best_combo), (0, 2));
+    }
+
+    #[test]
+    fn test_levels_accumulate_per_level_accuracy() {
+        let mut tally = LevelTally::default();
+        tally.record_tick(&[WorldEvent::Caught {
+            player: 0,
+            value: Some(GoodObjectValue::High),
+        }]);
+        tally.record_tick(&[WorldEvent::Missed { value: None }]);
+
+        let mut stats = Statistics::default();
+        stats.record_level(2, &tally, true);
+        stats.record_level(2, &tally, false);
+        assert_eq!(stats.levels_completed, 1);
+        assert_eq!(stats.caught.high, 2);
+        assert_eq!(stats.level(0).accuracy(), None);
+        assert_eq!(stats.level(2).accuracy(), Some(0.5));
+        assert_eq!(stats.ticks_played, 4);
+    }
+}
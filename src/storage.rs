@@ -0,0 +1,53 @@
+use std::{fs, io::ErrorKind, path::Path};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::errors::DodgerError;
+
+/// **Loads a value stored as JSON.**
+///
+/// ## Parameters
+/// `path`: path to the JSON file.
+///
+/// ## Returns
+/// * `Ok(Some(value))` if the file exists and is parsed successfully.
+/// * `Ok(None)` if the file does not exist yet.
+/// * A `DodgerError` if the file cannot be read or parsed.
+pub fn load_json<T: DeserializeOwned>(path: &Path) -> Result<Option<T>, DodgerError> {
+    let path_name = path.display().to_string();
+
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(DodgerError::ReadFile(path_name, err.to_string())),
+    };
+
+    serde_json::from_str(&contents)
+        .map(Some)
+        .map_err(|err| DodgerError::ParseFile(path_name, err.to_string()))
+}
+
+/// **Stores a value as pretty-printed JSON.**
+///
+/// ## Parameters
+/// * `path`: path to the JSON file.
+/// * `value`: the value to store.
+///
+/// ## Behavior
+/// Missing parent directories are created.
+///
+/// ## Returns
+/// `Ok(())` if the value is stored successfully, or a `DodgerError` if the file cannot be written.
+pub fn save_json<T: Serialize>(path: &Path, value: &T) -> Result<(), DodgerError> {
+    let path_name = path.display().to_string();
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|err| DodgerError::WriteFile(path_name.clone(), err.to_string()))?;
+    }
+
+    let contents = serde_json::to_string_pretty(value)
+        .map_err(|err| DodgerError::WriteFile(path_name.clone(), err.to_string()))?;
+
+    fs::write(path, contents).map_err(|err| DodgerError::WriteFile(path_name, err.to_string()))
+}
@@ -0,0 +1,204 @@
+use crate::{
+    consts::TICK_RATE, difficulty::DifficultyPreset, objects::GoodObjectValue, stats::LevelTally,
+};
+
+/// Points for each second left when the level's objective was met, before the preset's score multiplier.
+pub const TIME_BONUS_PER_SEC: i32 = 2;
+
+/// Points for each life left at the end of the level, before the preset's score multiplier.
+pub const LIFE_BONUS: i32 = 25;
+
+/// Seconds it takes to count up one line of the summary.
+pub const TALLY_LINE_SECS: f32 = 0.4;
+
+/// **The results of a completed level, counted up line by line on the summary screen.**
+///
+/// ## Fields
+/// * `tally`: what happened during the level.
+/// * `time_bonus`: points for the seconds left when the objective was met.
+/// * `lives_bonus`: points for the lives left.
+/// * `level_score`: the level score, without the bonuses.
+/// * `total_score`: the total score of the run, including the level and the bonuses.
+/// * `stars`: the stars earned on the level.
+/// * `victory`: whether the level was the last one, so the victory screen follows.
+/// * `elapsed_secs`: time the summary has been shown, driving the animation.
+#[derive(Clone, PartialEq, Debug)]
+pub struct LevelSummary {
+    pub tally: LevelTally,
+    pub time_bonus: i32,
+    pub lives_bonus: i32,
+    pub level_score: i32,
+    pub total_score: i32,
+    pub stars: u8,
+    pub victory: bool,
+    pub elapsed_secs: f32,
+}
+
+/// **Returns the bonuses for completing a level.**
+///
+/// ## Parameters
+/// * `remaining_secs`: seconds left when the objective was met.
+/// * `lives`: lives left at the end of the level.
+/// * `preset`: the difficulty preset of the run; the bonuses are scaled by its score multiplier.
+///
+/// ## Returns
+/// The time bonus and the lives bonus.
+pub fn level_bonuses(remaining_secs: u64, lives: u8, preset: DifficultyPreset) -> (i32, i32) {
+    (
+        preset.scaled_score(remaining_secs as i32 * TIME_BONUS_PER_SEC),
+        preset.scaled_score(i32::from(lives) * LIFE_BONUS),
+    )
+}
+
+impl LevelSummary {
+    /// **Returns the lines of the summary with the value each one counts up to.**
+    pub fn lines(&self) -> Vec<(String, i32)> {
+        let mut lines: Vec<(String, i32)> = GoodObjectValue::ALL
+            .iter()
+            .map(|value| {
+                (
+                    format!("{} caught", value.label()),
+                    self.tally.caught.get(*value) as i32,
+                )
+            })
+            .collect();
+        lines.extend([
+            ("Bad hits".to_string(), self.tally.bad_hits as i32),
+            ("Missed".to_string(), self.tally.misses as i32),
+            ("Best combo".to_string(), self.tally.best_combo as i32),
+            ("Time bonus".to_string(), self.time_bonus),
+            ("Lives bonus".to_string(), self.lives_bonus),
+            ("Level score".to_string(), self.level_score),
+            ("Total score".to_string(), self.total_score),
+        ]);
+        lines
+    }
+
+    /// **Returns the value a line shows at the current point of the animation.**
+    ///
+    /// ## Parameters
+    /// * `index`: index of the line.
+    /// * `value`: the value the line counts up to.
+    ///
+    /// ## Returns
+    /// `None` while the line is not shown yet, otherwise the value counted up so far.
+    pub fn shown_value(&self, index: usize, value: i32) -> Option<i32> {
+        let progress = self.elapsed_secs / TALLY_LINE_SECS - index as f32;
+        (progress >= 0.0).then(|| (value as f32 * progress.min(1.0)).round() as i32)
+    }
+
+    /// **Checks whether every line is counted up and the stars are shown.**
+    pub fn is_finished(&self) -> bool {
+        self.elapsed_secs >= self.lines().len() as f32 * TALLY_LINE_SECS
+    }
+
+    /// **Ends the animation, showing every line with its final value.**
+    pub fn skip(&mut self) {
+        self.elapsed_secs = self.lines().len() as f32 * TALLY_LINE_SECS;
+    }
+}
+
+/// **How a run ended on the "Game Over" screen.**
+///
+/// ## Fields
+/// * `final_score`: the total score of the run.
+/// * `level`: index of the level the run ended on.
+/// * `cause`: what cost the last life.
+/// * `tick`: the tick of the level the last life was lost on.
+/// * `difficulty`: the difficulty preset of the run.
+/// * `previous_best`: the best score on the preset before the run, if there was one.
+/// * `ranked`: whether the run counts for the high scores.
+#[derive(Clone, PartialEq, Debug)]
+pub struct GameOverReport {
+    pub final_score: i32,
+    pub level: usize,
+    pub cause: String,
+    pub tick: u64,
+    pub difficulty: DifficultyPreset,
+    pub previous_best: Option<i32>,
+    pub ranked: bool,
+}
+
+impl GameOverReport {
+    /// **Returns the time into the level the last life was lost, as minutes and seconds.**
+    pub fn time_of_death(&self) -> String {
+        let secs = self.tick / TICK_RATE;
+        format!("{}:{:02}", secs / 60, secs % 60)
+    }
+
+    /// **Returns how the final score compares with the personal best on the run's preset.**
+    pub fn best_comparison(&self) -> String {
+        let preset = self.difficulty.name();
+        match self.previous_best {
+            _ if !self.ranked => "Not ranked".to_string(),
+            None => format!("First score on {}!", preset),
+            Some(best) if self.final_score > best => {
+                format!("New best on {}! Previous: {}", preset, best)
+            }
+            Some(best) => format!(
+                "Best on {}: {} ({} to go)",
+                preset,
+                best,
+                best - self.final_score
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bonuses_scale_with_the_preset() {
+        assert_eq!(level_bonuses(10, 3, DifficultyPreset::Normal), (20, 75));
+        assert_eq!(level_bonuses(0, 1, DifficultyPreset::Hardcore), (0, 50));
+    }
+
+    #[test]
+    fn test_lines_count_up_one_after_another() {
+        let mut summary = LevelSummary {
+            tally: LevelTally::default(),
+            time_bonus: 40,
+            lives_bonus: 100,
+            level_score: 200,
+            total_score: 500,
+            stars: 2,
+            victory: false,
+            elapsed_secs: 0.0,
+        };
+        let lines = summary.lines();
+        let time_bonus = lines.iter().position(|(label, _)| label == "Time bonus");
+        let time_bonus = time_bonus.unwrap();
+        assert_eq!(summary.shown_value(time_bonus, 40), None);
+
+        summary.elapsed_secs = (time_bonus as f32 + 0.5) * TALLY_LINE_SECS;
+        assert_eq!(summary.shown_value(time_bonus, 40), Some(20));
+        assert_eq!(summary.shown_value(time_bonus + 1, 100), None);
+        assert!(!summary.is_finished());
+
+        summary.skip();
+        assert!(summary.is_finished());
+        assert_eq!(summary.shown_value(lines.len() - 1, 500), Some(500));
+    }
+
+    #[test]
+    fn test_game_over_compares_with_the_previous_best() {
+        let mut report = GameOverReport {
+            final_score: 250,
+            level: 2,
+            cause: "Hit by a bad object".to_string(),
+            tick: 75 * TICK_RATE,
+            difficulty: DifficultyPreset::Hard,
+            previous_best: Some(300),
+            ranked: true,
+        };
+        assert_eq!(report.time_of_death(), "1:15");
+        assert_eq!(report.best_comparison(), "Best on Hard: 300 (50 to go)");
+
+        report.previous_best = Some(200);
+        assert_eq!(report.best_comparison(), "New best on Hard! Previous: 200");
+        report.ranked = false;
+        assert_eq!(report.best_comparison(), "Not ranked");
+    }
+}
@@ -1,11 +1,11 @@
 use crate::{
     buttons::{DrawText, IconButton, TextButton},
-    consts::{TEXT_SIZE, WINDOW_HEIGHT, WINDOW_WIDTH, YELLOW},
+    consts::{BUTTON_TEXT_SIZE, TEXT_SIZE, TOAST_SECS, WINDOW_HEIGHT, WINDOW_WIDTH, YELLOW},
     errors::DodgerError,
     utils::{text_button_rect, validate_coordinates, RectSize},
 };
 use ggez::{
-    graphics::{Canvas, Color, DrawMode, DrawParam, Drawable, Image, Mesh},
+    graphics::{Canvas, Color, DrawMode, DrawParam, Drawable, Image, Mesh, Rect},
     mint::{Point2, Vector2},
     Context,
 };
@@ -50,6 +50,79 @@ pub fn draw_text(canvas: &mut Canvas, text: DrawText) -> Result<(), DodgerError>
     Ok(())
 }
 
+/// **A short notification shown over any screen for `TOAST_SECS` seconds.**
+///
+/// ## Fields
+/// * `text`: the text of the notification.
+/// * `remaining_secs`: how long the notification is still shown.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Toast {
+    pub text: String,
+    pub remaining_secs: f32,
+}
+
+impl Toast {
+    /// **Creates a notification shown for `TOAST_SECS` seconds.**
+    pub fn new(text: String) -> Self {
+        Self {
+            text,
+            remaining_secs: TOAST_SECS,
+        }
+    }
+}
+
+/// **Draws a notification at the top of the window.**
+///
+/// ## Parameters
+/// * `ctx`: the game context.
+/// * `canvas`: canvas to draw the notification on.
+/// * `toast`: the notification.
+///
+/// ## Returns
+/// `Ok(())` if the notification is drawn successfully, or a `DodgerError` if the panel or text cannot be drawn.
+///
+/// ## Behavior
+/// The text is centered on a dark panel with a yellow frame.
+pub fn draw_toast(
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    toast: &Toast,
+) -> Result<(), DodgerError> {
+    let panel_rect = Rect::new(WINDOW_WIDTH / 2.0 - 250.0, 70.0, 500.0, 50.0);
+    let panel = Mesh::new_rectangle(
+        &ctx.gfx,
+        DrawMode::fill(),
+        panel_rect,
+        Color::new(0.0, 0.0, 0.0, 0.8),
+    )
+    .map_err(|err| DodgerError::BuildRect(err.to_string()))?;
+    let frame = Mesh::new_rectangle(&ctx.gfx, DrawMode::stroke(2.0), panel_rect, YELLOW)
+        .map_err(|err| DodgerError::BuildRect(err.to_string()))?;
+    canvas.draw(&panel, DrawParam::default());
+    canvas.draw(&frame, DrawParam::default());
+
+    let text = DrawText::new(
+        Point2 {
+            x: panel_rect.x,
+            y: panel_rect.y,
+        },
+        toast.text.clone(),
+        "text_font".to_string(),
+        BUTTON_TEXT_SIZE,
+        Color::WHITE,
+    )?;
+    if let Some(text_size) = text.text.dimensions(ctx) {
+        canvas.draw(
+            &text.text,
+            DrawParam::default().dest(Point2 {
+                x: panel_rect.x + (panel_rect.w - text_size.w) / 2.0,
+                y: panel_rect.y + (panel_rect.h - text_size.h) / 2.0,
+            }),
+        );
+    }
+    Ok(())
+}
+
 /// **Draws a timer on the canvas, formatted as "00:SS".**
 ///
 /// ## Parameters
@@ -219,3 +292,116 @@ pub fn draw_ellipse_with_text(
     }
     Ok(())
 }
+
+/// **Draws a frame around a focused button.**
+///
+/// ## Parameters
+/// * `ctx`: the game context.
+/// * `canvas`: canvas to draw the frame on.
+/// * `button_rect`: the rectangle representing the button's boundaries.
+///
+/// ## Returns
+/// `Ok(())` if the frame is drawn successfully, or a `DodgerError` if the rectangle cannot be built.
+///
+/// ## Behavior
+/// The frame is drawn slightly outside the button so it stays visible on any button color.
+pub fn draw_focus_frame(
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    button_rect: Rect,
+) -> Result<(), DodgerError> {
+    let frame_rect = Rect::new(
+        button_rect.x - 4.0,
+        button_rect.y - 4.0,
+        button_rect.w + 8.0,
+        button_rect.h + 8.0,
+    );
+
+    let frame = Mesh::new_rectangle(&ctx.gfx, DrawMode::stroke(4.0), frame_rect, YELLOW)
+        .map_err(|err| DodgerError::BuildRect(err.to_string()))?;
+
+    canvas.draw(&frame, DrawParam::default());
+    Ok(())
+}
+
+/// **Draws a five-pointed star, used for level ratings.**
+///
+/// ## Parameters
+/// * `ctx`: the game context.
+/// * `canvas`: canvas to draw the star on.
+/// * `center`: center of the star.
+/// * `radius`: distance from the center to the tips of the star.
+/// * `earned`: whether the star is earned; earned stars are filled, the others only outlined.
+///
+/// ## Returns
+/// `Ok(())` if the star is drawn successfully, or a `DodgerError` if the polygon cannot be built.
+pub fn draw_star(
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    center: Point2<f32>,
+    radius: f32,
+    earned: bool,
+) -> Result<(), DodgerError> {
+    let points: Vec<Point2<f32>> = (0..10)
+        .map(|i| {
+            let distance = if i % 2 == 0 { radius } else { radius * 0.45 };
+            let angle = std::f32::consts::PI * (i as f32 / 5.0 - 0.5);
+            Point2 {
+                x: center.x + distance * angle.cos(),
+                y: center.y + distance * angle.sin(),
+            }
+        })
+        .collect();
+    let mode = if earned {
+        DrawMode::fill()
+    } else {
+        DrawMode::stroke(2.0)
+    };
+
+    let star = Mesh::new_polygon(&ctx.gfx, mode, &points, YELLOW)
+        .map_err(|err| DodgerError::BuildPolygon(err.to_string()))?;
+    canvas.draw(&star, DrawParam::default());
+    Ok(())
+}
+
+/// **Draws a padlock, used for locked levels.**
+///
+/// ## Parameters
+/// * `ctx`: the game context.
+/// * `canvas`: canvas to draw the padlock on.
+/// * `coords`: top-left corner of the padlock, which is 30 pixels wide and 36 pixels high.
+///
+/// ## Returns
+/// `Ok(())` if the padlock is drawn successfully, or a `DodgerError` if its shapes cannot be built.
+///
+/// ## Behavior
+/// The shackle is drawn as a ring whose lower half is covered by the body.
+pub fn draw_lock(
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    coords: Point2<f32>,
+) -> Result<(), DodgerError> {
+    let shackle = Mesh::new_circle(
+        &ctx.gfx,
+        DrawMode::stroke(4.0),
+        Point2 {
+            x: coords.x + 15.0,
+            y: coords.y + 14.0,
+        },
+        10.0,
+        0.1,
+        Color::WHITE,
+    )
+    .map_err(|err| DodgerError::BuildEllipse(err.to_string()))?;
+    let body = Mesh::new_rectangle(
+        &ctx.gfx,
+        DrawMode::fill(),
+        Rect::new(coords.x, coords.y + 14.0, 30.0, 22.0),
+        Color::WHITE,
+    )
+    .map_err(|err| DodgerError::BuildRect(err.to_string()))?;
+
+    canvas.draw(&shackle, DrawParam::default());
+    canvas.draw(&body, DrawParam::default());
+    Ok(())
+}
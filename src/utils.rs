@@ -8,14 +8,14 @@ use ggez::{
 use crate::{
     buttons::{IconButton, TextButton},
     consts::{
-        BUTTON_SPACING, BUTTON_TEXT_SIZE, OBJECT_SCALING, PLAYER_SCALING, TEXT_BUTTON_HEIGHT,
-        TEXT_BUTTON_WIDTH, WINDOW_HEIGHT, WINDOW_WIDTH,
+        BUTTON_SPACING, BUTTON_TEXT_SIZE, GO_SECS, OBJECT_SCALING, PLAYER_SCALING,
+        TEXT_BUTTON_HEIGHT, TEXT_BUTTON_WIDTH, TICK_RATE, WINDOW_HEIGHT, WINDOW_WIDTH,
     },
     errors::DodgerError,
 };
 
 /// **Size of a rectangle (width and height)**
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub struct RectSize {
     pub w: f32,
     pub h: f32,
@@ -108,6 +108,38 @@ pub fn get_level_button(
     )
 }
 
+/// **Creates a button for changing the bindings of an action on the controls screen.**
+///
+/// ## Parameters
+/// * `action_index`: index of the action in the list of actions (0-based).
+/// * `start_y`: vertical starting point for the first button in the set.
+/// * `line`: text string to be displayed on the button.
+/// * `font`: font to be used for the button text.
+///
+/// ## Returns
+/// A result containing a new `TextButton` instance, or a `DodgerError` if creation fails.
+pub fn get_rebind_button(
+    action_index: usize,
+    start_y: f32,
+    line: String,
+    font: String,
+) -> Result<TextButton, DodgerError> {
+    let button_coords = Point2::from_slice(&[
+        WINDOW_WIDTH - TEXT_BUTTON_WIDTH - 150.0,
+        start_y + (action_index as f32 * (TEXT_BUTTON_HEIGHT + BUTTON_SPACING)),
+    ]);
+
+    TextButton::new(
+        button_coords,
+        Color::WHITE,
+        text_button_rectsize(),
+        line,
+        Color::BLACK,
+        BUTTON_TEXT_SIZE,
+        font,
+    )
+}
+
 /// **Returns the default scaling vector for the player.**
 pub fn player_scaling() -> Vector2<f32> {
     Vector2::from_slice(&[PLAYER_SCALING, PLAYER_SCALING])
@@ -118,6 +150,26 @@ pub fn object_scaling() -> Vector2<f32> {
     Vector2::from_slice(&[OBJECT_SCALING, OBJECT_SCALING])
 }
 
+/// **Returns the text of the countdown before a level starts.**
+///
+/// ## Parameters
+/// `remaining_secs`: seconds left until the level starts.
+///
+/// ## Returns
+/// The whole seconds left of the countdown ("3", "2", "1"), or "Go!" during the last `GO_SECS`.
+pub fn countdown_label(remaining_secs: f32) -> String {
+    if remaining_secs > GO_SECS {
+        ((remaining_secs - GO_SECS).ceil() as u32).to_string()
+    } else {
+        "Go!".to_string()
+    }
+}
+
+/// **Converts a duration in milliseconds to a number of simulation ticks.**
+pub fn millis_to_ticks(millis: u64) -> u64 {
+    millis * TICK_RATE / 1000
+}
+
 /// **Returns a scaling vector representing half scaling.**
 pub fn half_scaling() -> Vector2<f32> {
     Vector2::from_slice(&[0.5, 0.5])
@@ -250,4 +302,12 @@ mod tests {
             Err(e) => panic!("Unexpected error: {:?}", e),
         }
     }
+
+    #[test]
+    fn test_countdown_label() {
+        assert_eq!(countdown_label(3.5), "3");
+        assert_eq!(countdown_label(2.4), "2");
+        assert_eq!(countdown_label(0.6), "1");
+        assert_eq!(countdown_label(0.5), "Go!");
+    }
 }